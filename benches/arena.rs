@@ -0,0 +1,36 @@
+#![feature(test)]
+
+extern crate test;
+
+use custom_dst::{arena::DstArena, Dst};
+use test::{black_box, Bencher};
+
+const RECORDS: usize = 10_000;
+
+fn footer_len(record: usize) -> usize {
+    record % 17
+}
+
+#[bench]
+fn per_record_dst_new(b: &mut Bencher) {
+    b.iter(|| {
+        let records = (0..RECORDS)
+            .map(|record| Dst::new_with(record as u32, footer_len(record), |i| i as u16))
+            .collect::<Vec<_>>();
+
+        black_box(records);
+    });
+}
+
+#[bench]
+fn arena_alloc(b: &mut Bencher) {
+    let mut arena = DstArena::new();
+
+    b.iter(|| {
+        for record in 0..RECORDS {
+            black_box(arena.alloc(record as u32, (0..footer_len(record)).map(|i| i as u16)));
+        }
+
+        arena.reset();
+    });
+}
@@ -0,0 +1,33 @@
+#![feature(test)]
+
+extern crate test;
+
+use custom_dst::DstArray;
+use test::{black_box, Bencher};
+
+const RECORDS: usize = 100_000;
+
+fn records() -> DstArray<u64, u32> {
+    DstArray::from_fn(12, RECORDS, |record| (record as u64, [record as u32; 12]))
+}
+
+#[bench]
+fn sum_headers_indexed(b: &mut Bencher) {
+    let arr = records();
+
+    b.iter(|| {
+        let mut sum = 0;
+        for arr_index in 0..arr.len() {
+            sum += *arr.get_header_ref(arr_index);
+        }
+
+        black_box(sum);
+    });
+}
+
+#[bench]
+fn sum_headers_iter(b: &mut Bencher) {
+    let arr = records();
+
+    b.iter(|| black_box(arr.iter_headers().sum::<u64>()));
+}
@@ -0,0 +1,37 @@
+#![feature(test)]
+
+extern crate test;
+
+use custom_dst::DstArray;
+use test::{black_box, Bencher};
+
+const RECORDS: usize = 1_000;
+const FOOTER_LEN: usize = 512;
+
+fn records() -> DstArray<u64, u64> {
+    DstArray::from_fn(FOOTER_LEN, RECORDS, |record| {
+        let key = (record as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 40;
+
+        (key, [record as u64; FOOTER_LEN])
+    })
+}
+
+#[bench]
+fn sort_unstable_large_footers(b: &mut Bencher) {
+    b.iter(|| {
+        let mut arr = records();
+        arr.sort_unstable_by_key(|member| *member.get_header());
+
+        black_box(arr);
+    });
+}
+
+#[bench]
+fn sort_by_cached_key_large_footers(b: &mut Bencher) {
+    b.iter(|| {
+        let mut arr = records();
+        arr.sort_by_cached_key(|member| *member.get_header());
+
+        black_box(arr);
+    });
+}
@@ -0,0 +1,41 @@
+#![feature(test)]
+
+extern crate test;
+
+use custom_dst::DstArray;
+use test::{black_box, Bencher};
+
+const RECORDS: usize = 100_000;
+
+fn records() -> DstArray<u64, u32> {
+    DstArray::from_fn(12, RECORDS, |record| (record as u64, [record as u32; 12]))
+}
+
+#[bench]
+fn sum_last_footer_element_checked(b: &mut Bencher) {
+    let arr = records();
+
+    b.iter(|| {
+        let mut sum = 0u64;
+        for arr_index in 0..arr.len() {
+            sum += u64::from(arr[arr_index].get_footer()[11]);
+        }
+
+        black_box(sum);
+    });
+}
+
+#[bench]
+fn sum_last_footer_element_unchecked(b: &mut Bencher) {
+    let arr = records();
+
+    b.iter(|| {
+        let mut sum = 0u64;
+        for arr_index in 0..arr.len() {
+            let footer = unsafe { arr.get_unchecked(arr_index) }.get_footer();
+            sum += u64::from(unsafe { *footer.get_unchecked(11) });
+        }
+
+        black_box(sum);
+    });
+}
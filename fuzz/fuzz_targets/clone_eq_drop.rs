@@ -0,0 +1,28 @@
+#![no_main]
+
+//!Clones, compares and drops generated Dsts and DstArrays
+//!
+//!Run with `cargo +nightly fuzz run clone_eq_drop` from the crate root
+
+use custom_dst::{Dst, DstArray};
+use libfuzzer_sys::{
+    arbitrary::{Arbitrary, Unstructured},
+    fuzz_target,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let mut u = Unstructured::new(data);
+
+    if let Ok(dst) = Dst::<String, Vec<u8>>::arbitrary(&mut u) {
+        let clone = dst.clone();
+        assert!(clone == dst);
+        assert!(clone.get_footer_len() == dst.get_footer_len());
+    }
+
+    if let Ok(arr) = DstArray::<u16, String>::arbitrary(&mut u) {
+        let clone = arr.clone();
+        assert!(clone.len() == arr.len());
+        assert!(clone.get_footer_len() == arr.get_footer_len());
+        assert!(clone.iter().zip(arr.iter()).all(|(a, b)| a == b));
+    }
+});
@@ -0,0 +1,169 @@
+//!`Arbitrary` implementations for fuzzing, enabled by the `arbitrary` feature
+//!
+//!Values are built through the same safe constructors callers use, so a fuzz target that
+//!generates them also exercises the initialization paths. Footer lengths and array sizes are
+//!bounded by the amount of data left, the same way `arbitrary` bounds the length of a `Vec`
+//!
+//!The `fuzz` directory holds a `cargo fuzz` target that clones, compares and drops them
+
+use arbitrary::{size_hint, Arbitrary, Error, MaxRecursionReached, Result, Unstructured};
+
+use crate::{Dst, DstArray, MaybeUninitDstArray};
+
+///The number of bytes `T` is expected to take, as `Unstructured::arbitrary_len` estimates it
+fn expected_size<'a, T: Arbitrary<'a>>() -> usize {
+    let (lower, upper) = T::size_hint(0);
+
+    upper.unwrap_or(lower.saturating_mul(2))
+}
+
+impl<'a, H: Arbitrary<'a>, F: Arbitrary<'a>> Arbitrary<'a> for Dst<H, F> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let len = u.arbitrary_len::<F>()?;
+        let header = H::arbitrary(u)?;
+
+        Dst::try_new_with(header, len, |_| F::arbitrary(u))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Self::try_size_hint(depth).unwrap_or_default()
+    }
+
+    fn try_size_hint(depth: usize) -> Result<(usize, Option<usize>), MaxRecursionReached> {
+        size_hint::try_recursion_guard(depth, |depth| {
+            Ok(size_hint::and(H::try_size_hint(depth)?, (0, None)))
+        })
+    }
+}
+
+impl<'a, H: Arbitrary<'a>, F: Arbitrary<'a>> Arbitrary<'a> for DstArray<H, F> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let footer_len = u.arbitrary_len::<F>()?;
+        let member_size = expected_size::<F>()
+            .saturating_mul(footer_len)
+            .saturating_add(expected_size::<H>())
+            .max(1);
+        let array_size = u.arbitrary_len::<u8>()? / member_size;
+
+        MaybeUninitDstArray::try_new(footer_len, array_size)
+            .map_err(|_| Error::IncorrectFormat)?
+            .try_init_each(|_| {
+                let header = H::arbitrary(u)?;
+                let footer = (0..footer_len)
+                    .map(|_| F::arbitrary(u))
+                    .collect::<Result<Vec<_>>>()?;
+
+                Ok((header, footer))
+            })
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        Self::try_size_hint(depth).unwrap_or_default()
+    }
+
+    fn try_size_hint(depth: usize) -> Result<(usize, Option<usize>), MaxRecursionReached> {
+        size_hint::try_recursion_guard(depth, |depth| {
+            H::try_size_hint(depth)?;
+            F::try_size_hint(depth)?;
+
+            Ok((0, None))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{assert_no_leaks, next_random};
+    use std::cell::Cell;
+
+    thread_local! {
+        static LIVE: Cell<isize> = const { Cell::new(0) };
+    }
+
+    ///Fails once the data runs out instead of making up values, counting the live instances
+    struct Strict;
+
+    impl<'a> Arbitrary<'a> for Strict {
+        fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+            if u.is_empty() {
+                return Err(Error::NotEnoughData);
+            }
+
+            u8::arbitrary(u)?;
+            LIVE.with(|live| live.set(live.get() + 1));
+
+            Ok(Strict)
+        }
+
+        fn size_hint(depth: usize) -> (usize, Option<usize>) {
+            u8::size_hint(depth)
+        }
+    }
+
+    impl Drop for Strict {
+        fn drop(&mut self) {
+            LIVE.with(|live| live.set(live.get() - 1));
+        }
+    }
+
+    fn random_bytes(state: &mut u64, len: usize) -> Vec<u8> {
+        (0..len).map(|_| next_random(state) as u8).collect()
+    }
+
+    #[test]
+    fn arbitrary_dst() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+
+        for len in [0, 1, 2, 17, 300, 4096] {
+            let data = random_bytes(&mut state, len);
+            let mut u = Unstructured::new(&data);
+
+            let dst = Dst::<String, u16>::arbitrary(&mut u).unwrap();
+            assert!(dst.get_footer_len() <= len / 2);
+            assert!(dst.clone() == dst);
+        }
+
+        let empty = Dst::<u8, u64>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+        assert!(empty.get_footer_len() == 0);
+
+        assert!(Dst::<u32, u8>::size_hint(0) == (4, None));
+    }
+
+    #[test]
+    fn arbitrary_dst_array() {
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+
+        for len in [0, 1, 2, 17, 300, 4096] {
+            let data = random_bytes(&mut state, len);
+            let mut u = Unstructured::new(&data);
+
+            let arr = DstArray::<u32, u8>::arbitrary(&mut u).unwrap();
+            assert!(arr.len() * (4 + arr.get_footer_len()) <= len);
+
+            let clone = arr.clone();
+            assert!(clone.len() == arr.len());
+            assert!(clone.iter().zip(arr.iter()).all(|(a, b)| a == b));
+        }
+    }
+
+    #[test]
+    fn arbitrary_drops_on_error() {
+        let mut state = 0xdead_beef_cafe_f00d;
+        let mut errors = 0;
+
+        assert_no_leaks(|| {
+            for len in 0..64 {
+                let data = random_bytes(&mut state, len);
+
+                let dst = Dst::<Strict, Strict>::arbitrary(&mut Unstructured::new(&data));
+                let arr = DstArray::<Strict, Strict>::arbitrary(&mut Unstructured::new(&data));
+                errors += dst.is_err() as usize + arr.is_err() as usize;
+            }
+        });
+
+        assert!(errors != 0);
+        assert!(LIVE.with(Cell::get) == 0);
+    }
+}
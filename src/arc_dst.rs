@@ -0,0 +1,94 @@
+//!Constructors for [`DstData`]s shared through an [`Arc`]
+//!
+//!The value is built in a [`Dst`] and then moved into the `Arc`'s allocation, so accessing it
+//!afterwards takes a single pointer chase
+
+use std::sync::Arc;
+
+use crate::{Dst, DstData};
+
+///Creates an `Arc` holding `header` and a clone of every element of `footer`
+///
+///```
+///use custom_dst::arc_dst;
+///
+///let shared = arc_dst::new(String::from("header"), &[1, 2, 3]);
+///let clone = shared.clone();
+///
+///assert_eq!(clone.get_footer(), [1, 2, 3]);
+///```
+pub fn new<H, F: Clone>(header: H, footer: &[F]) -> Arc<DstData<H, F>> {
+    Arc::from(Dst::new(header, footer).into_box())
+}
+
+///Creates an `Arc` holding `header` and the elements of `footer`, moving them rather than
+///cloning them
+pub fn from_vec<H, F>(header: H, footer: Vec<F>) -> Arc<DstData<H, F>> {
+    Arc::from(Dst::from_vec(header, footer).into_box())
+}
+
+///Creates an `Arc` holding `header` and the items of `iter`
+///
+///# Panics
+///
+///Panics if the iterator yields fewer or more items than its reported length
+#[track_caller]
+pub fn from_header_and_iter<H, F, I>(header: H, iter: I) -> Arc<DstData<H, F>>
+where
+    I: IntoIterator<Item = F>,
+    I::IntoIter: ExactSizeIterator,
+{
+    Arc::from(Dst::from_header_and_iter(header, iter).into_box())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        arc_dst,
+        test_support::{assert_no_leaks, SyncDropCounter},
+    };
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    #[test]
+    fn arc_shared_across_threads() {
+        let drops = AtomicUsize::new(0);
+
+        let footer = (0..4).map(|_| SyncDropCounter(&drops)).collect::<Vec<_>>();
+        let shared = arc_dst::from_vec(7u32, footer);
+
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                let shared = Arc::clone(&shared);
+
+                scope.spawn(move || {
+                    assert!(*shared.get_header() == 7);
+                    assert!(shared.get_footer().len() == 4);
+                });
+            }
+        });
+
+        assert!(drops.load(Ordering::Relaxed) == 0);
+
+        drop(shared);
+
+        assert!(drops.load(Ordering::Relaxed) == 4);
+    }
+
+    #[test]
+    fn arc_constructors() {
+        assert_no_leaks(|| {
+            let cloned = arc_dst::new(String::from("header"), &[String::from("a")]);
+            let iterated = arc_dst::from_header_and_iter((), (1..4).map(|x| x * 2));
+
+            assert!(cloned.get_header() == "header");
+            assert!(cloned.get_footer() == ["a"]);
+            assert!(iterated.get_footer() == [2, 4, 6]);
+        });
+    }
+}
@@ -0,0 +1,288 @@
+//!Bump allocation of many differently-sized [`DstData`]s
+
+use std::{
+    alloc::{Global, Layout},
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    mem,
+    ptr::{self, NonNull},
+};
+
+use crate::{alloc_layout, dealloc_layout, DstData, DstError, PrefixGuard};
+
+///The size of the first chunk an arena allocates, later chunks double in size
+const INITIAL_CHUNK_SIZE: usize = 4096;
+
+///A block of memory that values are carved out of
+struct Chunk {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+///Allocates [`DstData`]s with differently-sized footers out of large chunks of memory
+///
+///Unlike a [`DstArray`](crate::DstArray), the values don't share a stride. Every value is
+///dropped when the arena is dropped or [`DstArena::reset`]
+///
+///```
+///use custom_dst::arena::DstArena;
+///
+///let arena = DstArena::new();
+///
+///let short = arena.alloc(1u32, [1u8]);
+///let long = arena.alloc(2u32, 0..100);
+///
+///long.get_footer_mut()[0] = 7;
+///
+///assert_eq!(short.get_footer(), [1]);
+///assert_eq!(long.get_footer()[..2], [7, 1]);
+///```
+pub struct DstArena<H, F> {
+    chunks: RefCell<Vec<Chunk>>,
+    ///The index of the chunk values are being carved out of
+    current: Cell<usize>,
+    ///The offset of the free memory in the current chunk
+    offset: Cell<usize>,
+    ///Every value that needs to be dropped, left empty if neither part needs dropping
+    values: RefCell<Vec<NonNull<DstData<H, F>>>>,
+    phantom: PhantomData<DstData<H, F>>,
+}
+
+impl<H, F> DstArena<H, F> {
+    pub fn new() -> Self {
+        DstArena {
+            chunks: RefCell::new(Vec::new()),
+            current: Cell::new(0),
+            offset: Cell::new(0),
+            values: RefCell::new(Vec::new()),
+            phantom: PhantomData,
+        }
+    }
+
+    ///Moves `header` and the items of `footer` into the arena
+    ///
+    ///# Panics
+    ///
+    ///Panics if the iterator yields fewer or more items than its reported length. Any items
+    ///already written are dropped
+    #[track_caller]
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc<I>(&self, header: H, footer: I) -> &mut DstData<H, F>
+    where
+        I: IntoIterator<Item = F>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut footer = footer.into_iter();
+        let len = footer.len();
+
+        let layout =
+            DstData::<H, F>::layout_of(len).unwrap_or_else(|err| DstError::from(err).handle());
+        let ptr: NonNull<DstData<H, F>> = NonNull::from_raw_parts(self.bump(layout), len);
+
+        unsafe {
+            let mut guard = PrefixGuard::new(DstData::get_footer_slice(ptr.as_ptr()).as_mut_ptr());
+            for _ in 0..len {
+                let element = footer
+                    .next()
+                    .expect("the iterator yielded fewer items than its reported length");
+                guard.push(element);
+            }
+
+            //Dropped together with the completed footer if the iterator has extra items
+            DstData::get_header_ptr(ptr.as_ptr()).write(header);
+            guard.finish();
+        }
+
+        if mem::needs_drop::<H>() || mem::needs_drop::<F>() {
+            self.values.borrow_mut().push(ptr);
+        }
+
+        assert!(
+            footer.next().is_none(),
+            "the iterator yielded more items than its reported length"
+        );
+
+        unsafe { &mut *ptr.as_ptr() }
+    }
+
+    ///Drops every value in the arena, keeping its chunks around to be reused
+    pub fn reset(&mut self) {
+        self.drop_values();
+
+        self.current.set(0);
+        self.offset.set(0);
+    }
+
+    fn drop_values(&mut self) {
+        for value in mem::take(self.values.get_mut()) {
+            unsafe { ptr::drop_in_place(value.as_ptr()) };
+        }
+    }
+
+    ///Returns memory for `layout` from the current chunk, moving on to the next chunk or
+    ///allocating a new one if it doesn't fit
+    fn bump(&self, layout: Layout) -> NonNull<u8> {
+        if layout.size() == 0 {
+            return NonNull::without_provenance(layout.align().try_into().unwrap());
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+
+        loop {
+            if let Some(chunk) = chunks.get(self.current.get()) {
+                //Every value shares the alignment of the chunks, and its size is a multiple
+                //of it, so the offset is always aligned
+                let start = self.offset.get();
+
+                if chunk.layout.size() - start >= layout.size() {
+                    self.offset.set(start + layout.size());
+
+                    return unsafe { chunk.ptr.add(start) };
+                }
+
+                if self.current.get() + 1 < chunks.len() {
+                    self.current.set(self.current.get() + 1);
+                    self.offset.set(0);
+
+                    continue;
+                }
+            }
+
+            let size = chunks
+                .last()
+                .map_or(INITIAL_CHUNK_SIZE, |chunk| {
+                    chunk.layout.size().saturating_mul(2)
+                })
+                .max(layout.size());
+            let chunk_layout = Layout::from_size_align(size, layout.align())
+                .unwrap_or_else(|err| DstError::from(err).handle());
+
+            let ptr = alloc_layout(&Global, chunk_layout).unwrap_or_else(|err| err.handle());
+
+            chunks.push(Chunk {
+                ptr: unsafe { NonNull::new_unchecked(ptr) },
+                layout: chunk_layout,
+            });
+            self.current.set(chunks.len() - 1);
+            self.offset.set(0);
+        }
+    }
+}
+
+impl<H, F> Default for DstArena<H, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H, F> Drop for DstArena<H, F> {
+    fn drop(&mut self) {
+        self.drop_values();
+
+        for chunk in self.chunks.get_mut().drain(..) {
+            unsafe { dealloc_layout(&Global, chunk.ptr.as_ptr(), chunk.layout) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{assert_no_leaks, Aligned32, DropCounter, LyingIter, Marker};
+    use std::panic::{self, AssertUnwindSafe};
+
+    #[test]
+    fn arena_varying_lengths() {
+        let arena = DstArena::new();
+
+        let values = (0..200)
+            .map(|len| arena.alloc(len, (0..len).map(|x| x as u64)))
+            .collect::<Vec<_>>();
+
+        for (len, value) in values.into_iter().enumerate() {
+            assert!(*value.get_header() == len);
+            assert!(value.get_footer().len() == len);
+            assert!(value
+                .get_footer()
+                .last()
+                .is_none_or(|&last| last == len as u64 - 1));
+        }
+    }
+
+    #[test]
+    fn arena_reset_drops_values() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut arena = DstArena::new();
+
+            for len in 0..10 {
+                arena.alloc(DropCounter(&drops), (0..len).map(|_| DropCounter(&drops)));
+            }
+
+            assert!(drops.get() == 0);
+
+            arena.reset();
+
+            //10 headers and 45 footer elements
+            assert!(drops.get() == 55);
+
+            arena.alloc(DropCounter(&drops), [DropCounter(&drops)]);
+        });
+
+        assert!(drops.get() == 57);
+    }
+
+    #[test]
+    fn arena_reuses_chunks() {
+        let mut arena = DstArena::<u64, u8>::new();
+
+        let first = arena.alloc(0, [1]) as *mut DstData<u64, u8>;
+        arena.alloc(0, [0; 10_000]);
+
+        arena.reset();
+
+        let reused = arena.alloc(1, [2]) as *mut DstData<u64, u8>;
+        assert!(ptr::addr_eq(first, reused));
+    }
+
+    #[test]
+    fn arena_large_and_zero_sized() {
+        assert_no_leaks(|| {
+            let arena = DstArena::new();
+
+            let large = arena.alloc(Aligned32(1), vec![Aligned32(2); 1000]);
+            let small = arena.alloc(Aligned32(3), []);
+
+            assert!(large.get_footer().len() == 1000);
+            assert!((small as *const DstData<Aligned32, Aligned32>)
+                .addr()
+                .is_multiple_of(32));
+
+            let zst_arena = DstArena::new();
+            let zst = zst_arena.alloc(Marker, [Marker; 4]);
+
+            assert!(zst.get_footer().len() == 4);
+        });
+    }
+
+    #[test]
+    fn arena_iterator_panic() {
+        let drops = Cell::new(0);
+
+        let arena = DstArena::new();
+        let iter = LyingIter {
+            remaining: 2,
+            error: 1,
+            drops: &drops,
+        };
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            arena.alloc(DropCounter(&drops), iter);
+        }));
+
+        assert!(result.is_err());
+        assert!(drops.get() == 3);
+    }
+}
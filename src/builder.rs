@@ -0,0 +1,437 @@
+//!Builders that only hand out a Dst once every part of it has been written
+//!
+//!Forgetting to write a part is a type error for [`DstBuilder`], rather than undefined
+//!behavior behind [`MaybeUninitDst::assume_init`](crate::MaybeUninitDst::assume_init):
+//!
+//!```compile_fail,E0599
+//!use custom_dst::builder::DstBuilder;
+//!
+//!let dst = DstBuilder::<u8, u8>::new(2).header(0).build();
+//!```
+//!
+//!```compile_fail,E0599
+//!use custom_dst::builder::DstBuilder;
+//!
+//!let dst = DstBuilder::<u8, u8>::new(2).footer_with(|index| index as u8).build();
+//!```
+//!
+//!Each part can only be written once:
+//!
+//!```compile_fail,E0599
+//!use custom_dst::builder::DstBuilder;
+//!
+//!let dst = DstBuilder::<u8, u8>::new(2).header(0).header(1);
+//!```
+
+use std::{
+    convert::Infallible,
+    marker::PhantomData,
+    mem::ManuallyDrop,
+    ptr::{self, drop_in_place},
+};
+
+use crate::{Dst, DstArray, MaybeUninitDst, MaybeUninitDstArray};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+///The parts of a [`DstBuilder`] that have been provided so far
+pub trait BuilderState: sealed::Sealed {
+    #[doc(hidden)]
+    const FOOTER_WRITTEN: bool;
+}
+
+///Neither the header nor the footer has been provided
+pub struct NeedsHeader;
+
+///The header has been provided, but the footer hasn't
+pub struct NeedsFooter;
+
+///The header and footer have been provided
+pub struct Ready;
+
+impl sealed::Sealed for NeedsHeader {}
+impl sealed::Sealed for NeedsFooter {}
+impl sealed::Sealed for Ready {}
+
+impl BuilderState for NeedsHeader {
+    const FOOTER_WRITTEN: bool = false;
+}
+
+impl BuilderState for NeedsFooter {
+    const FOOTER_WRITTEN: bool = false;
+}
+
+impl BuilderState for Ready {
+    const FOOTER_WRITTEN: bool = true;
+}
+
+///Builds a [`Dst`] in place, first from a header and then from a footer
+///
+///```
+///use custom_dst::builder::DstBuilder;
+///
+///let dst = DstBuilder::new(3)
+///    .header("squares")
+///    .footer_with(|index| index * index)
+///    .build();
+///
+///assert_eq!(dst.get_footer_ref(), [0, 1, 4]);
+///```
+pub struct DstBuilder<H, F, S: BuilderState = NeedsHeader> {
+    dst: MaybeUninitDst<H, F>,
+    ///Kept out of the allocation until the Dst is built, so that it is dropped normally if
+    ///writing the footer panics
+    header: Option<H>,
+    state: PhantomData<S>,
+}
+
+impl<H, F, S: BuilderState> DstBuilder<H, F, S> {
+    ///Returns the number of footer elements the Dst will have
+    pub fn get_footer_len(&self) -> usize {
+        self.dst.get_footer_len()
+    }
+
+    fn into_state<T: BuilderState>(self) -> DstBuilder<H, F, T> {
+        let this = ManuallyDrop::new(self);
+
+        unsafe {
+            DstBuilder {
+                dst: ptr::read(&this.dst),
+                header: ptr::read(&this.header),
+                state: PhantomData,
+            }
+        }
+    }
+}
+
+impl<H, F> DstBuilder<H, F> {
+    ///Allocates a Dst whose footer will have `count` elements
+    pub fn new(count: usize) -> DstBuilder<H, F> {
+        DstBuilder {
+            dst: MaybeUninitDst::new(count),
+            header: None,
+            state: PhantomData,
+        }
+    }
+
+    pub fn header(mut self, header: H) -> DstBuilder<H, F, NeedsFooter> {
+        self.header = Some(header);
+
+        self.into_state()
+    }
+}
+
+impl<H, F> DstBuilder<H, F, NeedsFooter> {
+    ///Writes a clone of every element of `footer` into the footer
+    ///
+    ///# Panics
+    ///
+    ///Panics if `footer` doesn't have exactly [`DstBuilder::get_footer_len`] elements
+    #[track_caller]
+    pub fn footer_cloned(mut self, footer: &[F]) -> DstBuilder<H, F, Ready>
+    where
+        F: Clone,
+    {
+        self.dst.write_footer_cloned(footer);
+
+        self.into_state()
+    }
+
+    ///Writes `f(index)` into every footer element `index`
+    ///
+    ///If `f` panics, the header and the elements written so far are dropped and the
+    ///allocation is freed
+    pub fn footer_with(mut self, mut f: impl FnMut(usize) -> F) -> DstBuilder<H, F, Ready> {
+        let written = self
+            .dst
+            .element_mut()
+            .try_write_footer_with(|index| Ok::<F, Infallible>(f(index)));
+        if let Err(never) = written {
+            match never {}
+        }
+
+        self.into_state()
+    }
+
+    ///Writes the items of `iter` into the footer in order
+    ///
+    ///# Panics
+    ///
+    ///Panics if the iterator doesn't yield exactly [`DstBuilder::get_footer_len`] items,
+    ///dropping everything written so far
+    #[track_caller]
+    pub fn footer_from_iter(
+        mut self,
+        iter: impl IntoIterator<Item = F>,
+    ) -> DstBuilder<H, F, Ready> {
+        let mut iter = iter.into_iter();
+
+        let written = self
+            .dst
+            .element_mut()
+            .try_write_footer_with(|_| iter.next().ok_or(()));
+        assert!(
+            written.is_ok(),
+            "the iterator yielded fewer items than the footer has elements"
+        );
+
+        let ready = self.into_state();
+
+        assert!(
+            iter.next().is_none(),
+            "the iterator yielded more items than the footer has elements"
+        );
+
+        ready
+    }
+}
+
+impl<H, F> DstBuilder<H, F, Ready> {
+    pub fn build(self) -> Dst<H, F> {
+        let this = ManuallyDrop::new(self);
+
+        unsafe {
+            let mut dst = ptr::read(&this.dst);
+            let header = ptr::read(&this.header);
+
+            dst.write_header(header.unwrap_unchecked());
+
+            dst.assume_init()
+        }
+    }
+}
+
+impl<H, F, S: BuilderState> Drop for DstBuilder<H, F, S> {
+    fn drop(&mut self) {
+        if S::FOOTER_WRITTEN {
+            unsafe { drop_in_place(self.dst.get_footer_ptr_mut()) }
+        }
+    }
+}
+
+///Builds a [`DstArray`] in place from one member after another
+///
+///```
+///use custom_dst::builder::DstArrayBuilder;
+///
+///let mut builder = DstArrayBuilder::new(2, 2);
+///
+///builder.push_cloned('a', &[1, 2]);
+///assert!(!builder.is_full());
+///
+///builder.push_with('b', |index| index + 3);
+///
+///let Ok(arr) = builder.build() else {
+///    panic!("every member was pushed");
+///};
+///
+///assert_eq!(arr.get_footer_ref(1), [3, 4]);
+///```
+pub struct DstArrayBuilder<H, F> {
+    arr: MaybeUninitDstArray<H, F>,
+    pushed: usize,
+}
+
+impl<H, F> DstArrayBuilder<H, F> {
+    ///Allocates an array of `array_size` members whose footers will have `footer_len`
+    ///elements
+    pub fn new(footer_len: usize, array_size: usize) -> DstArrayBuilder<H, F> {
+        DstArrayBuilder {
+            arr: MaybeUninitDstArray::new(footer_len, array_size),
+            pushed: 0,
+        }
+    }
+
+    ///Returns the number of members pushed so far
+    pub fn len(&self) -> usize {
+        self.pushed
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pushed == 0
+    }
+
+    ///Returns `true` once every member has been pushed
+    pub fn is_full(&self) -> bool {
+        self.pushed == self.arr.len
+    }
+
+    #[track_caller]
+    fn next_index(&self) -> usize {
+        assert!(
+            !self.is_full(),
+            "every member of the array was already pushed"
+        );
+
+        self.pushed
+    }
+
+    ///Pushes a member holding `header` and a clone of every element of `footer`
+    ///
+    ///# Panics
+    ///
+    ///Panics if the array is full or `footer` has the wrong length
+    #[track_caller]
+    pub fn push_cloned(&mut self, header: H, footer: &[F])
+    where
+        F: Clone,
+    {
+        let arr_index = self.next_index();
+
+        self.arr.write_footer_cloned(arr_index, footer);
+        self.arr.write_header(arr_index, header);
+
+        self.pushed += 1;
+    }
+
+    ///Pushes a member holding `header` and a footer whose element `index` is `f(index)`
+    ///
+    ///# Panics
+    ///
+    ///Panics if the array is full
+    #[track_caller]
+    pub fn push_with(&mut self, header: H, mut f: impl FnMut(usize) -> F) {
+        let arr_index = self.next_index();
+
+        let written = self
+            .arr
+            .element_mut(arr_index)
+            .try_write_footer_with(|index| Ok::<F, Infallible>(f(index)));
+        if let Err(never) = written {
+            match never {}
+        }
+        self.arr.write_header(arr_index, header);
+
+        self.pushed += 1;
+    }
+
+    ///Pushes a member holding `header` and the items of `iter`
+    ///
+    ///# Panics
+    ///
+    ///Panics if the array is full or the iterator doesn't yield exactly as many items as a
+    ///footer has elements
+    #[track_caller]
+    pub fn push_from_iter(&mut self, header: H, iter: impl IntoIterator<Item = F>) {
+        let arr_index = self.next_index();
+        let mut iter = iter.into_iter();
+
+        let written = self
+            .arr
+            .element_mut(arr_index)
+            .try_write_footer_with(|_| iter.next().ok_or(()));
+        assert!(
+            written.is_ok(),
+            "the iterator yielded fewer items than the footer has elements"
+        );
+        self.arr.write_header(arr_index, header);
+
+        self.pushed += 1;
+
+        assert!(
+            iter.next().is_none(),
+            "the iterator yielded more items than the footer has elements"
+        );
+    }
+
+    ///Returns the array if every member has been pushed, and the builder otherwise
+    pub fn build(self) -> Result<DstArray<H, F>, DstArrayBuilder<H, F>> {
+        if !self.is_full() {
+            return Err(self);
+        }
+
+        let this = ManuallyDrop::new(self);
+
+        Ok(unsafe { ptr::read(&this.arr).assume_init() })
+    }
+}
+
+impl<H, F> Drop for DstArrayBuilder<H, F> {
+    fn drop(&mut self) {
+        for arr_index in 0..self.pushed {
+            unsafe { drop_in_place(self.arr.get_element(arr_index).as_ptr()) }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{assert_no_leaks, silent_panic, DropCounter};
+    use std::{
+        cell::Cell,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    #[test]
+    fn builder_drops() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let dst = DstBuilder::new(2)
+                .header(DropCounter(&drops))
+                .footer_from_iter([DropCounter(&drops), DropCounter(&drops)])
+                .build();
+            drop(dst);
+            assert!(drops.get() == 3);
+
+            drop(DstBuilder::<_, u8>::new(2).header(DropCounter(&drops)));
+            assert!(drops.get() == 4);
+
+            drop(
+                DstBuilder::new(2)
+                    .header(DropCounter(&drops))
+                    .footer_with(|_| DropCounter(&drops)),
+            );
+            assert!(drops.get() == 7);
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                DstBuilder::new(3)
+                    .header(DropCounter(&drops))
+                    .footer_with(|index| {
+                        if index == 2 {
+                            silent_panic();
+                        }
+
+                        DropCounter(&drops)
+                    })
+            }));
+            assert!(result.is_err());
+            assert!(drops.get() == 10);
+        });
+    }
+
+    #[test]
+    fn array_builder() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut builder = DstArrayBuilder::new(1, 3);
+
+            builder.push_from_iter(DropCounter(&drops), [DropCounter(&drops)]);
+            builder.push_with(DropCounter(&drops), |_| DropCounter(&drops));
+
+            let Err(builder) = builder.build() else {
+                panic!("only two members were pushed");
+            };
+            assert!(builder.len() == 2);
+
+            drop(builder);
+            assert!(drops.get() == 4);
+
+            let mut builder = DstArrayBuilder::new(1, 1);
+            builder.push_with(DropCounter(&drops), |_| DropCounter(&drops));
+
+            let Ok(arr) = builder.build() else {
+                panic!("every member was pushed");
+            };
+            assert!(drops.get() == 4);
+
+            drop(arr);
+            assert!(drops.get() == 6);
+        });
+    }
+}
@@ -0,0 +1,154 @@
+//!Byte views of [`DstData`]s made of plain data, enabled by the `bytemuck` feature
+//!
+//!The views rely on `bytemuck`'s [`NoUninit`] and [`Pod`] rather than a marker of this crate's
+//!own, so any type `bytemuck` accepts works here
+
+use std::{mem, slice};
+
+use bytemuck::{NoUninit, Pod, PodCastError};
+
+use crate::DstData;
+
+impl<H: NoUninit, F: NoUninit> DstData<H, F> {
+    ///Returns the bytes of the header followed by the bytes of the footer
+    ///
+    ///The footer has to start right where the header ends, so that no padding sits between
+    ///them, which is checked when the call is compiled. Any padding after the footer, which
+    ///rounds the size up to the alignment of the header, is left out
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::new(0x0102_0304u32, &[0x0506u16]);
+    ///
+    ///let bytes = dst.get_data().as_bytes();
+    ///
+    ///assert_eq!(bytes[..4], 0x0102_0304u32.to_ne_bytes());
+    ///assert_eq!(bytes[4..], 0x0506u16.to_ne_bytes());
+    ///```
+    ///
+    ///A `u8` header followed by `u32` footer elements is padded, so its bytes can't be viewed:
+    ///
+    ///```compile_fail,E0080
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::new(0u8, &[0u32]);
+    ///
+    ///let _ = dst.get_data().as_bytes();
+    ///```
+    pub fn as_bytes(&self) -> &[u8] {
+        const {
+            assert!(
+                mem::size_of::<H>().is_multiple_of(mem::align_of::<F>()),
+                "the footer doesn't start where the header ends, leaving padding between them"
+            )
+        };
+
+        let len = mem::size_of::<H>() + mem::size_of_val(self.get_footer());
+
+        unsafe { slice::from_raw_parts((self as *const Self).cast::<u8>(), len) }
+    }
+
+    ///Returns the bytes of the footer
+    pub fn footer_as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(self.get_footer())
+    }
+}
+
+impl<H, F: Pod> DstData<H, F> {
+    ///Returns the bytes of the footer, which can be overwritten with any value
+    pub fn footer_as_bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::cast_slice_mut(self.get_footer_mut())
+    }
+
+    ///Views the footer as a slice of `G`s
+    ///
+    ///Fails if the footer isn't aligned for `G`, or if its size isn't a multiple of the size of
+    ///`G`
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::new(0u32, &[1u8, 0, 0, 0, 2, 0, 0, 0]);
+    ///
+    ///assert_eq!(dst.get_data().footer_cast::<u32>(), Ok(&[1u32.to_le(), 2u32.to_le()][..]));
+    ///```
+    pub fn footer_cast<G: Pod>(&self) -> Result<&[G], PodCastError> {
+        bytemuck::try_cast_slice(self.get_footer())
+    }
+
+    ///Like [`DstData::footer_cast`], but for a mutable view
+    pub fn footer_cast_mut<G: Pod>(&mut self) -> Result<&mut [G], PodCastError> {
+        bytemuck::try_cast_slice_mut(self.get_footer_mut())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dst, DstArray};
+    use bytemuck::PodCastError;
+
+    #[test]
+    fn byte_views() {
+        let mut dst = Dst::new(0x0102_0304u32, &[0x0506u16, 0x0708]);
+
+        let header = 0x0102_0304u32.to_ne_bytes();
+        let footer = [0x0506u16.to_ne_bytes(), 0x0708u16.to_ne_bytes()].concat();
+        assert!(dst.get_data().as_bytes() == [&header[..], &footer].concat());
+        assert!(dst.get_data().footer_as_bytes() == footer);
+
+        dst.get_data_mut().footer_as_bytes_mut().fill(0xff);
+        assert!(dst.get_footer_ref() == [0xffff; 2]);
+
+        //The trailing padding that rounds the size up to the alignment of `u32` is left out
+        let padded = Dst::new(0u32, &[1u8, 2, 3]);
+        assert!(padded.get_data().as_bytes() == [0, 0, 0, 0, 1, 2, 3]);
+
+        let empty = Dst::<u16, u8>::new(7, &[]);
+        assert!(empty.get_data().as_bytes() == 7u16.to_ne_bytes());
+        assert!(empty.get_data().footer_as_bytes().is_empty());
+    }
+
+    #[test]
+    fn footer_cast() {
+        let mut dst = Dst::new(0u32, &[0u8; 8]);
+        dst.get_data_mut().footer_cast_mut::<u32>().unwrap()[1] = 5;
+
+        assert!(dst.get_data().footer_cast::<u32>() == Ok(&[0, 5][..]));
+        assert!(
+            dst.get_data()
+                .footer_cast::<[u8; 2]>()
+                .map(<[[u8; 2]]>::len)
+                == Ok(4)
+        );
+
+        let uneven = Dst::new(0u32, &[0u8; 6]);
+        assert!(
+            uneven.get_data().footer_cast::<u32>() == Err(PodCastError::OutputSliceWouldHaveSlop)
+        );
+    }
+
+    #[test]
+    fn footer_cast_misaligned() {
+        //Members take 6 bytes, so the footers of neighbouring members start 2 and 8 bytes into
+        //an allocation aligned to 2, and exactly one of them is aligned to 4
+        let mut arr = DstArray::from_fn(4, 2, |_| (0u16, [0u8; 4]));
+
+        let casts = [0, 1].map(|index| arr.get_arr_element(index).footer_cast::<u32>().is_ok());
+        assert!(casts[0] != casts[1]);
+
+        let misaligned = casts.iter().position(|&ok| !ok).unwrap();
+        assert!(
+            arr.get_arr_element(misaligned).footer_cast::<u32>()
+                == Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned)
+        );
+        assert!(
+            arr.get_mut_arr_element(misaligned).footer_cast_mut::<u32>()
+                == Err(PodCastError::TargetAlignmentGreaterAndInputNotAligned)
+        );
+        assert!(arr
+            .get_arr_element(misaligned)
+            .footer_cast::<[u8; 4]>()
+            .is_ok());
+    }
+}
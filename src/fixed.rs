@@ -0,0 +1,130 @@
+//!A header and footer whose length is known at compile time
+
+use std::{
+    alloc::Global,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    ptr,
+};
+
+use crate::{Dst, DstData, DstRef};
+
+///A header followed by `N` footer elements, laid out exactly like a [`DstData`] with `N`
+///footer elements so that it can be used as one without allocating
+///
+///```
+///use custom_dst::{fixed::FixedDst, DstData};
+///
+///fn sum(data: &DstData<&str, u32>) -> u32 {
+///    data.get_footer().iter().sum()
+///}
+///
+///let fixed = FixedDst::new("numbers", [1, 2, 3]);
+///
+///assert_eq!(sum(&fixed), 6);
+///assert_eq!(*fixed.get_header(), "numbers");
+///```
+#[repr(C)]
+pub struct FixedDst<H, F, const N: usize> {
+    pub header: H,
+    pub footer: [F; N],
+}
+
+impl<H, F, const N: usize> FixedDst<H, F, N> {
+    pub const fn new(header: H, footer: [F; N]) -> FixedDst<H, F, N> {
+        FixedDst { header, footer }
+    }
+
+    ///Returns a view of the value, for interfaces that take a [`DstRef`]
+    pub fn as_dst_ref(&self) -> DstRef<'_, H, F> {
+        DstRef::from(&**self)
+    }
+}
+
+impl<H, F, const N: usize> Deref for FixedDst<H, F, N> {
+    type Target = DstData<H, F>;
+
+    fn deref(&self) -> &DstData<H, F> {
+        unsafe { &*ptr::from_raw_parts(self as *const Self as *const (), N) }
+    }
+}
+
+impl<H, F, const N: usize> DerefMut for FixedDst<H, F, N> {
+    fn deref_mut(&mut self) -> &mut DstData<H, F> {
+        unsafe { &mut *ptr::from_raw_parts_mut(self as *mut Self as *mut (), N) }
+    }
+}
+
+impl<H, F, const N: usize> From<FixedDst<H, F, N>> for Dst<H, F> {
+    ///Moves the value into a new heap allocation
+    fn from(value: FixedDst<H, F, N>) -> Self {
+        let ptr = unsafe { DstData::alloc_self(N, &Global) }.unwrap_or_else(|err| err.handle());
+
+        let value = ManuallyDrop::new(value);
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                (&*value as *const FixedDst<H, F, N>).cast::<u8>(),
+                ptr.as_ptr().cast::<u8>(),
+                mem::size_of::<FixedDst<H, F, N>>(),
+            );
+        }
+
+        Dst {
+            ptr,
+            phantom: PhantomData,
+            alloc: Global,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{assert_no_leaks, Aligned32, DropCounter, OddHeader};
+    use std::{alloc::Layout, cell::Cell};
+
+    #[test]
+    fn fixed_dst_layout() {
+        fn assert_matches<H, F, const N: usize>() {
+            assert!(Layout::new::<FixedDst<H, F, N>>() == DstData::<H, F>::layout_of(N).unwrap());
+        }
+
+        assert_matches::<u8, u8, 0>();
+        assert_matches::<u8, u64, 3>();
+        assert_matches::<u64, u8, 3>();
+        assert_matches::<u16, u32, 5>();
+        assert_matches::<OddHeader, u16, 7>();
+        assert_matches::<Aligned32, u8, 2>();
+        assert_matches::<u8, Aligned32, 2>();
+
+        let mut fixed = FixedDst::new(Aligned32(1), [1u8, 2]);
+        let data: &mut DstData<_, _> = &mut fixed;
+
+        data.get_footer_mut()[1] = 3;
+        assert!(ptr::addr_eq(data, &fixed));
+        assert!(fixed.footer == [1, 3]);
+        assert!(fixed.as_dst_ref().get_footer() == [1, 3]);
+    }
+
+    #[test]
+    fn fixed_dst_into_dst() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let fixed = FixedDst::new(
+                DropCounter(&drops),
+                [DropCounter(&drops), DropCounter(&drops)],
+            );
+
+            let dst = Dst::from(fixed);
+
+            assert!(drops.get() == 0);
+            assert!(dst.get_footer_len() == 2);
+        });
+
+        assert!(drops.get() == 3);
+    }
+}
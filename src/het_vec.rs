@@ -0,0 +1,436 @@
+//!A growable list of [`DstData`]s whose footers may all have different lengths
+
+use std::{
+    alloc::{Global, Layout},
+    clone::CloneToUninit,
+    cmp,
+    iter::FusedIterator,
+    marker::PhantomData,
+    mem,
+    ops::{Index, IndexMut},
+    ptr::{self, drop_in_place, NonNull},
+    slice,
+};
+
+use crate::{alloc_layout, check_index, dealloc_layout, DstData, DstError};
+
+///The number of bytes the buffer grows to the first time it allocates
+const MIN_CAPACITY: usize = 256;
+
+///Stores records with differently-sized footers back to back in one buffer, indexed through
+///a table of their offsets
+///
+///Unlike a [`DstArena`](crate::arena::DstArena), records are reached by index, and the buffer
+///moves as it grows, so no reference into it outlives a push
+///
+///```
+///use custom_dst::het_vec::DstHetVec;
+///
+///let mut records = DstHetVec::new();
+///
+///records.push("short", &[1u16]);
+///records.push("long", &[2, 3, 4, 5]);
+///
+///records[1].get_footer_mut()[0] = 7;
+///
+///assert_eq!(records.len(), 2);
+///assert_eq!(records[0].get_footer(), [1]);
+///assert_eq!(records[1].get_footer(), [7, 3, 4, 5]);
+///```
+pub struct DstHetVec<H, F> {
+    ///Dangles while nothing is allocated
+    buf: NonNull<u8>,
+    ///The size of the buffer in bytes
+    cap: usize,
+    ///The number of bytes at the start of the buffer that records occupy
+    used: usize,
+    ///The byte offset and footer length of every record, in order
+    records: Vec<(usize, usize)>,
+    phantom: PhantomData<DstData<H, F>>,
+}
+
+impl<H, F> DstHetVec<H, F> {
+    ///Creates an empty list without allocating
+    pub fn new() -> DstHetVec<H, F> {
+        DstHetVec {
+            buf: NonNull::without_provenance(Self::align().try_into().unwrap()),
+            cap: 0,
+            used: 0,
+            records: Vec::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns the alignment shared by every record, whatever its footer length
+    fn align() -> usize {
+        cmp::max(mem::align_of::<H>(), mem::align_of::<F>())
+    }
+
+    ///Returns the number of records in the list
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    ///Returns the number of bytes the records occupy
+    pub fn byte_len(&self) -> usize {
+        self.used
+    }
+
+    ///Returns the number of bytes the records can occupy without reallocating
+    pub fn byte_capacity(&self) -> usize {
+        self.cap
+    }
+
+    fn buffer_layout(&self) -> Layout {
+        unsafe { Layout::from_size_align_unchecked(self.cap, Self::align()) }
+    }
+
+    ///Makes room for `additional` more bytes of records, at least doubling the buffer if it
+    ///has to reallocate
+    fn reserve_bytes(&mut self, additional: usize) {
+        let required = self
+            .used
+            .checked_add(additional)
+            .unwrap_or_else(|| DstError::LayoutOverflow.handle());
+
+        if required <= self.cap {
+            return;
+        }
+
+        let cap = cmp::max(cmp::max(self.cap.saturating_mul(2), required), MIN_CAPACITY);
+        let layout = Layout::from_size_align(cap, Self::align())
+            .unwrap_or_else(|err| DstError::from(err).handle());
+
+        let buf = alloc_layout(&Global, layout).unwrap_or_else(|err| err.handle());
+
+        unsafe {
+            ptr::copy_nonoverlapping(self.buf.as_ptr(), buf, self.used);
+            dealloc_layout(&Global, self.buf.as_ptr(), self.buffer_layout());
+
+            self.buf = NonNull::new_unchecked(buf);
+        }
+        self.cap = cap;
+    }
+
+    ///Returns a pointer to the record at `offset` with `len` footer elements
+    ///
+    ///# Safety
+    ///
+    /// `offset` must be at most the number of bytes in use
+    unsafe fn record_ptr(&self, (offset, len): (usize, usize)) -> NonNull<DstData<H, F>> {
+        NonNull::from_raw_parts(unsafe { self.buf.add(offset) }, len)
+    }
+
+    ///Appends a record holding `header` and a clone of every element of `footer`
+    ///
+    ///If a clone panics, the elements cloned so far and `header` are dropped and the list is
+    ///left unchanged
+    pub fn push(&mut self, header: H, footer: &[F])
+    where
+        F: Clone,
+    {
+        let size = DstData::<H, F>::layout_of(footer.len())
+            .unwrap_or_else(|err| DstError::from(err).handle())
+            .size();
+
+        self.reserve_bytes(size);
+        self.records.reserve(1);
+
+        let record = (self.used, footer.len());
+
+        unsafe {
+            let ptr = self.record_ptr(record).as_ptr();
+
+            footer.clone_to_uninit(DstData::get_footer_slice(ptr).as_mut_ptr().cast::<u8>());
+            DstData::get_header_ptr(ptr).write(header);
+        }
+
+        //Every record's size is a multiple of the shared alignment, so the next one is aligned
+        self.used += size;
+        self.records.push(record);
+    }
+
+    ///Returns record `index`, or `None` if it is out of bounds
+    pub fn get(&self, index: usize) -> Option<&DstData<H, F>> {
+        let &record = self.records.get(index)?;
+
+        Some(unsafe { self.record_ptr(record).as_ref() })
+    }
+
+    ///Returns record `index` mutably, or `None` if it is out of bounds
+    ///
+    ///Its footer can be changed in place, but not resized
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut DstData<H, F>> {
+        let &record = self.records.get(index)?;
+
+        Some(unsafe { self.record_ptr(record).as_mut() })
+    }
+
+    ///Drops every record, keeping the buffer
+    pub fn clear(&mut self) {
+        //Leaks the remaining records rather than dropping one twice if a destructor panics
+        let records = mem::take(&mut self.records);
+        self.used = 0;
+
+        for record in records {
+            unsafe { drop_in_place(self.record_ptr(record).as_ptr()) };
+        }
+    }
+
+    ///Returns an iterator over the records in order
+    pub fn iter(&self) -> DstHetIter<'_, H, F> {
+        DstHetIter {
+            buf: self.buf,
+            records: self.records.iter(),
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns an iterator over mutable references to the records in order
+    pub fn iter_mut(&mut self) -> DstHetIterMut<'_, H, F> {
+        DstHetIterMut {
+            buf: self.buf,
+            records: self.records.iter(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<H, F> Default for DstHetVec<H, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<H: Send, F: Send> Send for DstHetVec<H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstHetVec<H, F> {}
+
+impl<H, F> Drop for DstHetVec<H, F> {
+    fn drop(&mut self) {
+        self.clear();
+
+        unsafe { dealloc_layout(&Global, self.buf.as_ptr(), self.buffer_layout()) };
+    }
+}
+
+impl<H, F> Index<usize> for DstHetVec<H, F> {
+    type Output = DstData<H, F>;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &DstData<H, F> {
+        check_index(index, self.len());
+
+        unsafe { self.record_ptr(self.records[index]).as_ref() }
+    }
+}
+
+impl<H, F> IndexMut<usize> for DstHetVec<H, F> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut DstData<H, F> {
+        check_index(index, self.len());
+
+        unsafe { self.record_ptr(self.records[index]).as_mut() }
+    }
+}
+
+impl<'a, H, F> IntoIterator for &'a DstHetVec<H, F> {
+    type Item = &'a DstData<H, F>;
+    type IntoIter = DstHetIter<'a, H, F>;
+
+    fn into_iter(self) -> DstHetIter<'a, H, F> {
+        self.iter()
+    }
+}
+
+impl<'a, H, F> IntoIterator for &'a mut DstHetVec<H, F> {
+    type Item = &'a mut DstData<H, F>;
+    type IntoIter = DstHetIterMut<'a, H, F>;
+
+    fn into_iter(self) -> DstHetIterMut<'a, H, F> {
+        self.iter_mut()
+    }
+}
+
+///An iterator over the records of a [`DstHetVec`]
+pub struct DstHetIter<'a, H, F> {
+    buf: NonNull<u8>,
+    records: slice::Iter<'a, (usize, usize)>,
+    phantom: PhantomData<&'a DstData<H, F>>,
+}
+
+impl<'a, H, F> DstHetIter<'a, H, F> {
+    fn record(&self, (offset, len): (usize, usize)) -> &'a DstData<H, F> {
+        unsafe { NonNull::from_raw_parts(self.buf.add(offset), len).as_ref() }
+    }
+}
+
+impl<'a, H, F> Iterator for DstHetIter<'a, H, F> {
+    type Item = &'a DstData<H, F>;
+
+    fn next(&mut self) -> Option<&'a DstData<H, F>> {
+        let &record = self.records.next()?;
+
+        Some(self.record(record))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.records.size_hint()
+    }
+}
+
+impl<H, F> DoubleEndedIterator for DstHetIter<'_, H, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let &record = self.records.next_back()?;
+
+        Some(self.record(record))
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstHetIter<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstHetIter<'_, H, F> {}
+
+impl<H, F> Clone for DstHetIter<'_, H, F> {
+    fn clone(&self) -> Self {
+        DstHetIter {
+            buf: self.buf,
+            records: self.records.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+unsafe impl<H: Sync, F: Sync> Send for DstHetIter<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstHetIter<'_, H, F> {}
+
+///An iterator over mutable references to the records of a [`DstHetVec`]
+pub struct DstHetIterMut<'a, H, F> {
+    buf: NonNull<u8>,
+    records: slice::Iter<'a, (usize, usize)>,
+    phantom: PhantomData<&'a mut DstData<H, F>>,
+}
+
+impl<'a, H, F> DstHetIterMut<'a, H, F> {
+    ///Every record appears once in the table, so each is handed out at most once
+    fn record(&self, (offset, len): (usize, usize)) -> &'a mut DstData<H, F> {
+        unsafe { NonNull::from_raw_parts(self.buf.add(offset), len).as_mut() }
+    }
+}
+
+impl<'a, H, F> Iterator for DstHetIterMut<'a, H, F> {
+    type Item = &'a mut DstData<H, F>;
+
+    fn next(&mut self) -> Option<&'a mut DstData<H, F>> {
+        let &record = self.records.next()?;
+
+        Some(self.record(record))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.records.size_hint()
+    }
+}
+
+impl<H, F> DoubleEndedIterator for DstHetIterMut<'_, H, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let &record = self.records.next_back()?;
+
+        Some(self.record(record))
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstHetIterMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstHetIterMut<'_, H, F> {}
+
+unsafe impl<H: Send, F: Send> Send for DstHetIterMut<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstHetIterMut<'_, H, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{assert_no_leaks, DropCounter};
+    use std::cell::Cell;
+
+    #[test]
+    fn het_vec() {
+        let mut records = DstHetVec::new();
+        assert!(records.is_empty());
+        assert!(records.get(0).is_none());
+
+        for len in 0..40usize {
+            let footer = (0..len)
+                .map(|index| (len * 100 + index) as u32)
+                .collect::<Vec<_>>();
+            records.push(len as u8, &footer);
+        }
+
+        assert!(records.len() == 40);
+        for (len, record) in records.iter().enumerate() {
+            assert!(*record.get_header() == len as u8);
+            assert!(record.get_footer().len() == len);
+            assert!(record
+                .get_footer()
+                .iter()
+                .enumerate()
+                .all(|(index, &element)| { element == (len * 100 + index) as u32 }));
+        }
+
+        for record in &mut records {
+            if let Some(first) = record.get_footer_mut().first_mut() {
+                *first = 0;
+            }
+        }
+        records[39].get_footer_mut()[38] = 1;
+        *records.get_mut(3).unwrap().get_header_mut() = 77;
+
+        assert!(records[39].get_footer()[..2] == [0, 3901]);
+        assert!(records[39].get_footer()[38] == 1);
+        assert!(*records[3].get_header() == 77);
+        assert!(records
+            .iter()
+            .rev()
+            .map(|record| record.get_footer().len())
+            .eq((0..40).rev()));
+        assert!(records.get(40).is_none());
+
+        let mut zsts = DstHetVec::<(), ()>::new();
+        zsts.push((), &[(); 3]);
+        zsts.push((), &[]);
+        assert!(zsts[0].get_footer().len() == 3);
+        assert!(zsts.byte_capacity() == 0);
+    }
+
+    #[test]
+    fn het_vec_drops() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut records = DstHetVec::new();
+            for len in 0..10 {
+                let footer = (0..len).map(|_| DropCounter(&drops)).collect::<Vec<_>>();
+                records.push(DropCounter(&drops), &footer);
+            }
+
+            //The originals of every cloned footer, plus nothing from the records themselves
+            assert!(drops.get() == 45);
+
+            records.clear();
+            assert!(drops.get() == 100);
+            assert!(records.is_empty());
+            assert!(records.byte_len() == 0);
+
+            records.push(DropCounter(&drops), &[]);
+        });
+
+        assert!(drops.get() == 101);
+    }
+}
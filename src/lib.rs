@@ -1,802 +1,10658 @@
 #![feature(
     ptr_metadata,
-    alloc_layout_extra,
     layout_for_ptr,
     slice_ptr_get,
-    pointer_byte_offsets,
-    slice_index_methods
+    clone_to_uninit,
+    allocator_api
 )]
 
 use std::{
-    alloc::{alloc, dealloc, handle_alloc_error, Layout, LayoutError},
+    alloc::{handle_alloc_error, AllocError, Allocator, Global, Layout, LayoutError},
+    borrow::{Borrow, BorrowMut},
+    clone::CloneToUninit,
     cmp,
+    convert::Infallible,
+    error::Error,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
     marker::PhantomData,
-    mem::transmute,
-    ops::{Index, IndexMut},
-    ptr::{self, addr_of_mut, drop_in_place, from_raw_parts_mut},
+    mem::{self, ManuallyDrop},
+    ops::{Bound, Index, IndexMut, Range, RangeBounds},
+    panic,
+    ptr::{self, addr_of_mut, drop_in_place, NonNull},
+    slice::{GetDisjointMutError, SliceIndex},
+    thread,
 };
 
-#[repr(C)]
-pub struct DstData<H: Sized, F: Sized> {
-    header: H,
-    footer: [F],
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+pub mod arc_dst;
+pub mod arena;
+pub mod builder;
+#[cfg(feature = "bytemuck")]
+mod bytemuck;
+pub mod fixed;
+pub mod het_vec;
+pub mod pool;
+#[cfg(feature = "rayon")]
+mod rayon;
+pub mod rc_dst;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "serde")]
+mod serde;
+pub mod slab;
+pub mod small;
+pub mod str;
+pub mod thin;
+mod tracking;
+pub mod vec;
+mod view;
+
+#[cfg(test)]
+mod test_support;
+
+use builder::DstArrayBuilder;
+use tracking::InitTracker;
+pub use tracking::UninitializedReport;
+pub use view::{DstMut, DstRef};
+
+#[cfg(feature = "rayon")]
+pub use crate::rayon::{DstParIter, DstParIterMut};
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv::ArchivedDst;
+#[cfg(feature = "serde")]
+pub use crate::serde::{DstSeed, DEFAULT_MAX_FOOTER_BYTES};
+
+#[track_caller]
+fn check_index(index: usize, len: usize) {
+    assert!(
+        index < len,
+        "index out of bounds: the len is {len} but the index is {index}"
+    );
 }
 
-impl<H, F> DstData<H, F> {
-    pub fn get_header(&self) -> &H {
-        &self.header
-    }
+///Resolves `range` against a sequence of `len` elements, panicking with the offending bound if
+///it doesn't fit
+#[track_caller]
+fn check_range(range: impl RangeBounds<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        Bound::Excluded(&start) => start
+            .checked_add(1)
+            .expect("attempted to index slice from after maximum usize"),
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => end
+            .checked_add(1)
+            .expect("attempted to index slice up to maximum usize"),
+        Bound::Excluded(&end) => end,
+        Bound::Unbounded => len,
+    };
+
+    assert!(
+        start <= len,
+        "range start index {start} out of range for slice of length {len}"
+    );
+    assert!(
+        end <= len,
+        "range end index {end} out of range for slice of length {len}"
+    );
+    assert!(
+        start <= end,
+        "slice index starts at {start} but ends at {end}"
+    );
+
+    start..end
+}
 
-    pub fn get_header_mut(&mut self) -> &mut H {
-        &mut self.header
-    }
+///The error returned when a Dst could not be allocated
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DstError {
+    ///The size of the requested allocation overflowed `isize::MAX`
+    LayoutOverflow,
+    ///The allocator failed to provide memory for the contained layout
+    AllocFailed(Layout),
+    ///The byte budget could not hold even an empty Dst, which needs the contained number of
+    ///bytes
+    BudgetTooSmall(usize),
+}
 
-    pub fn get_footer(&self) -> &[F] {
-        &self.footer
+impl DstError {
+    ///Reports the error the same way the infallible constructors always have
+    #[track_caller]
+    fn handle(self) -> ! {
+        match self {
+            DstError::LayoutOverflow | DstError::BudgetTooSmall(_) => panic!("{self}"),
+            DstError::AllocFailed(layout) => handle_alloc_error(layout),
+        }
     }
+}
 
-    pub fn get_footer_mut(&mut self) -> &mut [F] {
-        &mut self.footer
+impl From<LayoutError> for DstError {
+    fn from(_: LayoutError) -> Self {
+        DstError::LayoutOverflow
     }
+}
 
-    pub fn get_parts_mut(&mut self) -> (&mut H, &mut [F]) {
-        (&mut self.header, &mut self.footer)
+impl fmt::Display for DstError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DstError::LayoutOverflow => f.write_str("the size of the dst overflowed isize::MAX"),
+            DstError::AllocFailed(layout) => write!(
+                f,
+                "failed to allocate {} bytes aligned to {}",
+                layout.size(),
+                layout.align()
+            ),
+            DstError::BudgetTooSmall(needed) => {
+                write!(
+                    f,
+                    "the byte budget is smaller than the {needed} bytes an empty dst needs"
+                )
+            }
+        }
     }
+}
 
-    fn layout_of(count: usize) -> Result<Layout, LayoutError> {
-        let (mut layout, _) = Layout::new::<H>().extend(Layout::array::<F>(count)?)?;
-        layout = layout.pad_to_align();
+impl Error for DstError {}
+
+///The error returned when an iterator doesn't yield exactly as many items as the footer it is
+///written into has elements
+///
+///The footer is left unwritten, with any items yielded so far dropped
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillError {
+    ///The iterator ran out after yielding the contained number of items
+    TooFew(usize),
+    ///The iterator yielded more items than the footer has elements
+    TooMany,
+}
 
-        Ok(layout)
+impl fmt::Display for FillError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FillError::TooFew(yielded) => write!(
+                f,
+                "the iterator ran out after {yielded} items, before the footer was filled"
+            ),
+            FillError::TooMany => {
+                f.write_str("the iterator yielded more items than the footer has elements")
+            }
+        }
     }
+}
 
-    ///Returns a pointer to an uninitialized Dst
-    unsafe fn alloc_self(count: usize) -> *mut Self {
-        let layout = Self::layout_of(count).unwrap();
+impl Error for FillError {}
 
-        let ptr = alloc(layout);
+///The error returned when Dsts whose footers have different lengths are collected into a
+///[`DstArray`]
+pub struct MismatchedLenError<H, F> {
+    ///The index of the first Dst whose footer length differs from that of the first one
+    pub index: usize,
+    ///The Dsts that were passed in, untouched
+    pub dsts: Vec<Dst<H, F>>,
+}
 
-        if ptr.is_null() {
-            handle_alloc_error(layout);
-        } else {
-            //Needed to make the pointer a fat pointer
-            from_raw_parts_mut::<DstData<H, F>>(ptr as *mut (), count)
-        }
+impl<H, F> fmt::Debug for MismatchedLenError<H, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MismatchedLenError")
+            .field("index", &self.index)
+            .field("len", &self.dsts.len())
+            .finish_non_exhaustive()
     }
+}
 
-    ///Returns pointer to array of arraySize members where [F] has count elements (members are uninitialized)
-    ///
-    ///Also returns distance between each member of the array
-    ///
-    unsafe fn alloc_self_array(count: usize, array_size: usize) -> *mut Self {
-        let (layout, _usize) = Self::layout_of(count).unwrap().repeat(array_size).unwrap();
+impl<H, F> fmt::Display for MismatchedLenError<H, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the footer of dst {} has {} elements but the first has {}",
+            self.index,
+            self.dsts[self.index].get_footer_len(),
+            self.dsts[0].get_footer_len()
+        )
+    }
+}
 
-        let ptr = alloc(layout);
+impl<H, F> Error for MismatchedLenError<H, F> {}
 
-        ptr::slice_from_raw_parts(ptr, count) as *mut DstData<H, F>
-    }
+///The error returned when two [`DstArray`]s whose footers have different lengths are
+///concatenated
+pub struct MismatchedArraysError<H, F, A: Allocator = Global> {
+    ///The array that would have come first, untouched
+    pub first: DstArray<H, F, A>,
+    ///The array that would have come second, untouched
+    pub second: DstArray<H, F, A>,
+}
 
-    unsafe fn get_footer_slice(ptr: *mut Self) -> *mut [F] {
-        addr_of_mut!((*ptr).footer)
+impl<H, F, A: Allocator> fmt::Debug for MismatchedArraysError<H, F, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MismatchedArraysError")
+            .field("first_footer_len", &self.first.get_footer_len())
+            .field("second_footer_len", &self.second.get_footer_len())
+            .finish_non_exhaustive()
     }
+}
 
-    unsafe fn get_header_ptr(ptr: *mut Self) -> *mut H {
-        addr_of_mut!((*ptr).header)
+impl<H, F, A: Allocator> fmt::Display for MismatchedArraysError<H, F, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the footers of the first array have {} elements but those of the second have {}",
+            self.first.get_footer_len(),
+            self.second.get_footer_len()
+        )
     }
+}
 
-    unsafe fn get_len(ptr: *const Self) -> usize {
-        unsafe { (*ptr).footer.len() }
-    }
+impl<H, F, A: Allocator> Error for MismatchedArraysError<H, F, A> {}
+
+///The error returned when `(header, footer)` parts whose footers have different lengths are
+///collected into a [`DstArray`]
+pub struct MismatchedPartsError<H, P> {
+    ///The index of the first part whose footer length differs from that of the first one
+    pub index: usize,
+    ///The footer length of the first part
+    pub expected: usize,
+    ///The footer length of the part at `index`
+    pub found: usize,
+    ///The parts that were collected, up to and including the one at `index`
+    pub parts: Vec<(H, P)>,
 }
 
-impl<H, F> Drop for DstData<H, F> {
-    fn drop(&mut self) {
-        unsafe {
-            drop_in_place(DstData::get_footer_slice(self));
-        }
+impl<H, P> fmt::Debug for MismatchedPartsError<H, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MismatchedPartsError")
+            .field("index", &self.index)
+            .field("expected", &self.expected)
+            .field("found", &self.found)
+            .finish_non_exhaustive()
     }
 }
 
-pub struct MaybeUninitDst<H: Sized, F: Sized> {
-    ptr: *mut DstData<H, F>,
+impl<H, P> fmt::Display for MismatchedPartsError<H, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the footer of part {} has {} elements but the first has {}",
+            self.index, self.found, self.expected
+        )
+    }
 }
 
-impl<H, F> MaybeUninitDst<H, F> {
-    pub fn new(count: usize) -> MaybeUninitDst<H, F> {
-        MaybeUninitDst {
-            ptr: unsafe { DstData::alloc_self(count) },
+impl<H, P> Error for MismatchedPartsError<H, P> {}
+
+///Parts collected by [`stage_parts`], along with their common footer length
+type StagedParts<H, P> = (Vec<(H, P)>, usize);
+
+///Collects `iter` into a Vec, checking that every part has the footer length of the first one
+fn stage_parts<H, P>(
+    iter: impl IntoIterator<Item = (H, P)>,
+    footer_len: impl Fn(&P) -> usize,
+) -> Result<StagedParts<H, P>, MismatchedPartsError<H, P>> {
+    let mut iter = iter.into_iter();
+    let mut parts = Vec::with_capacity(iter.size_hint().0);
+
+    let Some(first) = iter.next() else {
+        return Ok((parts, 0));
+    };
+    let expected = footer_len(&first.1);
+    parts.push(first);
+
+    for part in iter {
+        let found = footer_len(&part.1);
+        parts.push(part);
+
+        if found != expected {
+            return Err(MismatchedPartsError {
+                index: parts.len() - 1,
+                expected,
+                found,
+                parts,
+            });
         }
     }
 
-    pub fn write_header(&mut self, header: H) {
-        unsafe {
-            self.get_header_ptr_mut().write(header);
-        }
-    }
+    Ok((parts, expected))
+}
 
-    pub fn write_footer(&mut self, footer: &[F]) {
-        unsafe {
-            let footer_ptr = self.get_footer_ptr_mut();
-            let footer_len = self.get_footer_len();
+///Allocates memory for `layout` from `alloc`, returning a dangling pointer if it is
+///zero-sized
+fn alloc_layout<A: Allocator>(alloc: &A, layout: Layout) -> Result<*mut u8, DstError> {
+    alloc_layout_with(layout, |layout| alloc.allocate(layout))
+}
 
-            assert!(footer.len() == footer_len);
+///Like [`alloc_layout`], but the memory is zeroed
+fn alloc_zeroed_layout<A: Allocator>(alloc: &A, layout: Layout) -> Result<*mut u8, DstError> {
+    alloc_layout_with(layout, |layout| alloc.allocate_zeroed(layout))
+}
 
-            ptr::copy_nonoverlapping(footer.as_ptr(), footer_ptr.as_mut_ptr(), footer_len);
-        }
+fn alloc_layout_with(
+    layout: Layout,
+    allocate: impl FnOnce(Layout) -> Result<NonNull<[u8]>, AllocError>,
+) -> Result<*mut u8, DstError> {
+    if layout.size() == 0 {
+        return Ok(ptr::without_provenance_mut(layout.align()));
     }
 
-    pub fn write_footer_element(&mut self, index: usize, element: F) {
-        unsafe {
-            let footer_len = self.get_footer_len();
-            assert!(index < footer_len);
+    match allocate(layout) {
+        Ok(ptr) => Ok(ptr.as_mut_ptr()),
+        Err(AllocError) => Err(DstError::AllocFailed(layout)),
+    }
+}
 
-            let footer_ptr = self.get_footer_element_ptr_mut(index);
+///Frees memory returned by [`alloc_layout`] for the same `alloc` and `layout`
+unsafe fn dealloc_layout<A: Allocator>(alloc: &A, ptr: *mut u8, layout: Layout) {
+    if layout.size() != 0 {
+        unsafe { alloc.deallocate(NonNull::new_unchecked(ptr), layout) }
+    }
+}
+
+///Tracks a prefix of elements written in place, dropping them if it is dropped before
+///[`PrefixGuard::finish`] is called, such as when a clone panics partway through
+struct PrefixGuard<T> {
+    start: *mut T,
+    initialized: usize,
+}
 
-            footer_ptr.write(element);
+impl<T> PrefixGuard<T> {
+    fn new(start: *mut T) -> Self {
+        PrefixGuard {
+            start,
+            initialized: 0,
         }
     }
 
+    ///Writes `value` just past the initialized prefix
+    ///
     ///# Safety
     ///
-    /// Implies that all parts of the Dst have been initialized
-    pub unsafe fn assume_init(self) -> Dst<H, F> {
-        Dst { ptr: self.ptr }
+    /// The memory for the element must be valid and uninitialized
+    unsafe fn push(&mut self, value: T) {
+        unsafe { self.start.add(self.initialized).write(value) };
+        self.initialized += 1;
     }
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the header has been initialized
-    pub fn get_header_ptr(&self) -> *const H {
-        unsafe { DstData::get_header_ptr(self.ptr) as *const H }
+    ///Hands ownership of the initialized prefix to the caller, returning its length
+    fn finish(self) -> usize {
+        ManuallyDrop::new(self).initialized
     }
+}
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the header has been initialized
-    pub fn get_header_ptr_mut(&mut self) -> *mut H {
-        unsafe { DstData::get_header_ptr(self.ptr) }
+impl<T> Drop for PrefixGuard<T> {
+    fn drop(&mut self) {
+        unsafe { drop_in_place(ptr::slice_from_raw_parts_mut(self.start, self.initialized)) }
     }
+}
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the footer has been initialized
-    pub fn get_footer_ptr(&self) -> *const [F] {
-        unsafe { DstData::get_footer_slice(self.ptr) as *const [F] }
-    }
+///Drops the first `initialized` values spaced `stride` bytes apart from `start` unless
+///[`ArrayPrefixGuard::finish`] is called, such as when building a member of an array panics
+///
+///`T` is a whole member, or the header or footer of one
+struct ArrayPrefixGuard<T: ?Sized> {
+    start: NonNull<T>,
+    stride: usize,
+    initialized: usize,
+}
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the footer has been initialized
-    pub fn get_footer_ptr_mut(&mut self) -> *mut [F] {
-        unsafe { DstData::get_footer_slice(self.ptr) }
+impl<T: ?Sized> ArrayPrefixGuard<T> {
+    fn new(start: NonNull<T>, stride: usize) -> Self {
+        ArrayPrefixGuard {
+            start,
+            stride,
+            initialized: 0,
+        }
     }
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the element has been initialized
-    pub fn get_footer_element_ptr(&self, index: usize) -> *const F {
-        unsafe {
-            (DstData::get_footer_slice(self.ptr) as *const [F])
-                .as_ptr()
-                .add(index)
-        }
+    ///Extends the initialized prefix by the value just past it
+    ///
+    ///# Safety
+    ///
+    /// That value must have been fully initialized
+    unsafe fn push_initialized(&mut self) {
+        self.initialized += 1;
     }
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the element has been initialized
-    pub fn get_footer_element_ptr_mut(&self, index: usize) -> *mut F {
-        unsafe {
-            (DstData::get_footer_slice(self.ptr) as *mut [F])
-                .as_mut_ptr()
-                .add(index)
-        }
+    ///Hands ownership of the initialized prefix to the caller, returning its length
+    fn finish(self) -> usize {
+        ManuallyDrop::new(self).initialized
     }
+}
 
-    pub fn get_footer_len(&self) -> usize {
-        unsafe { DstData::get_len(self.ptr) }
+impl<T: ?Sized> Drop for ArrayPrefixGuard<T> {
+    fn drop(&mut self) {
+        for index in 0..self.initialized {
+            unsafe { drop_in_place(self.start.byte_add(self.stride * index).as_ptr()) }
+        }
     }
 }
 
-pub struct Dst<H: Sized, F: Sized> {
-    ptr: *mut DstData<H, F>,
+#[repr(C)]
+pub struct DstData<H: Sized, F: Sized> {
+    header: H,
+    footer: [F],
 }
 
-impl<H, F> Dst<H, F> {
-    pub fn get_header_ref(&self) -> &H {
-        unsafe { (*self.ptr).get_header() }
+impl<H, F> DstData<H, F> {
+    pub fn get_header(&self) -> &H {
+        &self.header
     }
 
-    pub fn get_header_ref_mut(&mut self) -> &mut H {
-        unsafe { (*self.ptr).get_header_mut() }
+    pub fn get_header_mut(&mut self) -> &mut H {
+        &mut self.header
     }
 
-    pub fn get_footer_ref(&self) -> &[F] {
-        unsafe { (*self.ptr).get_footer() }
+    pub fn get_footer(&self) -> &[F] {
+        &self.footer
     }
 
-    pub fn get_footer_ref_mut(&mut self) -> &mut [F] {
-        unsafe { (*self.ptr).get_footer_mut() }
+    pub fn get_footer_mut(&mut self) -> &mut [F] {
+        &mut self.footer
     }
 
-    pub fn get_footer_len(&self) -> usize {
-        self.get_footer_ref().len()
+    pub fn get_parts_mut(&mut self) -> (&mut H, &mut [F]) {
+        (&mut self.header, &mut self.footer)
     }
-}
-
-impl<H, F> Drop for Dst<H, F> {
-    fn drop(&mut self) {
-        let layout = DstData::<H, F>::layout_of(self.get_footer_len()).unwrap();
 
-        unsafe {
-            drop_in_place(self.ptr);
+    ///Returns the largest footer length for which the Dst, including any padding, takes up at
+    ///most `total_bytes`
+    ///
+    ///Returns 0 if not even the header fits, and `usize::MAX` for zero-sized footer elements
+    ///
+    ///```
+    ///use custom_dst::DstData;
+    ///
+    /////The u64 footer starts 8 bytes in, after the header and 7 bytes of padding
+    ///const ENTRIES: usize = DstData::<u8, u64>::capacity_for(4096);
+    ///
+    ///assert_eq!(ENTRIES, 511);
+    ///```
+    pub const fn capacity_for(total_bytes: usize) -> usize {
+        let align = if mem::align_of::<H>() > mem::align_of::<F>() {
+            mem::align_of::<H>()
+        } else {
+            mem::align_of::<F>()
+        };
+        let footer_offset = mem::size_of::<H>().next_multiple_of(mem::align_of::<F>());
 
-            dealloc(self.ptr as *mut u8, layout);
+        //Layouts may not be larger than isize::MAX
+        let total_bytes = if total_bytes > isize::MAX as usize {
+            isize::MAX as usize
+        } else {
+            total_bytes
         };
+        //The padded size fits the budget exactly when the unpadded size fits the budget
+        //rounded down to the alignment
+        let usable = total_bytes - total_bytes % align;
+
+        if usable < footer_offset {
+            0
+        } else if mem::size_of::<F>() == 0 {
+            usize::MAX
+        } else {
+            (usable - footer_offset) / mem::size_of::<F>()
+        }
     }
-}
 
-pub struct MaybeUninitDstArray<H: Sized, F: Sized> {
-    len: usize,
-    stride: usize,
-    ptr: *mut DstData<H, F>,
-}
+    fn layout_of(count: usize) -> Result<Layout, LayoutError> {
+        let (mut layout, _) = Layout::new::<H>().extend(Layout::array::<F>(count)?)?;
+        layout = layout.pad_to_align();
 
-impl<H, F> MaybeUninitDstArray<H, F> {
-    pub fn new(count: usize, array_size: usize) -> Self {
-        MaybeUninitDstArray {
-            len: array_size,
-            stride: unsafe { DstData::<H, F>::layout_of(count).unwrap_unchecked().size() },
-            ptr: unsafe { DstData::alloc_self_array(count, array_size) },
-        }
+        Ok(layout)
     }
 
-    fn get_stride(&self) -> usize {
-        self.stride
+    ///Returns the layout of an array of `array_size` members where [F] has count elements
+    ///
+    ///Also returns distance between each member of the array
+    fn array_layout_of(count: usize, array_size: usize) -> Result<(Layout, usize), LayoutError> {
+        Self::layout_of(count)?.repeat(array_size)
     }
 
-    fn get_element(&self, arr_index: usize) -> MaybeUninitDst<H, F> {
-        assert!(arr_index < self.len);
+    ///Returns a pointer to an uninitialized Dst
+    unsafe fn alloc_self<A: Allocator>(count: usize, alloc: &A) -> Result<NonNull<Self>, DstError> {
+        let layout = Self::layout_of(count)?;
 
-        let ptr = unsafe { self.ptr.byte_add(self.get_stride() * arr_index) };
+        let ptr = alloc_layout(alloc, layout)?;
 
-        MaybeUninitDst { ptr }
+        //Needed to make the pointer a fat pointer
+        Ok(NonNull::from_raw_parts(NonNull::new_unchecked(ptr), count))
     }
-    ///# Safety
+
+    ///Returns a pointer to a Dst whose bytes are all zero
+    unsafe fn alloc_self_zeroed<A: Allocator>(
+        count: usize,
+        alloc: &A,
+    ) -> Result<NonNull<Self>, DstError> {
+        let layout = Self::layout_of(count)?;
+
+        let ptr = alloc_zeroed_layout(alloc, layout)?;
+
+        //Needed to make the pointer a fat pointer
+        Ok(NonNull::from_raw_parts(NonNull::new_unchecked(ptr), count))
+    }
+
+    ///Returns pointer to array of arraySize members where [F] has count elements (members are uninitialized)
     ///
-    /// Declares that the Dst array is fully initialized, and is unsafe it has not been
-    pub unsafe fn assume_init(self) -> DstArray<H, F> {
-        DstArray {
-            len: self.len,
-            stride: self.stride,
-            ptr: self.ptr,
-        }
+    ///Also returns distance between each member of the array
+    ///
+    unsafe fn alloc_self_array<A: Allocator>(
+        count: usize,
+        array_size: usize,
+        alloc: &A,
+    ) -> Result<(NonNull<Self>, usize), DstError> {
+        let (layout, stride) = Self::array_layout_of(count, array_size)?;
+
+        let ptr = alloc_layout(alloc, layout)?;
+
+        //Needed to make the pointer a fat pointer
+        Ok((
+            NonNull::from_raw_parts(NonNull::new_unchecked(ptr), count),
+            stride,
+        ))
     }
 
-    pub fn write_header(&mut self, arr_index: usize, header: H) {
-        self.get_element(arr_index).write_header(header);
+    ///Returns the layout of the allocation behind `ptr`
+    ///
+    ///Unlike [`DstData::layout_of`], this cannot fail since the allocation already exists
+    unsafe fn layout_of_raw(ptr: *const Self) -> Layout {
+        unsafe { Layout::for_value_raw(ptr) }
     }
 
-    pub fn write_footer(&mut self, arr_index: usize, footer: &[F]) {
-        self.get_element(arr_index).write_footer(footer);
+    ///Returns the layout of the array allocation starting at `ptr`
+    unsafe fn array_layout_of_raw(ptr: *const Self, array_size: usize, stride: usize) -> Layout {
+        unsafe {
+            Layout::from_size_align_unchecked(stride * array_size, mem::align_of_val_raw(ptr))
+        }
     }
 
-    pub fn write_footer_element(&mut self, arr_index: usize, footer_index: usize, element: F) {
-        self.get_element(arr_index)
-            .write_footer_element(footer_index, element);
+    ///Returns the distance between each member of an array where [F] has count elements
+    fn array_stride_of(count: usize) -> Result<usize, LayoutError> {
+        Ok(Self::array_layout_of(count, 1)?.1)
     }
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the header of the element has been initialized
-    pub fn get_header_ptr(&self, arr_index: usize) -> *const H {
-        self.get_element(arr_index).get_header_ptr()
+    unsafe fn get_footer_slice(ptr: *mut Self) -> *mut [F] {
+        addr_of_mut!((*ptr).footer)
     }
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the header of the element has been initialized
-    pub fn get_header_ptr_mut(&mut self, arr_index: usize) -> *mut H {
-        self.get_element(arr_index).get_header_ptr_mut()
+    unsafe fn get_header_ptr(ptr: *mut Self) -> *mut H {
+        addr_of_mut!((*ptr).header)
     }
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the footer of the element has been initialized
-    pub fn get_footer_ptr(&self, arr_index: usize) -> *const [F] {
-        self.get_element(arr_index).get_footer_ptr()
+    unsafe fn get_len(ptr: *const Self) -> usize {
+        ptr::metadata(ptr)
     }
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the footer of the element has been initialized
-    pub fn get_footer_ptr_mut(&mut self, arr_index: usize) -> *mut [F] {
-        self.get_element(arr_index).get_footer_ptr_mut()
+    ///Moves the header and footer out of `ptr`, leaving it uninitialized
+    ///
+    ///The Vec is allocated before anything is moved, so if that fails `ptr` is left untouched
+    unsafe fn read_parts(ptr: *mut Self) -> (H, Vec<F>) {
+        let len = unsafe { Self::get_len(ptr) };
+        let mut footer = Vec::with_capacity(len);
+
+        unsafe {
+            let header = Self::get_header_ptr(ptr).read();
+
+            ptr::copy_nonoverlapping(
+                Self::get_footer_slice(ptr).as_mut_ptr(),
+                footer.as_mut_ptr(),
+                len,
+            );
+            footer.set_len(len);
+
+            (header, footer)
+        }
     }
+}
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the element has been , arr_index: usizeinitialized
-    pub fn get_footer_element_ptr(&self, arr_index: usize, footer_index: usize) -> *const F {
-        self.get_element(arr_index)
-            .get_footer_element_ptr(footer_index)
+//Lets `Rc::make_mut` and `Arc::make_mut` clone a shared DstData
+unsafe impl<H: Clone, F: Clone> CloneToUninit for DstData<H, F> {
+    unsafe fn clone_to_uninit(&self, dest: *mut u8) {
+        let dest = ptr::from_raw_parts_mut::<Self>(dest, self.footer.len());
+
+        unsafe {
+            //Drops the cloned header again if cloning the footer panics
+            let mut header = PrefixGuard::new(DstData::get_header_ptr(dest));
+            header.push(self.header.clone());
+
+            self.footer
+                .clone_to_uninit(DstData::get_footer_slice(dest).cast::<u8>());
+
+            header.finish();
+        }
     }
+}
 
-    ///Reading from this pointer or turning it into a reference is undefined behavior
-    ///unless the element has been initialized
-    pub fn get_footer_element_ptr_mut(&mut self, arr_index: usize, footer_index: usize) -> *mut F {
-        self.get_element(arr_index)
-            .get_footer_element_ptr_mut(footer_index)
+///Indexes into the footer, with a `usize` for one element or a range for a subslice
+///
+///```
+///use custom_dst::Dst;
+///
+///let mut dst = Dst::new("header", &[1, 2, 3, 4]);
+///
+///dst[0] = 5;
+///
+///assert_eq!(dst[0], 5);
+///assert_eq!(dst[1..3], [2, 3]);
+///assert_eq!(dst.get_data()[..], [5, 2, 3, 4]);
+///```
+impl<H, F, I: SliceIndex<[F]>> Index<I> for DstData<H, F> {
+    type Output = I::Output;
+
+    #[track_caller]
+    fn index(&self, index: I) -> &I::Output {
+        &self.footer[index]
     }
 }
 
-pub struct DstArray<H, F> {
-    len: usize,
-    stride: usize,
-    ptr: *mut DstData<H, F>,
+impl<H, F, I: SliceIndex<[F]>> IndexMut<I> for DstData<H, F> {
+    #[track_caller]
+    fn index_mut(&mut self, index: I) -> &mut I::Output {
+        &mut self.footer[index]
+    }
 }
 
-impl<H, F> DstArray<H, F> {
-    fn get_stride(&self) -> usize {
-        self.stride
+impl<H: Clone, F: Clone> ToOwned for DstData<H, F> {
+    type Owned = Dst<H, F>;
+
+    ///Clones the value into a new Dst, see [`Dst::clone`]
+    fn to_owned(&self) -> Dst<H, F> {
+        Dst::try_clone_data_in(self, Global).unwrap_or_else(|err| err.handle())
     }
+}
 
-    pub fn get_header_ref(&self, arr_index: usize) -> &H {
-        &self[arr_index].header
+impl<H: PartialEq, F: PartialEq> PartialEq for DstData<H, F> {
+    ///Compares the headers and then the footers element by element, so footers of different
+    ///lengths are never equal
+    fn eq(&self, other: &Self) -> bool {
+        self.header == other.header && self.footer == other.footer
     }
+}
 
-    pub fn get_header_ref_mut(&mut self, arr_index: usize) -> &mut H {
-        &mut self[arr_index].header
+impl<H: Eq, F: Eq> Eq for DstData<H, F> {}
+
+impl<H: PartialOrd, F: PartialOrd> PartialOrd for DstData<H, F> {
+    ///Compares the headers, and then the footers lexicographically if the headers are equal
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        match self.header.partial_cmp(&other.header) {
+            Some(cmp::Ordering::Equal) => self.footer.partial_cmp(&other.footer),
+            ordering => ordering,
+        }
     }
+}
 
-    pub fn get_footer_ref(&self, arr_index: usize) -> &[F] {
-        &self[arr_index].footer
+impl<H: Ord, F: Ord> Ord for DstData<H, F> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.header
+            .cmp(&other.header)
+            .then_with(|| self.footer.cmp(&other.footer))
     }
+}
 
-    pub fn get_footer_ref_mut(&mut self, arr_index: usize) -> &mut [F] {
-        &mut self[arr_index].footer
+impl<H: Hash, F: Hash> Hash for DstData<H, F> {
+    ///Hashes the header and then the footer, including its length
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.header.hash(state);
+        self.footer.hash(state);
     }
+}
 
-    pub fn get_footer_len(&self) -> usize {
-        self.get_footer_ref(0).len()
+impl<H: fmt::Debug, F: fmt::Debug> fmt::Debug for DstData<H, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DstData")
+            .field("header", &self.header)
+            .field("footer", &&self.footer)
+            .finish()
     }
+}
 
-    pub fn get_mut_slice(&mut self, start: usize, end: usize) -> DstSliceMut<H, F> {
-        assert!(start < end);
-        assert!(end <= self.len);
+pub struct MaybeUninitDst<H: Sized, F: Sized, A: Allocator = Global> {
+    ptr: NonNull<DstData<H, F>>,
+    init: InitTracker,
+    alloc: A,
+}
 
-        DstSliceMut {
-            start: unsafe { self.ptr.byte_add(self.get_stride() * start) },
-            len: end - start,
-            stride: self.stride,
-            phantom: PhantomData,
+impl<H, F> MaybeUninitDst<H, F> {
+    ///Allocates an uninitialized Dst whose footer has `count` elements
+    ///
+    ///`count` may be zero, in which case only the header needs to be written
+    pub fn new(count: usize) -> MaybeUninitDst<H, F> {
+        Self::try_new(count).unwrap_or_else(|err| err.handle())
+    }
+
+    ///Allocates an uninitialized Dst whose footer has `count` elements, returning an error
+    ///instead of panicking or aborting if the allocation cannot be made
+    pub fn try_new(count: usize) -> Result<MaybeUninitDst<H, F>, DstError> {
+        Self::try_new_in(count, Global)
+    }
+
+    ///Allocates a Dst whose footer has `count` elements and whose bytes are all zero
+    ///
+    ///The zeroed parts still count as unwritten, see [`MaybeUninitDst::assume_zero_valid`]
+    ///for when zero is a valid value for them
+    pub fn new_zeroed(count: usize) -> MaybeUninitDst<H, F> {
+        Self::try_new_zeroed(count).unwrap_or_else(|err| err.handle())
+    }
+
+    ///Like [`MaybeUninitDst::new_zeroed`], but returns an error instead of panicking or
+    ///aborting if the allocation cannot be made
+    pub fn try_new_zeroed(count: usize) -> Result<MaybeUninitDst<H, F>, DstError> {
+        Self::try_new_zeroed_in(count, Global)
+    }
+
+    ///Allocates an uninitialized Dst with the longest footer for which the allocation takes
+    ///up at most `total_bytes`, see [`DstData::capacity_for`]
+    ///
+    ///Returns [`DstError::BudgetTooSmall`] if not even the header fits
+    ///
+    ///```
+    ///use custom_dst::MaybeUninitDst;
+    ///
+    ///let page = MaybeUninitDst::<[u32; 3], u64>::new_fitting(4096).unwrap();
+    ///
+    ///assert_eq!(page.get_footer_len(), 510);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `F` is zero-sized, as any number of such elements fits
+    pub fn new_fitting(total_bytes: usize) -> Result<MaybeUninitDst<H, F>, DstError> {
+        assert!(
+            mem::size_of::<F>() != 0,
+            "any number of zero-sized footer elements fits the budget"
+        );
+
+        let empty_size = DstData::<H, F>::layout_of(0)?.size();
+        if empty_size > total_bytes {
+            return Err(DstError::BudgetTooSmall(empty_size));
         }
+
+        Self::try_new(DstData::<H, F>::capacity_for(total_bytes))
     }
 
-    pub fn get_slice(&mut self, start: usize, end: usize) -> DstSlice<H, F> {
-        assert!(start < end);
-        assert!(end <= self.len);
+    ///Consumes the `MaybeUninitDst`, returning the raw allocation without freeing it
+    ///
+    ///The caller becomes responsible for the memory, which can be handed back with
+    ///[`MaybeUninitDst::from_raw`]
+    pub fn into_raw(self) -> *mut DstData<H, F> {
+        let mut this = ManuallyDrop::new(self);
+        unsafe { drop_in_place(&mut this.init) };
 
-        DstSlice {
-            start: unsafe { self.ptr.byte_add(self.get_stride() * start) },
-            len: end - start,
-            stride: self.stride,
+        this.ptr.as_ptr()
+    }
+
+    ///# Safety
+    ///
+    /// `ptr` must have been returned by [`MaybeUninitDst::into_raw`] and must not be used
+    /// again afterwards
+    pub unsafe fn from_raw(ptr: *mut DstData<H, F>) -> MaybeUninitDst<H, F> {
+        MaybeUninitDst {
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            init: InitTracker::untracked(),
+            alloc: Global,
+        }
+    }
+}
+
+impl<H, F, A: Allocator> MaybeUninitDst<H, F, A> {
+    ///Like [`MaybeUninitDst::new`], but allocates from `alloc`
+    pub fn new_in(count: usize, alloc: A) -> MaybeUninitDst<H, F, A> {
+        Self::try_new_in(count, alloc).unwrap_or_else(|err| err.handle())
+    }
+
+    ///Like [`MaybeUninitDst::try_new`], but allocates from `alloc`
+    pub fn try_new_in(count: usize, alloc: A) -> Result<MaybeUninitDst<H, F, A>, DstError> {
+        Ok(MaybeUninitDst {
+            ptr: unsafe { DstData::alloc_self(count, &alloc)? },
+            init: InitTracker::new::<H, F>(1, count),
+            alloc,
+        })
+    }
+
+    ///Like [`MaybeUninitDst::new_zeroed`], but allocates from `alloc`
+    pub fn new_zeroed_in(count: usize, alloc: A) -> MaybeUninitDst<H, F, A> {
+        Self::try_new_zeroed_in(count, alloc).unwrap_or_else(|err| err.handle())
+    }
+
+    ///Like [`MaybeUninitDst::try_new_zeroed`], but allocates from `alloc`
+    pub fn try_new_zeroed_in(count: usize, alloc: A) -> Result<MaybeUninitDst<H, F, A>, DstError> {
+        Ok(MaybeUninitDst {
+            ptr: unsafe { DstData::alloc_self_zeroed(count, &alloc)? },
+            init: InitTracker::new::<H, F>(1, count),
+            alloc,
+        })
+    }
+
+    fn element_mut(&mut self) -> UninitElementMut<'_, H, F> {
+        UninitElementMut {
+            ptr: self.ptr,
+            init: &mut self.init,
+            arr_index: 0,
+        }
+    }
+
+    pub fn write_header(&mut self, header: H) {
+        self.element_mut().write_header(header);
+    }
+
+    pub fn write_footer(&mut self, footer: &[F])
+    where
+        F: Copy,
+    {
+        self.element_mut().write_footer(footer);
+    }
+
+    ///Writes a clone of every element of `footer` into the footer
+    ///
+    ///If a clone panics, the elements cloned so far are dropped again
+    pub fn write_footer_cloned(&mut self, footer: &[F])
+    where
+        F: Clone,
+    {
+        self.element_mut().write_footer_cloned(footer);
+    }
+
+    ///Moves every element of `footer` into the footer
+    pub fn write_footer_from_vec(&mut self, footer: Vec<F>) {
+        self.element_mut().write_footer_from_vec(footer);
+    }
+
+    pub fn write_footer_element(&mut self, index: usize, element: F) {
+        self.element_mut().write_footer_element(index, element);
+    }
+
+    ///Copies `src` into the footer elements starting at `offset`, see
+    ///[`UninitElementMut::write_footer_slice_at`]
+    ///
+    ///```
+    ///use custom_dst::MaybeUninitDst;
+    ///
+    ///let mut packet = MaybeUninitDst::<u16, u8>::new(6);
+    ///
+    ///packet.write_header(6);
+    ///packet.write_footer_slice_at(0, b"he");
+    ///packet.write_footer_slice_at(2, b"llo");
+    ///packet.fill_footer_range(5..6, b'!');
+    ///
+    ///let packet = unsafe { packet.assume_init() };
+    ///
+    ///assert_eq!(packet.get_footer_ref(), b"hello!");
+    ///```
+    #[track_caller]
+    pub fn write_footer_slice_at(&mut self, offset: usize, src: &[F])
+    where
+        F: Copy,
+    {
+        self.element_mut().write_footer_slice_at(offset, src);
+    }
+
+    ///Writes `value` into every footer element in `range`, see
+    ///[`UninitElementMut::fill_footer_range`]
+    #[track_caller]
+    pub fn fill_footer_range(&mut self, range: Range<usize>, value: F)
+    where
+        F: Clone,
+    {
+        self.element_mut().fill_footer_range(range, value);
+    }
+
+    ///Zeroes every footer element in `range`
+    ///
+    ///# Safety
+    ///
+    /// All-zero bytes must be a valid value of `F`
+    #[track_caller]
+    pub unsafe fn zero_footer_range(&mut self, range: Range<usize>) {
+        unsafe { self.element_mut().zero_footer_range(range) };
+    }
+
+    ///Writes the items of `iter` into the footer in order, see
+    ///[`UninitElementMut::write_footer_from_iter`]
+    ///
+    ///```
+    ///use custom_dst::{FillError, MaybeUninitDst};
+    ///
+    ///let mut dst = MaybeUninitDst::<u8, u32>::new(3);
+    ///
+    ///assert_eq!(dst.write_footer_from_iter(0..2), Err(FillError::TooFew(2)));
+    ///assert_eq!(dst.write_footer_from_iter(0..4), Err(FillError::TooMany));
+    ///assert_eq!(dst.write_footer_from_iter(0..3), Ok(()));
+    ///```
+    #[track_caller]
+    pub fn write_footer_from_iter(
+        &mut self,
+        iter: impl IntoIterator<Item = F>,
+    ) -> Result<(), FillError> {
+        self.element_mut().write_footer_from_iter(iter)
+    }
+
+    ///# Safety
+    ///
+    /// Implies that all parts of the Dst have been initialized
+    ///
+    /// In debug builds, this panics if a header or footer element was never written
+    #[track_caller]
+    pub unsafe fn assume_init(self) -> Dst<H, F, A> {
+        self.init.debug_assert_init();
+
+        let mut this = ManuallyDrop::new(self);
+        unsafe { drop_in_place(&mut this.init) };
+
+        Dst {
+            ptr: this.ptr,
             phantom: PhantomData,
+            alloc: unsafe { ptr::read(&this.alloc) },
         }
     }
 
-    pub fn get_mut_arr_element(&mut self, index: usize) -> &mut DstData<H, F> {
-        assert!(index < self.len);
+    ///Treats every part that was not written as holding the zeroes it was allocated with
+    ///
+    ///# Safety
+    ///
+    /// The `MaybeUninitDst` must come from [`MaybeUninitDst::new_zeroed`], and all-zero bytes
+    /// must be a valid value of `H` and `F` unless those parts were written
+    pub unsafe fn assume_zero_valid(mut self) -> Dst<H, F, A> {
+        self.init = InitTracker::untracked();
 
-        unsafe { &mut *self.ptr.byte_add(self.get_stride() * index) }
+        unsafe { self.assume_init() }
     }
 
-    pub fn swap(&mut self, arr: &mut DstArray<H, F>) {
-        std::mem::swap(&mut self.ptr, &mut arr.ptr);
-        std::mem::swap(&mut self.len, &mut arr.len);
-        std::mem::swap(&mut self.stride, &mut arr.stride);
+    ///Returns the initialized Dst if every part of it was written, or a report of the parts
+    ///that were not
+    ///
+    ///If the report is returned, the allocation is freed without dropping anything
+    ///
+    ///Parts written through the mutable pointer getters are assumed to have been written as
+    ///soon as the pointer was handed out, and a `MaybeUninitDst` reconstructed with
+    ///[`MaybeUninitDst::from_raw`] is always assumed to be fully written
+    #[cfg(feature = "init-tracking")]
+    pub fn try_assume_init(self) -> Result<Dst<H, F, A>, UninitializedReport> {
+        match self.init.report() {
+            Some(report) => Err(report),
+            None => Ok(unsafe { self.assume_init() }),
+        }
     }
 
-    pub fn get_arr_element(&self, index: usize) -> &DstData<H, F> {
-        assert!(index < self.len);
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the header has been initialized
+    pub fn get_header_ptr(&self) -> *const H {
+        unsafe { DstData::get_header_ptr(self.ptr.as_ptr()) as *const H }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the header has been initialized
+    pub fn get_header_ptr_mut(&mut self) -> *mut H {
+        self.element_mut().get_header_ptr_mut()
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the footer has been initialized
+    pub fn get_footer_ptr(&self) -> *const [F] {
+        unsafe { DstData::get_footer_slice(self.ptr.as_ptr()) as *const [F] }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the footer has been initialized
+    pub fn get_footer_ptr_mut(&mut self) -> *mut [F] {
+        self.element_mut().get_footer_ptr_mut()
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the element has been initialized
+    #[track_caller]
+    pub fn get_footer_element_ptr(&self, index: usize) -> *const F {
+        check_index(index, self.get_footer_len());
 
         unsafe {
-            &*transmute::<*mut DstData<H, F>, *const DstData<H, F>>(
-                self.ptr.byte_add(self.get_stride() * index),
-            )
+            (DstData::get_footer_slice(self.ptr.as_ptr()) as *const [F])
+                .as_ptr()
+                .add(index)
         }
     }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the element has been initialized
+    ///
+    ///```
+    ///use custom_dst::MaybeUninitDst;
+    ///
+    ///let mut dst = MaybeUninitDst::<u32, u16>::new(2);
+    ///
+    ///dst.write_header(0);
+    ///unsafe {
+    ///    dst.get_footer_element_ptr_mut(0).write(1);
+    ///    dst.get_footer_element_ptr_mut(1).write(2);
+    ///}
+    ///
+    ///let dst = unsafe { dst.assume_init() };
+    ///
+    ///assert_eq!(dst.get_footer_ref(), [1, 2]);
+    ///```
+    pub fn get_footer_element_ptr_mut(&mut self, index: usize) -> *mut F {
+        self.element_mut().get_footer_element_ptr_mut(index)
+    }
+
+    pub fn get_footer_len(&self) -> usize {
+        unsafe { DstData::get_len(self.ptr.as_ptr()) }
+    }
 }
 
-trait SplitSliceMutExt<'a, H, F> {
-    unsafe fn split_at_mut(
-        self,
-        len: usize,
-        mid: usize,
-    ) -> (DstSliceMut<'a, H, F>, DstSliceMut<'a, H, F>);
-    unsafe fn split_at_mut_unchecked(
-        self,
-        len: usize,
-        mid: usize,
-    ) -> (DstSliceMut<'a, H, F>, DstSliceMut<'a, H, F>);
+impl<H, F, A: Allocator> Drop for MaybeUninitDst<H, F, A> {
+    ///Frees the allocation without dropping the header or footer, as they may be uninitialized
+    fn drop(&mut self) {
+        unsafe {
+            let layout = DstData::layout_of_raw(self.ptr.as_ptr());
+
+            dealloc_layout(&self.alloc, self.ptr.cast::<u8>().as_ptr(), layout);
+        }
+    }
 }
 
-trait SplitSliceExt<'a, H, F> {
-    unsafe fn split_at(self, len: usize, mid: usize) -> (DstSlice<'a, H, F>, DstSlice<'a, H, F>);
-    unsafe fn split_at_unchecked(
-        self,
-        len: usize,
-        mid: usize,
-    ) -> (DstSlice<'a, H, F>, DstSlice<'a, H, F>);
+///Writes the parts of a single uninitialized member of a [`MaybeUninitDst`] or
+///[`MaybeUninitDstArray`]
+///
+///The writer borrows the allocation rather than owning it, so dropping it frees nothing
+pub struct UninitElementMut<'a, H, F> {
+    ptr: NonNull<DstData<H, F>>,
+    init: &'a mut InitTracker,
+    arr_index: usize,
 }
 
-impl<H, F> Drop for DstArray<H, F> {
-    fn drop(&mut self) {
-        let mut ptr = unsafe { self.ptr.byte_add(self.get_stride()) };
+impl<H, F> UninitElementMut<'_, H, F> {
+    fn footer_start(&self) -> *mut F {
+        unsafe { DstData::get_footer_slice(self.ptr.as_ptr()).as_mut_ptr() }
+    }
 
-        for _ in 0..self.len {
-            unsafe {
-                drop_in_place(ptr);
-                ptr = ptr.byte_add(self.get_stride());
-            }
+    #[track_caller]
+    pub fn write_header(&mut self, header: H) {
+        self.init
+            .check_header(self.arr_index, mem::needs_drop::<H>());
+
+        unsafe {
+            DstData::get_header_ptr(self.ptr.as_ptr()).write(header);
         }
 
-        let layout = DstData::<H, F>::layout_of(self.get_footer_len()).unwrap();
+        self.init.mark_header(self.arr_index);
+    }
+
+    pub fn write_footer(&mut self, footer: &[F])
+    where
+        F: Copy,
+    {
+        let footer_len = self.get_footer_len();
+
+        assert!(footer.len() == footer_len);
 
         unsafe {
-            dealloc(self.ptr as *mut u8, layout);
+            ptr::copy_nonoverlapping(footer.as_ptr(), self.footer_start(), footer_len);
         }
+
+        self.init.mark_footer(self.arr_index, 0..footer_len);
     }
-}
 
-impl<H, F> Index<usize> for DstArray<H, F> {
-    type Output = DstData<H, F>;
+    ///Writes a clone of every element of `footer` into the footer
+    ///
+    ///If a clone panics, the elements cloned so far are dropped again
+    #[track_caller]
+    pub fn write_footer_cloned(&mut self, footer: &[F])
+    where
+        F: Clone,
+    {
+        let footer_len = self.get_footer_len();
 
-    fn index(&self, index: usize) -> &DstData<H, F> {
-        let ptr = unsafe { self.ptr.byte_add(self.get_stride() * index) };
+        assert!(footer.len() == footer_len);
 
-        assert!(ptr <= unsafe { self.ptr.byte_add(self.get_stride() * self.len) });
+        self.init
+            .check_footer(self.arr_index, 0..footer_len, mem::needs_drop::<F>());
 
-        unsafe { &*ptr }
+        //Copies `Copy` footers in one go, and drops the elements cloned so far if a clone panics
+        unsafe { footer.clone_to_uninit(self.footer_start().cast::<u8>()) };
+
+        self.init.mark_footer(self.arr_index, 0..footer_len);
     }
-}
 
-impl<H, F> IndexMut<usize> for DstArray<H, F> {
-    fn index_mut(&mut self, index: usize) -> &mut DstData<H, F> {
-        let ptr = unsafe { self.ptr.byte_add(self.get_stride() * index) };
+    ///Moves every element of `footer` into the footer
+    #[track_caller]
+    pub fn write_footer_from_vec(&mut self, mut footer: Vec<F>) {
+        let footer_len = self.get_footer_len();
+
+        assert!(footer.len() == footer_len);
+
+        self.init
+            .check_footer(self.arr_index, 0..footer_len, mem::needs_drop::<F>());
+
+        unsafe {
+            ptr::copy_nonoverlapping(footer.as_ptr(), self.footer_start(), footer_len);
+
+            //The elements now belong to the Dst, so only the Vec's buffer may be freed
+            footer.set_len(0);
+        }
+
+        self.init.mark_footer(self.arr_index, 0..footer_len);
+    }
+
+    #[track_caller]
+    pub fn write_footer_element(&mut self, index: usize, element: F) {
+        let footer_len = self.get_footer_len();
+        assert!(index < footer_len);
+
+        self.init
+            .check_footer(self.arr_index, index..index + 1, mem::needs_drop::<F>());
+
+        unsafe {
+            self.footer_start().add(index).write(element);
+        }
+
+        self.init.mark_footer(self.arr_index, index..index + 1);
+    }
+
+    #[track_caller]
+    fn check_footer_range(&self, range: &Range<usize>) {
+        let footer_len = self.get_footer_len();
+
+        assert!(
+            range.start <= range.end && range.end <= footer_len,
+            "footer range {range:?} is out of bounds for a footer of {footer_len} elements"
+        );
+    }
+
+    ///Copies `src` into the footer elements starting at `offset`, so that a footer can be
+    ///written from several fragments
+    ///
+    ///# Panics
+    ///
+    ///Panics if `offset + src.len()` is past the end of the footer
+    #[track_caller]
+    pub fn write_footer_slice_at(&mut self, offset: usize, src: &[F])
+    where
+        F: Copy,
+    {
+        let range = offset..offset.saturating_add(src.len());
+        self.check_footer_range(&range);
+
+        unsafe {
+            ptr::copy_nonoverlapping(src.as_ptr(), self.footer_start().add(offset), src.len());
+        }
+
+        self.init.mark_footer(self.arr_index, range);
+    }
+
+    ///Writes `value` into every footer element in `range`, cloning it for all but the last
+    ///
+    ///If a clone panics, the elements written so far are dropped again
+    ///
+    ///# Panics
+    ///
+    ///Panics if `range` is out of bounds of the footer
+    #[track_caller]
+    pub fn fill_footer_range(&mut self, range: Range<usize>, value: F)
+    where
+        F: Clone,
+    {
+        self.check_footer_range(&range);
+
+        self.init
+            .check_footer(self.arr_index, range.clone(), mem::needs_drop::<F>());
+
+        let mut value = Some(value);
+        let mut guard = PrefixGuard::new(unsafe { self.footer_start().add(range.start) });
+
+        for index in range.clone() {
+            let element = if index + 1 == range.end {
+                value.take()
+            } else {
+                value.clone()
+            };
+
+            unsafe { guard.push(element.unwrap()) };
+        }
+
+        guard.finish();
+
+        self.init.mark_footer(self.arr_index, range);
+    }
+
+    ///Zeroes every footer element in `range`
+    ///
+    ///# Safety
+    ///
+    /// All-zero bytes must be a valid value of `F`
+    ///
+    ///# Panics
+    ///
+    ///Panics if `range` is out of bounds of the footer
+    #[track_caller]
+    pub unsafe fn zero_footer_range(&mut self, range: Range<usize>) {
+        self.check_footer_range(&range);
+
+        self.init
+            .check_footer(self.arr_index, range.clone(), mem::needs_drop::<F>());
+
+        unsafe {
+            self.footer_start()
+                .add(range.start)
+                .write_bytes(0, range.end - range.start);
+        }
+
+        self.init.mark_footer(self.arr_index, range);
+    }
+
+    ///Writes the items of `iter` into the footer in order
+    ///
+    ///If the iterator yields fewer or more items than the footer has elements, the items
+    ///written so far and the first extra item are dropped and the footer is left unwritten
+    #[track_caller]
+    pub fn write_footer_from_iter(
+        &mut self,
+        iter: impl IntoIterator<Item = F>,
+    ) -> Result<(), FillError> {
+        let footer_len = self.get_footer_len();
+
+        self.init
+            .check_footer(self.arr_index, 0..footer_len, mem::needs_drop::<F>());
+
+        let mut iter = iter.into_iter();
+        let mut guard = PrefixGuard::new(self.footer_start());
+
+        for yielded in 0..footer_len {
+            match iter.next() {
+                Some(element) => unsafe { guard.push(element) },
+                None => return Err(FillError::TooFew(yielded)),
+            }
+        }
+
+        if iter.next().is_some() {
+            return Err(FillError::TooMany);
+        }
+
+        guard.finish();
+
+        self.init.mark_footer(self.arr_index, 0..footer_len);
+
+        Ok(())
+    }
+
+    ///Writes `header` and a footer of the items `footer` yields
+    ///
+    ///# Panics
+    ///
+    ///Panics if `footer` yields fewer or more items than the footer has elements, dropping
+    ///the header and the items
+    #[track_caller]
+    fn write_parts(&mut self, header: H, footer: impl IntoIterator<Item = F>) {
+        let footer_len = self.get_footer_len();
+
+        match self.write_footer_from_iter(footer) {
+            Ok(()) => self.write_header(header),
+            Err(FillError::TooFew(_)) => panic!(
+                "the footer of member {} has fewer than {footer_len} elements",
+                self.arr_index
+            ),
+            Err(FillError::TooMany) => panic!(
+                "the footer of member {} has more than {footer_len} elements",
+                self.arr_index
+            ),
+        }
+    }
+
+    ///Writes every footer element `index` with `f(index)` in order, stopping at the first
+    ///error
+    ///
+    ///If `f` panics or fails, the elements written so far are dropped again
+    #[track_caller]
+    fn try_write_footer_with<E>(
+        &mut self,
+        mut f: impl FnMut(usize) -> Result<F, E>,
+    ) -> Result<(), E> {
+        let footer_len = self.get_footer_len();
+
+        self.init
+            .check_footer(self.arr_index, 0..footer_len, mem::needs_drop::<F>());
+
+        let mut guard = PrefixGuard::new(self.footer_start());
+
+        for index in 0..footer_len {
+            let element = f(index)?;
+            unsafe { guard.push(element) };
+        }
+
+        guard.finish();
+
+        self.init.mark_footer(self.arr_index, 0..footer_len);
+
+        Ok(())
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the header has been initialized
+    pub fn get_header_ptr(&self) -> *const H {
+        unsafe { DstData::get_header_ptr(self.ptr.as_ptr()) as *const H }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the header has been initialized
+    pub fn get_header_ptr_mut(&mut self) -> *mut H {
+        self.init.mark_header(self.arr_index);
+
+        unsafe { DstData::get_header_ptr(self.ptr.as_ptr()) }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the footer has been initialized
+    pub fn get_footer_ptr(&self) -> *const [F] {
+        unsafe { DstData::get_footer_slice(self.ptr.as_ptr()) as *const [F] }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the footer has been initialized
+    pub fn get_footer_ptr_mut(&mut self) -> *mut [F] {
+        self.init
+            .mark_footer(self.arr_index, 0..self.get_footer_len());
+
+        unsafe { DstData::get_footer_slice(self.ptr.as_ptr()) }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the element has been initialized
+    #[track_caller]
+    pub fn get_footer_element_ptr_mut(&mut self, index: usize) -> *mut F {
+        check_index(index, self.get_footer_len());
+
+        self.init.mark_footer(self.arr_index, index..index + 1);
+
+        unsafe { self.footer_start().add(index) }
+    }
+
+    pub fn get_footer_len(&self) -> usize {
+        unsafe { DstData::get_len(self.ptr.as_ptr()) }
+    }
+}
+
+unsafe impl<H: Send, F: Send> Send for UninitElementMut<'_, H, F> {}
+
+///A contiguous range of the members of a [`MaybeUninitDstArray`] that one thread of
+///[`MaybeUninitDstArray::par_init`] initializes
+struct ParInitRange<H, F> {
+    start: NonNull<DstData<H, F>>,
+    range: Range<usize>,
+}
+
+unsafe impl<H: Send, F: Send> Send for ParInitRange<H, F> {}
+
+impl<H, F> ParInitRange<H, F> {
+    ///Builds every member of the range from the parts `f` returns for its index
+    ///
+    ///The writes are checked against `init`, the tracker of the whole array, which the caller
+    ///marks once every range is done. If `f` panics, the members of the range initialized so
+    ///far are dropped
+    ///
+    ///# Safety
+    ///
+    /// No other thread may access the members of the range
+    #[track_caller]
+    unsafe fn init<G>(
+        self,
+        stride: usize,
+        footer_len: usize,
+        init: &InitTracker,
+        f: &impl Fn(usize) -> (H, G),
+    ) where
+        G: IntoIterator<Item = F>,
+    {
+        let mut untracked = InitTracker::untracked();
+        let mut guard = ArrayPrefixGuard::new(self.start, stride);
+
+        for (local_index, arr_index) in self.range.enumerate() {
+            init.check_header(arr_index, mem::needs_drop::<H>());
+            init.check_footer(arr_index, 0..footer_len, mem::needs_drop::<F>());
+
+            let (header, footer) = f(arr_index);
+            let mut element = UninitElementMut {
+                ptr: unsafe { self.start.byte_add(stride * local_index) },
+                init: &mut untracked,
+                arr_index,
+            };
+
+            element.write_parts(header, footer);
+            unsafe { guard.push_initialized() };
+        }
+
+        guard.finish();
+    }
+}
+
+///An owned, initialized [`DstData`] in a single heap allocation
+///
+///Like `Box`, a `Dst` is covariant over its header and footer types:
+///
+///```
+///use custom_dst::{Dst, MaybeUninitDst};
+///
+///fn shorten<'a>(dst: Dst<&'static str, u8>) -> Dst<&'a str, u8> {
+///    dst
+///}
+///
+///let mut dst = MaybeUninitDst::<&'static str, u8>::new(1);
+///
+///dst.write_header("header");
+///dst.write_footer(&[0]);
+///
+///let dst = shorten(unsafe { dst.assume_init() });
+///
+///assert_eq!(*dst.get_header_ref(), "header");
+///```
+pub struct Dst<H: Sized, F: Sized, A: Allocator = Global> {
+    ptr: NonNull<DstData<H, F>>,
+    phantom: PhantomData<DstData<H, F>>,
+    alloc: A,
+}
+
+impl<H, F> Dst<H, F> {
+    ///Allocates a Dst whose footer has `count` elements, which must be initialized before
+    ///it can be used
+    pub fn new_uninit(count: usize) -> MaybeUninitDst<H, F> {
+        MaybeUninitDst::new(count)
+    }
+
+    ///Allocates a Dst whose footer has `count` elements, returning an error instead of
+    ///panicking or aborting if the allocation cannot be made
+    pub fn try_new_uninit(count: usize) -> Result<MaybeUninitDst<H, F>, DstError> {
+        MaybeUninitDst::try_new(count)
+    }
+
+    ///Allocates a Dst whose footer has `count` elements, with every byte zeroed
+    ///
+    ///The kernel hands out zeroed pages for large allocations, so this avoids writing the
+    ///memory again
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let buffer = unsafe { Dst::<u64, u8>::new_zeroed(4096) };
+    ///
+    ///assert!(buffer.get_footer_ref().iter().all(|&byte| byte == 0));
+    ///```
+    ///
+    ///# Safety
+    ///
+    /// All-zero bytes must be a valid value of both `H` and `F`
+    pub unsafe fn new_zeroed(count: usize) -> Dst<H, F> {
+        unsafe { MaybeUninitDst::new_zeroed(count).assume_zero_valid() }
+    }
+
+    ///Allocates a Dst holding `header` and a clone of every element of `footer`
+    ///
+    ///Footers of `Copy` elements are copied in one go. If cloning an element panics, the
+    ///header and the elements cloned so far are dropped and the allocation is freed
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::new(String::from("header"), &[1u8, 2, 3]);
+    ///
+    ///assert_eq!(dst.get_header_ref(), "header");
+    ///assert_eq!(dst.get_footer_ref(), [1, 2, 3]);
+    ///```
+    pub fn new(header: H, footer: &[F]) -> Dst<H, F>
+    where
+        F: Clone,
+    {
+        Self::new_in(header, footer, Global)
+    }
+
+    ///Allocates a Dst holding `header` and the elements of `footer`, moving them rather
+    ///than cloning them
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::from_vec(0u8, vec![String::from("a"), String::from("b")]);
+    ///
+    ///assert_eq!(dst.get_footer_ref(), ["a", "b"]);
+    ///```
+    pub fn from_vec(header: H, footer: Vec<F>) -> Dst<H, F> {
+        Self::from_vec_in(header, footer, Global)
+    }
+
+    ///Allocates a Dst holding `header` and a footer of `len` elements, where element
+    ///`index` is `f(index)`
+    ///
+    ///If `f` panics, the header and the elements written so far are dropped and the
+    ///allocation is freed
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let squares = Dst::new_with("squares", 4, |index| index * index);
+    ///
+    ///assert_eq!(squares.get_footer_ref(), [0, 1, 4, 9]);
+    ///```
+    pub fn new_with(header: H, len: usize, mut f: impl FnMut(usize) -> F) -> Dst<H, F> {
+        match Self::try_new_with(header, len, |index| Ok::<F, Infallible>(f(index))) {
+            Ok(dst) => dst,
+            Err(never) => match never {},
+        }
+    }
+
+    ///Like [`Dst::new_with`], but stops at the first error `f` returns
+    ///
+    ///The header and the elements written so far are dropped and the allocation is freed
+    ///before the error is returned
+    pub fn try_new_with<E>(
+        header: H,
+        len: usize,
+        f: impl FnMut(usize) -> Result<F, E>,
+    ) -> Result<Dst<H, F>, E> {
+        let mut dst = MaybeUninitDst::new(len);
+
+        dst.element_mut().try_write_footer_with(f)?;
+        dst.write_header(header);
+
+        Ok(unsafe { dst.assume_init() })
+    }
+
+    ///Allocates a Dst holding `header` and `count` copies of `element`
+    ///
+    ///`element` is cloned into every slot but the last, which it is moved into. If a clone
+    ///panics, the header and the elements written so far are dropped
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::filled((), 0xffu8, 4);
+    ///
+    ///assert_eq!(dst.get_footer_ref(), [0xff; 4]);
+    ///```
+    pub fn filled(header: H, element: F, count: usize) -> Dst<H, F>
+    where
+        F: Clone,
+    {
+        let mut element = Some(element);
+
+        //For one-byte `Copy` elements this loop is lowered to a memset
+        Self::new_with(header, count, |index| {
+            if index + 1 == count {
+                element.take()
+            } else {
+                element.clone()
+            }
+            .unwrap()
+        })
+    }
+
+    ///Allocates a Dst whose header and `count` footer elements are their default values
+    pub fn from_default(count: usize) -> Dst<H, F>
+    where
+        H: Default,
+        F: Default,
+    {
+        Self::new_with(H::default(), count, |_| F::default())
+    }
+
+    ///Allocates a Dst holding `header` and the items of `iter`, using the length the
+    ///iterator reports
+    ///
+    ///# Panics
+    ///
+    ///Panics if the iterator yields fewer or more items than its reported length, dropping
+    ///everything written so far
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::from_header_and_iter('h', [1, 2, 3].iter().map(|x| x * 10));
+    ///
+    ///assert_eq!(dst.get_footer_ref(), [10, 20, 30]);
+    ///```
+    #[track_caller]
+    pub fn from_header_and_iter<I>(header: H, iter: I) -> Dst<H, F>
+    where
+        I: IntoIterator<Item = F>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut iter = iter.into_iter();
+        let mut dst = MaybeUninitDst::new(iter.len());
+
+        let written = dst
+            .element_mut()
+            .try_write_footer_with(|_| iter.next().ok_or(()));
+        assert!(
+            written.is_ok(),
+            "the iterator yielded fewer items than its reported length"
+        );
+
+        dst.write_header(header);
+        let dst = unsafe { dst.assume_init() };
+
+        assert!(
+            iter.next().is_none(),
+            "the iterator yielded more items than its reported length"
+        );
+
+        dst
+    }
+
+    ///Like [`Dst::from_header_and_iter`], but trusts the length the iterator reports
+    ///
+    ///Any items past the reported length are left in the iterator
+    ///
+    ///# Safety
+    ///
+    /// The iterator must yield at least as many items as its reported length
+    pub unsafe fn from_header_and_iter_unchecked<I>(header: H, iter: I) -> Dst<H, F>
+    where
+        I: IntoIterator<Item = F>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let mut iter = iter.into_iter();
+
+        Self::new_with(header, iter.len(), |_| unsafe {
+            iter.next().unwrap_unchecked()
+        })
+    }
+
+    ///Consumes the Dst, returning a pointer to it without dropping it or freeing the
+    ///allocation
+    ///
+    ///The caller becomes responsible for the Dst, which can be reconstructed with
+    ///[`Dst::from_raw`] so that it is dropped properly
+    pub fn into_raw(self) -> *mut DstData<H, F> {
+        ManuallyDrop::new(self).ptr.as_ptr()
+    }
+
+    ///Takes ownership of a Dst that was turned into a pointer by [`Dst::into_raw`]
+    ///
+    ///# Safety
+    ///
+    /// `ptr` must have been returned by [`Dst::into_raw`] for the same `H` and `F`, and may
+    /// only be passed to this function once
+    pub unsafe fn from_raw(ptr: *mut DstData<H, F>) -> Dst<H, F> {
+        Dst {
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            phantom: PhantomData,
+            alloc: Global,
+        }
+    }
+
+    ///Like [`Dst::into_raw`], but splits the pointer into a thin pointer and the footer
+    ///length, for interfaces that can only store a `void*` and a length
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::new(1u32, &[2u8, 3]);
+    ///let (ptr, len) = dst.into_raw_parts();
+    ///
+    ///let dst = unsafe { Dst::<u32, u8>::from_raw_parts(ptr, len) };
+    ///
+    ///assert_eq!(dst.get_footer_ref(), [2, 3]);
+    ///```
+    pub fn into_raw_parts(self) -> (*mut u8, usize) {
+        let ptr = self.into_raw();
+
+        (ptr.cast::<u8>(), ptr::metadata(ptr))
+    }
+
+    ///Takes ownership of a Dst that was split up by [`Dst::into_raw_parts`]
+    ///
+    ///# Safety
+    ///
+    /// `ptr` and `len` must have been returned by [`Dst::into_raw_parts`] for the same `H`
+    /// and `F`, and may only be passed to this function once
+    pub unsafe fn from_raw_parts(ptr: *mut u8, len: usize) -> Dst<H, F> {
+        unsafe { Self::from_raw(ptr::from_raw_parts_mut(ptr, len)) }
+    }
+}
+
+impl<H, F, A: Allocator> Dst<H, F, A> {
+    ///Like [`Dst::new_uninit`], but allocates from `alloc`
+    pub fn new_uninit_in(count: usize, alloc: A) -> MaybeUninitDst<H, F, A> {
+        MaybeUninitDst::new_in(count, alloc)
+    }
+
+    ///Like [`Dst::try_new_uninit`], but allocates from `alloc`
+    pub fn try_new_uninit_in(count: usize, alloc: A) -> Result<MaybeUninitDst<H, F, A>, DstError> {
+        MaybeUninitDst::try_new_in(count, alloc)
+    }
+
+    ///Like [`Dst::new`], but allocates from `alloc`
+    pub fn new_in(header: H, footer: &[F], alloc: A) -> Dst<H, F, A>
+    where
+        F: Clone,
+    {
+        let mut dst = MaybeUninitDst::new_in(footer.len(), alloc);
+
+        dst.write_footer_cloned(footer);
+        dst.write_header(header);
+
+        unsafe { dst.assume_init() }
+    }
+
+    ///Like [`Dst::from_vec`], but allocates from `alloc`
+    pub fn from_vec_in(header: H, footer: Vec<F>, alloc: A) -> Dst<H, F, A> {
+        let mut dst = MaybeUninitDst::new_in(footer.len(), alloc);
+
+        dst.write_header(header);
+        dst.write_footer_from_vec(footer);
+
+        unsafe { dst.assume_init() }
+    }
+
+    pub fn get_header_ref(&self) -> &H {
+        unsafe { self.ptr.as_ref().get_header() }
+    }
+
+    pub fn get_header_ref_mut(&mut self) -> &mut H {
+        unsafe { self.ptr.as_mut().get_header_mut() }
+    }
+
+    pub fn get_footer_ref(&self) -> &[F] {
+        unsafe { self.ptr.as_ref().get_footer() }
+    }
+
+    pub fn get_footer_ref_mut(&mut self) -> &mut [F] {
+        unsafe { self.ptr.as_mut().get_footer_mut() }
+    }
+
+    pub fn get_footer_len(&self) -> usize {
+        self.get_footer_ref().len()
+    }
+
+    ///Returns the value as a [`DstData`] reference
+    pub fn get_data(&self) -> &DstData<H, F> {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    ///Returns the value as a mutable [`DstData`] reference
+    pub fn get_data_mut(&mut self) -> &mut DstData<H, F> {
+        unsafe { self.ptr.as_mut() }
+    }
+
+    ///Returns a pointer to the allocation, without giving up ownership of it
+    pub fn as_ptr(&self) -> *const DstData<H, F> {
+        self.ptr.as_ptr().cast_const()
+    }
+
+    ///Returns `true` if both Dsts are the same allocation, rather than merely equal
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::new(0u8, &[1, 2]);
+    ///
+    ///assert!(dst.ptr_eq(&dst));
+    ///assert!(!dst.ptr_eq(&Dst::new(0u8, &[1, 2])));
+    ///```
+    pub fn ptr_eq<B: Allocator>(&self, other: &Dst<H, F, B>) -> bool {
+        ptr::eq(self.as_ptr(), other.as_ptr())
+    }
+
+    ///Returns `true` if `data` refers to this Dst's allocation
+    pub fn ptr_eq_data(&self, data: &DstData<H, F>) -> bool {
+        ptr::eq(self.as_ptr(), data)
+    }
+
+    ///Consumes the Dst, returning its header and a Vec holding its footer elements
+    ///
+    ///```
+    ///use custom_dst::Dst;
+    ///
+    ///let dst = Dst::new(String::from("header"), &[1, 2, 3]);
+    ///let (header, footer) = dst.into_parts();
+    ///
+    ///assert_eq!(header, "header");
+    ///assert_eq!(footer, [1, 2, 3]);
+    ///```
+    pub fn into_parts(self) -> (H, Vec<F>) {
+        let this = ManuallyDrop::new(self);
+        let ptr = this.ptr.as_ptr();
+
+        unsafe {
+            let parts = DstData::read_parts(ptr);
+
+            let alloc = ptr::read(&this.alloc);
+            dealloc_layout(&alloc, ptr.cast::<u8>(), DstData::layout_of_raw(ptr));
+
+            parts
+        }
+    }
+
+    ///Consumes the Dst, returning its header and dropping its footer
+    pub fn into_header(self) -> H {
+        let this = ManuallyDrop::new(self);
+        let ptr = this.ptr.as_ptr();
+
+        unsafe {
+            let layout = DstData::layout_of_raw(ptr);
+            let header = DstData::get_header_ptr(ptr).read();
+
+            let alloc = ptr::read(&this.alloc);
+            drop_in_place(DstData::get_footer_slice(ptr));
+
+            dealloc_layout(&alloc, ptr.cast::<u8>(), layout);
+
+            header
+        }
+    }
+
+    ///Consumes the Dst, returning a Vec holding its footer elements and dropping its header
+    pub fn into_footer_vec(self) -> Vec<F> {
+        self.into_parts().1
+    }
+
+    ///Like [`Clone::clone`], but returns an error instead of panicking or aborting if the
+    ///allocation cannot be made
+    pub fn try_clone(&self) -> Result<Dst<H, F, A>, DstError>
+    where
+        H: Clone,
+        F: Clone,
+        A: Clone,
+    {
+        Self::try_clone_data_in(self.get_data(), self.alloc.clone())
+    }
+
+    ///Clones `data` into a new allocation from `alloc`, dropping the parts cloned so far and
+    ///freeing the allocation if a clone panics
+    fn try_clone_data_in(data: &DstData<H, F>, alloc: A) -> Result<Dst<H, F, A>, DstError>
+    where
+        H: Clone,
+        F: Clone,
+    {
+        let mut dst = MaybeUninitDst::try_new_in(data.footer.len(), alloc)?;
+
+        unsafe {
+            data.clone_to_uninit(dst.ptr.as_ptr().cast::<u8>());
+
+            dst.init = InitTracker::untracked();
+            Ok(dst.assume_init())
+        }
+    }
+
+    ///Converts the Dst into a `Box` without copying, as both take their memory from the same
+    ///allocator with the same layout
+    pub fn into_box(self) -> Box<DstData<H, F>, A> {
+        let this = ManuallyDrop::new(self);
+
+        debug_assert!(
+            DstData::<H, F>::layout_of(this.get_footer_len()).ok()
+                == Some(unsafe { DstData::layout_of_raw(this.ptr.as_ptr()) })
+        );
+
+        unsafe { Box::from_raw_in(this.ptr.as_ptr(), ptr::read(&this.alloc)) }
+    }
+
+    ///Consumes and leaks the Dst, returning a mutable reference to it that lives as long as
+    ///`H` and `F` do, like [`Box::leak`]
+    ///
+    ///```
+    ///use custom_dst::{Dst, DstData};
+    ///
+    ///let table: &'static DstData<&str, u32> = Dst::new_with("squares", 16, |i| (i * i) as u32).leak();
+    ///
+    ///assert_eq!(table.get_footer()[15], 225);
+    ///```
+    pub fn leak<'a>(self) -> &'a mut DstData<H, F>
+    where
+        H: 'a,
+        F: 'a,
+        A: 'a,
+    {
+        Box::leak(self.into_box())
+    }
+}
+
+impl<H, F, A: Allocator> From<Box<DstData<H, F>, A>> for Dst<H, F, A> {
+    ///Converts the `Box` into a Dst without copying
+    fn from(value: Box<DstData<H, F>, A>) -> Self {
+        debug_assert!(
+            DstData::<H, F>::layout_of(value.get_footer().len()).ok()
+                == Some(Layout::for_value(&*value))
+        );
+
+        let (ptr, alloc) = Box::into_raw_with_allocator(value);
+
+        Dst {
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            phantom: PhantomData,
+            alloc,
+        }
+    }
+}
+
+impl<H: Clone, F: Clone, A: Allocator + Clone> Clone for Dst<H, F, A> {
+    ///Clones the header and every footer element into a new allocation from a clone of the
+    ///allocator
+    ///
+    ///`Copy` footers are copied in one go. If a clone panics, the parts cloned so far are
+    ///dropped and the new allocation is freed
+    fn clone(&self) -> Self {
+        self.try_clone().unwrap_or_else(|err| err.handle())
+    }
+}
+
+impl<H: PartialEq, F: PartialEq, A: Allocator, B: Allocator> PartialEq<Dst<H, F, B>>
+    for Dst<H, F, A>
+{
+    fn eq(&self, other: &Dst<H, F, B>) -> bool {
+        self.get_data() == other.get_data()
+    }
+}
+
+impl<H: Eq, F: Eq, A: Allocator> Eq for Dst<H, F, A> {}
+
+impl<H: PartialEq, F: PartialEq, A: Allocator> PartialEq<DstData<H, F>> for Dst<H, F, A> {
+    fn eq(&self, other: &DstData<H, F>) -> bool {
+        self.get_data() == other
+    }
+}
+
+impl<H: PartialEq, F: PartialEq, A: Allocator> PartialEq<&DstData<H, F>> for Dst<H, F, A> {
+    fn eq(&self, other: &&DstData<H, F>) -> bool {
+        self.get_data() == *other
+    }
+}
+
+impl<H: PartialEq, F: PartialEq, A: Allocator> PartialEq<Dst<H, F, A>> for DstData<H, F> {
+    fn eq(&self, other: &Dst<H, F, A>) -> bool {
+        self == other.get_data()
+    }
+}
+
+impl<H: PartialOrd, F: PartialOrd, A: Allocator, B: Allocator> PartialOrd<Dst<H, F, B>>
+    for Dst<H, F, A>
+{
+    fn partial_cmp(&self, other: &Dst<H, F, B>) -> Option<cmp::Ordering> {
+        self.get_data().partial_cmp(other.get_data())
+    }
+}
+
+impl<H: Ord, F: Ord, A: Allocator> Ord for Dst<H, F, A> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.get_data().cmp(other.get_data())
+    }
+}
+
+impl<H: Hash, F: Hash, A: Allocator> Hash for Dst<H, F, A> {
+    fn hash<S: Hasher>(&self, state: &mut S) {
+        self.get_data().hash(state);
+    }
+}
+
+impl<H, F, A: Allocator, I: SliceIndex<[F]>> Index<I> for Dst<H, F, A> {
+    type Output = I::Output;
+
+    #[track_caller]
+    fn index(&self, index: I) -> &I::Output {
+        &self.get_data()[index]
+    }
+}
+
+impl<H, F, A: Allocator, I: SliceIndex<[F]>> IndexMut<I> for Dst<H, F, A> {
+    #[track_caller]
+    fn index_mut(&mut self, index: I) -> &mut I::Output {
+        &mut self.get_data_mut()[index]
+    }
+}
+
+impl<H, F, A: Allocator> fmt::Pointer for Dst<H, F, A> {
+    ///Formats the address of the allocation
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&self.as_ptr(), f)
+    }
+}
+
+impl<H, F, A: Allocator> AsRef<DstData<H, F>> for Dst<H, F, A> {
+    fn as_ref(&self) -> &DstData<H, F> {
+        self.get_data()
+    }
+}
+
+impl<H, F, A: Allocator> AsMut<DstData<H, F>> for Dst<H, F, A> {
+    fn as_mut(&mut self) -> &mut DstData<H, F> {
+        self.get_data_mut()
+    }
+}
+
+impl<H, F, A: Allocator> AsRef<[F]> for Dst<H, F, A> {
+    fn as_ref(&self) -> &[F] {
+        self.get_footer_ref()
+    }
+}
+
+impl<H, F, A: Allocator> AsMut<[F]> for Dst<H, F, A> {
+    fn as_mut(&mut self) -> &mut [F] {
+        self.get_footer_ref_mut()
+    }
+}
+
+//`Eq`, `Ord` and `Hash` for a Dst all forward to its DstData, as `Borrow` requires
+impl<H, F, A: Allocator> Borrow<DstData<H, F>> for Dst<H, F, A> {
+    fn borrow(&self) -> &DstData<H, F> {
+        self.get_data()
+    }
+}
+
+impl<H, F, A: Allocator> BorrowMut<DstData<H, F>> for Dst<H, F, A> {
+    fn borrow_mut(&mut self) -> &mut DstData<H, F> {
+        self.get_data_mut()
+    }
+}
+
+impl<H: fmt::Debug, F: fmt::Debug, A: Allocator> fmt::Debug for Dst<H, F, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.get_data(), f)
+    }
+}
+
+///A Dst owns its header and footer like a `Box` does, so it can be sent to or shared with
+///another thread whenever they can
+///
+///```compile_fail,E0277
+///use custom_dst::Dst;
+///use std::rc::Rc;
+///
+///let dst = Dst::new(Rc::new(0), &[0u8]);
+///std::thread::spawn(move || drop(dst));
+///```
+unsafe impl<H: Send, F: Send, A: Allocator + Send> Send for Dst<H, F, A> {}
+
+///```compile_fail,E0277
+///use custom_dst::Dst;
+///use std::{cell::Cell, sync::Arc};
+///
+///let dst = Arc::new(Dst::new(Cell::new(0), &[0u8]));
+///std::thread::spawn(move || dst.get_header_ref().get());
+///```
+unsafe impl<H: Sync, F: Sync, A: Allocator + Sync> Sync for Dst<H, F, A> {}
+
+impl<H, F, A: Allocator> Drop for Dst<H, F, A> {
+    fn drop(&mut self) {
+        unsafe {
+            let layout = DstData::layout_of_raw(self.ptr.as_ptr());
+
+            drop_in_place(self.ptr.as_ptr());
+
+            dealloc_layout(&self.alloc, self.ptr.cast::<u8>().as_ptr(), layout);
+        };
+    }
+}
+
+pub struct MaybeUninitDstArray<H: Sized, F: Sized, A: Allocator = Global> {
+    len: usize,
+    stride: usize,
+    ptr: NonNull<DstData<H, F>>,
+    init: InitTracker,
+    alloc: A,
+}
+
+impl<H, F> MaybeUninitDstArray<H, F> {
+    pub fn new(count: usize, array_size: usize) -> Self {
+        Self::try_new(count, array_size).unwrap_or_else(|err| err.handle())
+    }
+
+    ///Allocates an uninitialized array, returning an error instead of panicking or aborting if
+    ///the allocation cannot be made
+    pub fn try_new(count: usize, array_size: usize) -> Result<Self, DstError> {
+        Self::try_new_in(count, array_size, Global)
+    }
+
+    ///Returns how many whole members whose footers have `count` elements fit in `total_bytes`
+    ///
+    ///Returns `usize::MAX` if the members are zero-sized
+    pub fn capacity_for(count: usize, total_bytes: usize) -> usize {
+        match DstData::<H, F>::array_stride_of(count) {
+            Ok(0) => usize::MAX,
+            Ok(stride) => total_bytes / stride,
+            Err(_) => 0,
+        }
+    }
+
+    ///Consumes the `MaybeUninitDstArray`, returning a pointer to the first element and the
+    ///number of elements without freeing the allocation
+    ///
+    ///The caller becomes responsible for the memory, which can be handed back with
+    ///[`MaybeUninitDstArray::from_raw`]
+    pub fn into_raw(self) -> (*mut DstData<H, F>, usize) {
+        let mut this = ManuallyDrop::new(self);
+        unsafe { drop_in_place(&mut this.init) };
+
+        (this.ptr.as_ptr(), this.len)
+    }
+
+    ///# Safety
+    ///
+    /// `ptr` and `array_size` must have been returned by [`MaybeUninitDstArray::into_raw`]
+    /// and must not be used again afterwards
+    pub unsafe fn from_raw(ptr: *mut DstData<H, F>, array_size: usize) -> Self {
+        MaybeUninitDstArray {
+            len: array_size,
+            stride: unsafe {
+                DstData::<H, F>::array_stride_of(ptr::metadata(ptr)).unwrap_unchecked()
+            },
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            init: InitTracker::untracked(),
+            alloc: Global,
+        }
+    }
+}
+
+impl<H, F, A: Allocator> MaybeUninitDstArray<H, F, A> {
+    ///Like [`MaybeUninitDstArray::new`], but allocates from `alloc`
+    pub fn new_in(count: usize, array_size: usize, alloc: A) -> Self {
+        Self::try_new_in(count, array_size, alloc).unwrap_or_else(|err| err.handle())
+    }
+
+    ///Like [`MaybeUninitDstArray::try_new`], but allocates from `alloc`
+    pub fn try_new_in(count: usize, array_size: usize, alloc: A) -> Result<Self, DstError> {
+        Self::try_alloc_in(count, array_size, alloc, InitTracker::new::<H, F>)
+    }
+
+    ///Like [`MaybeUninitDstArray::new_in`], but doesn't track writes, for members that are
+    ///written through pointers the tracker can't follow
+    pub(crate) fn new_untracked_in(count: usize, array_size: usize, alloc: A) -> Self {
+        Self::try_alloc_in(count, array_size, alloc, |_, _| InitTracker::untracked())
+            .unwrap_or_else(|err| err.handle())
+    }
+
+    fn try_alloc_in(
+        count: usize,
+        array_size: usize,
+        alloc: A,
+        tracker: impl FnOnce(usize, usize) -> InitTracker,
+    ) -> Result<Self, DstError> {
+        let (ptr, stride) = unsafe { DstData::alloc_self_array(count, array_size, &alloc)? };
+
+        Ok(MaybeUninitDstArray {
+            len: array_size,
+            stride,
+            ptr,
+            init: tracker(array_size, count),
+            alloc,
+        })
+    }
+
+    fn get_stride(&self) -> usize {
+        self.stride
+    }
+
+    fn get_element(&self, arr_index: usize) -> NonNull<DstData<H, F>> {
+        assert!(arr_index < self.len);
+
+        unsafe { self.ptr.byte_add(self.get_stride() * arr_index) }
+    }
+
+    ///Returns a writer for the member at `arr_index`, borrowing the array
+    ///
+    ///```
+    ///use custom_dst::MaybeUninitDstArray;
+    ///
+    ///let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(2, 2);
+    ///
+    ///for arr_index in 0..2 {
+    ///    let mut element = dst_arr.element_mut(arr_index);
+    ///
+    ///    element.write_header(arr_index as u32);
+    ///    element.write_footer(&[1, 2]);
+    ///}
+    ///
+    ///let dst_arr = unsafe { dst_arr.assume_init() };
+    ///
+    ///assert_eq!(*dst_arr.get_header_ref(1), 1);
+    ///```
+    #[track_caller]
+    pub fn element_mut(&mut self, arr_index: usize) -> UninitElementMut<'_, H, F> {
+        UninitElementMut {
+            ptr: self.get_element(arr_index),
+            init: &mut self.init,
+            arr_index,
+        }
+    }
+
+    ///# Safety
+    ///
+    /// Declares that the Dst array is fully initialized, and is unsafe it has not been
+    ///
+    /// In debug builds, this panics if a header or footer element was never written
+    #[track_caller]
+    pub unsafe fn assume_init(self) -> DstArray<H, F, A> {
+        self.init.debug_assert_init();
+
+        let mut this = ManuallyDrop::new(self);
+        unsafe { drop_in_place(&mut this.init) };
+
+        DstArray {
+            len: this.len,
+            stride: this.stride,
+            ptr: this.ptr,
+            phantom: PhantomData,
+            alloc: unsafe { ptr::read(&this.alloc) },
+        }
+    }
+
+    ///Builds every member in order from the header and footer `f` returns for its index,
+    ///then assumes the array is initialized
+    ///
+    ///If `f` panics on member `k`, members `0..k` are dropped and the allocation is freed
+    ///
+    ///```
+    ///use custom_dst::MaybeUninitDstArray;
+    ///
+    ///let arr = MaybeUninitDstArray::<usize, u8>::new(2, 3)
+    ///    .init_each(|arr_index| (arr_index, [arr_index as u8; 2]));
+    ///
+    ///assert_eq!(arr.get_footer_ref(2), [2, 2]);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if a footer yields fewer or more elements than the footers have
+    #[track_caller]
+    pub fn init_each<G>(self, mut f: impl FnMut(usize) -> (H, G)) -> DstArray<H, F, A>
+    where
+        G: IntoIterator<Item = F>,
+    {
+        match self.try_init_each(|arr_index| Ok::<_, Infallible>(f(arr_index))) {
+            Ok(arr) => arr,
+            Err(never) => match never {},
+        }
+    }
+
+    ///Like [`MaybeUninitDstArray::init_each`], but stops at the first error `f` returns
+    ///
+    ///Members before the one `f` failed on are dropped and the allocation is freed before the
+    ///error is returned
+    ///
+    ///# Panics
+    ///
+    ///Panics if a footer yields fewer or more elements than the footers have
+    #[track_caller]
+    pub fn try_init_each<G, E>(
+        mut self,
+        mut f: impl FnMut(usize) -> Result<(H, G), E>,
+    ) -> Result<DstArray<H, F, A>, E>
+    where
+        G: IntoIterator<Item = F>,
+    {
+        let mut guard = ArrayPrefixGuard::new(self.ptr, self.stride);
+
+        for arr_index in 0..self.len {
+            let (header, footer) = f(arr_index)?;
+            self.element_mut(arr_index).write_parts(header, footer);
+            unsafe { guard.push_initialized() };
+        }
+
+        guard.finish();
+
+        Ok(unsafe { self.assume_init() })
+    }
+
+    ///Like [`MaybeUninitDstArray::init_each`], but splits the members into at most `threads`
+    ///contiguous ranges and builds each range on its own scoped thread
+    ///
+    ///If `f` panics on any thread, every member that was fully initialized is dropped, the
+    ///allocation is freed and the first panic is resumed on the calling thread
+    ///
+    ///```
+    ///use custom_dst::MaybeUninitDstArray;
+    ///
+    ///let arr = MaybeUninitDstArray::<usize, u8>::new(2, 100)
+    ///    .par_init(4, |arr_index| (arr_index, [arr_index as u8; 2]));
+    ///
+    ///assert_eq!(*arr.get_header_ref(99), 99);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `threads` is zero, or if a footer yields fewer or more elements than the
+    ///footers have
+    #[track_caller]
+    pub fn par_init<G>(
+        mut self,
+        threads: usize,
+        f: impl Fn(usize) -> (H, G) + Sync,
+    ) -> DstArray<H, F, A>
+    where
+        H: Send,
+        F: Send,
+        G: IntoIterator<Item = F>,
+    {
+        assert!(threads != 0, "thread count must be non-zero");
+
+        let len = self.len;
+        let stride = self.stride;
+        let footer_len = unsafe { DstData::get_len(self.ptr.as_ptr()) };
+        let per_thread = len.div_ceil(threads).max(1);
+
+        let ranges = (0..len)
+            .step_by(per_thread)
+            .map(|start| ParInitRange {
+                start: unsafe { self.ptr.byte_add(stride * start) },
+                range: start..cmp::min(start + per_thread, len),
+            })
+            .collect::<Vec<_>>();
+
+        let f = &f;
+        let init = &self.init;
+        let results = thread::scope(|scope| {
+            let handles = ranges
+                .iter()
+                .map(|part| {
+                    let part = ParInitRange {
+                        start: part.start,
+                        range: part.range.clone(),
+                    };
+
+                    scope.spawn(move || unsafe { part.init(stride, footer_len, init, f) })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .map(|handle| handle.join())
+                .collect::<Vec<_>>()
+        });
+
+        //Takes ownership of every range that finished, so that a panic elsewhere drops them
+        let mut finished = Vec::with_capacity(ranges.len());
+        let mut payload = None;
+
+        for (part, result) in ranges.iter().zip(results) {
+            match result {
+                Ok(()) => finished.push(ArrayPrefixGuard {
+                    start: part.start,
+                    stride,
+                    initialized: part.range.len(),
+                }),
+                Err(err) => {
+                    payload.get_or_insert(err);
+                }
+            }
+        }
+
+        if let Some(payload) = payload {
+            drop(finished);
+            drop(self);
+
+            panic::resume_unwind(payload);
+        }
+
+        for guard in finished {
+            guard.finish();
+        }
+
+        for arr_index in 0..len {
+            self.init.mark_header(arr_index);
+            self.init.mark_footer(arr_index, 0..footer_len);
+        }
+
+        unsafe { self.assume_init() }
+    }
+
+    ///Returns the initialized array if every part of every member was written, or a report
+    ///of the parts that were not
+    ///
+    ///See [`MaybeUninitDst::try_assume_init`] for what counts as written
+    #[cfg(feature = "init-tracking")]
+    pub fn try_assume_init(self) -> Result<DstArray<H, F, A>, UninitializedReport> {
+        match self.init.report() {
+            Some(report) => Err(report),
+            None => Ok(unsafe { self.assume_init() }),
+        }
+    }
+
+    pub fn write_header(&mut self, arr_index: usize, header: H) {
+        self.element_mut(arr_index).write_header(header);
+    }
+
+    pub fn write_footer(&mut self, arr_index: usize, footer: &[F])
+    where
+        F: Copy,
+    {
+        self.element_mut(arr_index).write_footer(footer);
+    }
+
+    pub fn write_footer_cloned(&mut self, arr_index: usize, footer: &[F])
+    where
+        F: Clone,
+    {
+        self.element_mut(arr_index).write_footer_cloned(footer);
+    }
+
+    pub fn write_footer_from_vec(&mut self, arr_index: usize, footer: Vec<F>) {
+        self.element_mut(arr_index).write_footer_from_vec(footer);
+    }
+
+    pub fn write_footer_element(&mut self, arr_index: usize, footer_index: usize, element: F) {
+        self.element_mut(arr_index)
+            .write_footer_element(footer_index, element);
+    }
+
+    #[track_caller]
+    pub fn write_footer_from_iter(
+        &mut self,
+        arr_index: usize,
+        iter: impl IntoIterator<Item = F>,
+    ) -> Result<(), FillError> {
+        self.element_mut(arr_index).write_footer_from_iter(iter)
+    }
+
+    ///Writes a clone of `headers[arr_index]` into the header of every member `arr_index`
+    ///
+    ///If a clone panics, the headers cloned so far are dropped again
+    #[track_caller]
+    pub fn write_headers(&mut self, headers: &[H])
+    where
+        H: Clone,
+    {
+        assert!(headers.len() == self.len);
+
+        self.write_headers_with(|arr_index| headers[arr_index].clone());
+    }
+
+    ///Writes `header` into the header of every member, cloning it for all but the last
+    ///
+    ///If a clone panics, the headers cloned so far are dropped again
+    #[track_caller]
+    pub fn write_header_all(&mut self, header: H)
+    where
+        H: Clone,
+    {
+        let len = self.len;
+        let mut header = Some(header);
+
+        self.write_headers_with(|arr_index| {
+            if arr_index + 1 == len {
+                header.take()
+            } else {
+                header.clone()
+            }
+            .unwrap()
+        });
+    }
+
+    #[track_caller]
+    fn write_headers_with(&mut self, mut f: impl FnMut(usize) -> H) {
+        for arr_index in 0..self.len {
+            self.init.check_header(arr_index, mem::needs_drop::<H>());
+        }
+
+        if self.len == 0 {
+            return;
+        }
+
+        let mut guard = ArrayPrefixGuard::new(
+            unsafe { NonNull::new_unchecked(DstData::get_header_ptr(self.ptr.as_ptr())) },
+            self.stride,
+        );
+
+        for arr_index in 0..self.len {
+            unsafe {
+                DstData::get_header_ptr(self.get_element(arr_index).as_ptr()).write(f(arr_index));
+                guard.push_initialized();
+            }
+        }
+
+        guard.finish();
+
+        for arr_index in 0..self.len {
+            self.init.mark_header(arr_index);
+        }
+    }
+
+    ///Writes a clone of every element of `footer` into the footer of every member
+    ///
+    ///`Copy` footers are copied in one go per member. If a clone panics, the footers cloned so
+    ///far are dropped again
+    #[track_caller]
+    pub fn write_footer_all(&mut self, footer: &[F])
+    where
+        F: Clone,
+    {
+        let footer_len = unsafe { DstData::get_len(self.ptr.as_ptr()) };
+        assert!(footer.len() == footer_len);
+
+        for arr_index in 0..self.len {
+            self.init
+                .check_footer(arr_index, 0..footer_len, mem::needs_drop::<F>());
+        }
+
+        if self.len == 0 {
+            return;
+        }
+
+        let mut guard = ArrayPrefixGuard::new(
+            unsafe { NonNull::new_unchecked(DstData::get_footer_slice(self.ptr.as_ptr())) },
+            self.stride,
+        );
+
+        for arr_index in 0..self.len {
+            unsafe {
+                let dest = DstData::get_footer_slice(self.get_element(arr_index).as_ptr());
+
+                footer.clone_to_uninit(dest.cast::<u8>());
+                guard.push_initialized();
+            }
+        }
+
+        guard.finish();
+
+        for arr_index in 0..self.len {
+            self.init.mark_footer(arr_index, 0..footer_len);
+        }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the header of the element has been initialized
+    pub fn get_header_ptr(&self, arr_index: usize) -> *const H {
+        unsafe { DstData::get_header_ptr(self.get_element(arr_index).as_ptr()) as *const H }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the header of the element has been initialized
+    pub fn get_header_ptr_mut(&mut self, arr_index: usize) -> *mut H {
+        self.element_mut(arr_index).get_header_ptr_mut()
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the footer of the element has been initialized
+    pub fn get_footer_ptr(&self, arr_index: usize) -> *const [F] {
+        unsafe { DstData::get_footer_slice(self.get_element(arr_index).as_ptr()) as *const [F] }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the footer of the element has been initialized
+    pub fn get_footer_ptr_mut(&mut self, arr_index: usize) -> *mut [F] {
+        self.element_mut(arr_index).get_footer_ptr_mut()
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the element has been initialized
+    #[track_caller]
+    pub fn get_footer_element_ptr(&self, arr_index: usize, footer_index: usize) -> *const F {
+        let element = self.get_element(arr_index);
+        check_index(footer_index, unsafe { DstData::get_len(element.as_ptr()) });
+
+        unsafe {
+            (DstData::get_footer_slice(element.as_ptr()) as *const [F])
+                .as_ptr()
+                .add(footer_index)
+        }
+    }
+
+    ///Reading from this pointer or turning it into a reference is undefined behavior
+    ///unless the element has been initialized
+    pub fn get_footer_element_ptr_mut(&mut self, arr_index: usize, footer_index: usize) -> *mut F {
+        self.element_mut(arr_index)
+            .get_footer_element_ptr_mut(footer_index)
+    }
+}
+
+impl<H, F, A: Allocator> Drop for MaybeUninitDstArray<H, F, A> {
+    ///Frees the allocation without dropping any element, as they may be uninitialized
+    fn drop(&mut self) {
+        unsafe {
+            let layout = DstData::array_layout_of_raw(self.ptr.as_ptr(), self.len, self.stride);
+
+            dealloc_layout(&self.alloc, self.ptr.cast::<u8>().as_ptr(), layout);
+        }
+    }
+}
+
+pub struct DstArray<H, F, A: Allocator = Global> {
+    len: usize,
+    stride: usize,
+    ptr: NonNull<DstData<H, F>>,
+    phantom: PhantomData<DstData<H, F>>,
+    alloc: A,
+}
+
+impl<H, F> DstArray<H, F> {
+    ///Builds an array of `array_size` members whose footers have `footer_len` elements,
+    ///where member `arr_index` takes its header and footer from `f(arr_index)`
+    ///
+    ///If `f` panics, the members built so far are dropped and the allocation is freed
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let table = DstArray::from_fn(3, 2, |row| (row, (0..3).map(move |column| row * 3 + column)));
+    ///
+    ///assert_eq!(*table.get_header_ref(1), 1);
+    ///assert_eq!(table.get_footer_ref(1), [3, 4, 5]);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if a footer yields fewer or more than `footer_len` elements
+    #[track_caller]
+    pub fn from_fn<G>(
+        footer_len: usize,
+        array_size: usize,
+        f: impl FnMut(usize) -> (H, G),
+    ) -> DstArray<H, F>
+    where
+        G: IntoIterator<Item = F>,
+    {
+        MaybeUninitDstArray::new(footer_len, array_size).init_each(f)
+    }
+
+    ///Builds an array of `array_size` members that are each a clone of `template`
+    ///
+    ///Members of `Copy` types are copied in one go per member. If a clone panics, the members
+    ///cloned so far are dropped and the allocation is freed
+    ///
+    ///```
+    ///use custom_dst::{Dst, DstArray};
+    ///
+    ///let template = Dst::new(0u32, &[0u8; 16]);
+    ///let mut slots = DstArray::new_filled(template.get_data(), 4);
+    ///
+    ///slots.get_footer_ref_mut(2)[0] = 1;
+    ///
+    ///assert_eq!(slots.get_footer_ref(1), [0; 16]);
+    ///assert_eq!(slots.get_footer_ref(2)[0], 1);
+    ///```
+    pub fn new_filled(template: &DstData<H, F>, array_size: usize) -> DstArray<H, F>
+    where
+        H: Clone,
+        F: Clone,
+    {
+        let arr =
+            MaybeUninitDstArray::new_untracked_in(template.get_footer().len(), array_size, Global);
+        let mut guard = ArrayPrefixGuard::new(arr.ptr, arr.stride);
+
+        for arr_index in 0..array_size {
+            unsafe {
+                template.clone_to_uninit(arr.get_element(arr_index).as_ptr().cast::<u8>());
+                guard.push_initialized();
+            }
+        }
+
+        guard.finish();
+
+        unsafe { arr.assume_init() }
+    }
+
+    ///Moves the headers and footers of `dsts` into a single array without cloning them,
+    ///freeing their allocations
+    ///
+    ///If the footers don't all have the same length, `dsts` is returned in the error
+    ///
+    ///```
+    ///use custom_dst::{Dst, DstArray};
+    ///
+    ///let dsts = vec![Dst::new("a", &[1, 2]), Dst::new("b", &[3, 4])];
+    ///let Ok(arr) = DstArray::from_dsts(dsts) else {
+    ///    panic!("the footers have the same length");
+    ///};
+    ///
+    ///assert_eq!(arr.get_footer_ref(1), [3, 4]);
+    ///
+    ///let dsts = vec![Dst::new("a", &[1, 2]), Dst::new("b", &[3])];
+    ///let Err(err) = DstArray::from_dsts(dsts) else {
+    ///    panic!("the second footer is shorter");
+    ///};
+    ///
+    ///assert_eq!(err.index, 1);
+    ///assert_eq!(err.dsts.len(), 2);
+    ///```
+    pub fn from_dsts(dsts: Vec<Dst<H, F>>) -> Result<DstArray<H, F>, MismatchedLenError<H, F>> {
+        let footer_len = dsts.first().map_or(0, Dst::get_footer_len);
+
+        if let Some(index) = dsts
+            .iter()
+            .position(|dst| dst.get_footer_len() != footer_len)
+        {
+            return Err(MismatchedLenError { index, dsts });
+        }
+
+        let arr = MaybeUninitDstArray::new_untracked_in(footer_len, dsts.len(), Global);
+
+        for (arr_index, dst) in dsts.into_iter().enumerate() {
+            let src = dst.into_raw();
+
+            unsafe {
+                let layout = DstData::layout_of_raw(src);
+
+                ptr::copy_nonoverlapping(
+                    src.cast::<u8>(),
+                    arr.get_element(arr_index).as_ptr().cast::<u8>(),
+                    layout.size(),
+                );
+
+                dealloc_layout(&Global, src.cast::<u8>(), layout);
+            }
+        }
+
+        Ok(unsafe { arr.assume_init() })
+    }
+}
+
+impl<H, F> DstArray<H, F> {
+    ///Builds an array from headers and owned footers, moving the footer elements in
+    ///
+    ///The length of the iterator isn't known up front, so the parts are first collected into
+    ///a temporary Vec, and the array is allocated once they have all been checked. If a
+    ///footer's length differs from the first one's, the parts collected so far are returned in
+    ///the error and the rest of the iterator is left unconsumed
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let Ok(arr) = DstArray::try_from_parts([("a", vec![1, 2]), ("b", vec![3, 4])]) else {
+    ///    panic!("the footers have the same length");
+    ///};
+    ///
+    ///assert_eq!(arr.get_footer_ref(1), [3, 4]);
+    ///
+    ///let Err(err) = DstArray::try_from_parts([("a", vec![1, 2]), ("b", vec![3])]) else {
+    ///    panic!("the second footer is shorter");
+    ///};
+    ///
+    ///assert_eq!((err.index, err.expected, err.found), (1, 2, 1));
+    ///```
+    pub fn try_from_parts(
+        iter: impl IntoIterator<Item = (H, Vec<F>)>,
+    ) -> Result<DstArray<H, F>, MismatchedPartsError<H, Vec<F>>> {
+        let (parts, footer_len) = stage_parts(iter, |footer| footer.len())?;
+
+        let mut builder = DstArrayBuilder::new(footer_len, parts.len());
+        for (header, footer) in parts {
+            builder.push_from_iter(header, footer);
+        }
+
+        let Ok(arr) = builder.build() else {
+            unreachable!("every part was pushed")
+        };
+
+        Ok(arr)
+    }
+
+    ///Builds an array from headers and borrowed footers, cloning the footer elements
+    ///
+    ///Staged in the same way as [`DstArray::try_from_parts`]
+    pub fn try_from_borrowed_parts<'a>(
+        iter: impl IntoIterator<Item = (H, &'a [F])>,
+    ) -> Result<DstArray<H, F>, MismatchedPartsError<H, &'a [F]>>
+    where
+        F: Clone + 'a,
+    {
+        let (parts, footer_len) = stage_parts(iter, |footer| footer.len())?;
+
+        let mut builder = DstArrayBuilder::new(footer_len, parts.len());
+        for (header, footer) in parts {
+            builder.push_cloned(header, footer);
+        }
+
+        let Ok(arr) = builder.build() else {
+            unreachable!("every part was pushed")
+        };
+
+        Ok(arr)
+    }
+}
+
+impl<H, F> FromIterator<(H, Vec<F>)> for DstArray<H, F> {
+    ///Collects with [`DstArray::try_from_parts`]
+    ///
+    ///# Panics
+    ///
+    ///Panics if the footers don't all have the same length
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr: DstArray<usize, usize> = (0..3).map(|index| (index, vec![index; 2])).collect();
+    ///
+    ///assert_eq!(arr.len(), 3);
+    ///assert_eq!(arr.get_footer_ref(2), [2, 2]);
+    ///```
+    #[track_caller]
+    fn from_iter<I: IntoIterator<Item = (H, Vec<F>)>>(iter: I) -> Self {
+        DstArray::try_from_parts(iter).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<'a, H, F: Clone + 'a> FromIterator<(H, &'a [F])> for DstArray<H, F> {
+    ///Collects with [`DstArray::try_from_borrowed_parts`]
+    ///
+    ///# Panics
+    ///
+    ///Panics if the footers don't all have the same length
+    #[track_caller]
+    fn from_iter<I: IntoIterator<Item = (H, &'a [F])>>(iter: I) -> Self {
+        DstArray::try_from_borrowed_parts(iter).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+impl<H, F, A: Allocator> DstArray<H, F, A> {
+    fn get_stride(&self) -> usize {
+        self.stride
+    }
+
+    ///Returns a pointer to member `index`, which is one past the end if `index` is the length
+    ///
+    ///# Safety
+    ///
+    /// `index` must be at most the length of the array
+    unsafe fn member_ptr(&self, index: usize) -> NonNull<DstData<H, F>> {
+        unsafe { self.ptr.byte_add(self.get_stride() * index) }
+    }
+
+    ///Returns member `index`, or `None` if it is out of bounds
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 2, |index| (index, [0u8]));
+    ///
+    ///assert_eq!(arr.get(1).map(|member| *member.get_header()), Some(1));
+    ///assert!(arr.get(2).is_none());
+    ///```
+    pub fn get(&self, index: usize) -> Option<&DstData<H, F>> {
+        if index < self.len {
+            Some(unsafe { self.member_ptr(index).as_ref() })
+        } else {
+            None
+        }
+    }
+
+    ///Returns member `index` mutably, or `None` if it is out of bounds
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut DstData<H, F>> {
+        if index < self.len {
+            Some(unsafe { self.member_ptr(index).as_mut() })
+        } else {
+            None
+        }
+    }
+
+    pub fn first(&self) -> Option<&DstData<H, F>> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&DstData<H, F>> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    pub fn first_mut(&mut self) -> Option<&mut DstData<H, F>> {
+        self.get_mut(0)
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut DstData<H, F>> {
+        self.get_mut(self.len.checked_sub(1)?)
+    }
+
+    ///Returns member `index` without checking that it is in bounds
+    ///
+    ///# Safety
+    ///
+    /// `index` must be less than the length of the array. This is only checked in debug builds
+    #[track_caller]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &DstData<H, F> {
+        debug_assert!(
+            index < self.len,
+            "get_unchecked index {index} out of bounds"
+        );
+
+        unsafe { self.member_ptr(index).as_ref() }
+    }
+
+    ///Returns member `index` without checking that it is in bounds
+    ///
+    ///# Safety
+    ///
+    /// `index` must be less than the length of the array. This is only checked in debug builds
+    #[track_caller]
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut DstData<H, F> {
+        debug_assert!(
+            index < self.len,
+            "get_unchecked_mut index {index} out of bounds"
+        );
+
+        unsafe { self.member_ptr(index).as_mut() }
+    }
+
+    pub fn get_header_ref(&self, arr_index: usize) -> &H {
+        &self[arr_index].header
+    }
+
+    pub fn get_header_ref_mut(&mut self, arr_index: usize) -> &mut H {
+        &mut self[arr_index].header
+    }
+
+    pub fn get_footer_ref(&self, arr_index: usize) -> &[F] {
+        &self[arr_index].footer
+    }
+
+    pub fn get_footer_ref_mut(&mut self, arr_index: usize) -> &mut [F] {
+        &mut self[arr_index].footer
+    }
+
+    ///Returns the number of footer elements of each member, which is known even when the
+    ///array is empty
+    pub fn get_footer_len(&self) -> usize {
+        unsafe { DstData::get_len(self.ptr.as_ptr()) }
+    }
+
+    ///Returns the number of members of the array
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///Returns a shared view of the members in `range`
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 4, |index| (index, [0u8]));
+    ///
+    ///assert_eq!(*arr.slice(1..=2)[1].get_header(), 2);
+    ///assert_eq!(*arr.slice(..)[3].get_header(), 3);
+    ///assert_eq!(*arr.slice(3..)[0].get_header(), 3);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if either bound is past the end of the array, or if the start is after the end
+    #[track_caller]
+    pub fn slice(&self, range: impl RangeBounds<usize>) -> DstSlice<'_, H, F> {
+        let Range { start, end } = check_range(range, self.len);
+
+        DstSlice {
+            start: unsafe { self.ptr.byte_add(self.get_stride() * start) },
+            len: end - start,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns a mutable view of the members in `range`
+    ///
+    ///# Panics
+    ///
+    ///Panics if either bound is past the end of the array, or if the start is after the end
+    #[track_caller]
+    pub fn slice_mut(&mut self, range: impl RangeBounds<usize>) -> DstSliceMut<'_, H, F> {
+        let Range { start, end } = check_range(range, self.len);
+
+        DstSliceMut {
+            start: unsafe { self.ptr.byte_add(self.get_stride() * start) },
+            len: end - start,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns a shared view of every member of the array
+    pub fn as_slice(&self) -> DstSlice<'_, H, F> {
+        self.slice(..)
+    }
+
+    ///Returns a mutable view of every member of the array
+    pub fn as_mut_slice(&mut self) -> DstSliceMut<'_, H, F> {
+        self.slice_mut(..)
+    }
+
+    ///Sets every footer element of every member to a clone of `value`, dropping the old
+    ///elements
+    ///
+    ///Each footer is filled with [`slice::fill`], which becomes a `memset` for byte-sized
+    ///`Copy` elements
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let mut arr = DstArray::from_fn(2, 3, |index| (index, [index as u8; 2]));
+    ///
+    ///arr.fill_footers(7);
+    ///
+    ///assert!(arr.iter().all(|member| member.get_footer() == [7, 7]));
+    ///```
+    pub fn fill_footers(&mut self, value: F)
+    where
+        F: Clone,
+    {
+        for member in self.iter_mut() {
+            member.footer.fill(value.clone());
+        }
+    }
+
+    ///Sets footer element `footer_index` of member `arr_index` to
+    ///`f(arr_index, footer_index)`, dropping the old elements
+    pub fn fill_footers_with(&mut self, mut f: impl FnMut(usize, usize) -> F) {
+        for (arr_index, member) in self.iter_mut().enumerate() {
+            for (footer_index, element) in member.footer.iter_mut().enumerate() {
+                *element = f(arr_index, footer_index);
+            }
+        }
+    }
+
+    ///Sets the header of every member to a clone of `header`, dropping the old headers
+    pub fn fill_headers(&mut self, header: H)
+    where
+        H: Clone,
+    {
+        for member in self.iter_mut() {
+            member.header = header.clone();
+        }
+    }
+
+    ///Copies `count` members starting at member `start` to `dest`, which must not overlap them
+    ///
+    ///# Safety
+    ///
+    /// `start + count` must be at most the length of the array and `dest` must be valid for
+    /// `count` members with the same stride. The members are duplicated, so afterwards only
+    /// one of the copies may be dropped
+    unsafe fn copy_members_to(&self, start: usize, count: usize, dest: NonNull<DstData<H, F>>) {
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.member_ptr(start).cast::<u8>().as_ptr(),
+                dest.cast::<u8>().as_ptr(),
+                self.stride * count,
+            );
+        }
+    }
+
+    ///Frees the allocation without dropping any member, returning the allocator
+    ///
+    ///# Safety
+    ///
+    /// Every member must have been moved out or dropped already
+    unsafe fn free_forgetting_members(self) -> A {
+        let this = ManuallyDrop::new(self);
+
+        unsafe {
+            let alloc = ptr::read(&this.alloc);
+
+            let layout = DstData::array_layout_of_raw(this.ptr.as_ptr(), this.len, this.stride);
+            dealloc_layout(&alloc, this.ptr.cast::<u8>().as_ptr(), layout);
+
+            alloc
+        }
+    }
+
+    ///Moves the members of `first` and then those of `second` into one new array allocated
+    ///from the allocator of `first`, freeing both of their allocations
+    ///
+    ///If the footers of the two arrays don't have the same length, both are returned in the
+    ///error
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let first = DstArray::from_fn(1, 2, |index| (index, [0u8]));
+    ///let second = DstArray::from_fn(1, 3, |index| (index + 2, [1u8]));
+    ///
+    ///let Ok(arr) = DstArray::concat(first, second) else {
+    ///    panic!("the footers have the same length");
+    ///};
+    ///
+    ///assert!(arr.iter_headers().eq(&[0, 1, 2, 3, 4]));
+    ///
+    ///let Err(err) = DstArray::concat(arr, DstArray::from_fn(2, 1, |_| (5, [0, 0]))) else {
+    ///    panic!("the footers have different lengths");
+    ///};
+    ///
+    ///assert_eq!(err.first.len(), 5);
+    ///```
+    pub fn concat(
+        first: DstArray<H, F, A>,
+        second: DstArray<H, F, A>,
+    ) -> Result<DstArray<H, F, A>, MismatchedArraysError<H, F, A>> {
+        if first.get_footer_len() != second.get_footer_len() {
+            return Err(MismatchedArraysError { first, second });
+        }
+
+        let len = first
+            .len
+            .checked_add(second.len)
+            .unwrap_or_else(|| DstError::LayoutOverflow.handle());
+
+        let (ptr, stride) =
+            unsafe { DstData::alloc_self_array(first.get_footer_len(), len, &first.alloc) }
+                .unwrap_or_else(|err| err.handle());
+
+        unsafe {
+            first.copy_members_to(0, first.len, ptr);
+            second.copy_members_to(0, second.len, ptr.byte_add(stride * first.len));
+
+            drop(second.free_forgetting_members());
+
+            Ok(DstArray {
+                len,
+                stride,
+                ptr,
+                phantom: PhantomData,
+                alloc: first.free_forgetting_members(),
+            })
+        }
+    }
+
+    ///Consumes the array, moving the members before `mid` and the members from `mid` on into
+    ///two new arrays of exactly that many members, allocated from clones of the allocator
+    ///
+    ///If `mid` is `0` or the length of the array, the array is handed back whole next to an
+    ///empty one, without moving anything
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 5, |index| (index, [String::from("member")]));
+    ///let (front, back) = arr.split_off(2);
+    ///
+    ///assert!(front.iter_headers().eq(&[0, 1]));
+    ///assert!(back.iter_headers().eq(&[2, 3, 4]));
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `mid` is greater than the length of the array
+    #[track_caller]
+    pub fn split_off(self, mid: usize) -> (DstArray<H, F, A>, DstArray<H, F, A>)
+    where
+        A: Clone,
+    {
+        assert!(
+            mid <= self.len,
+            "mid {mid} out of range for array of length {}",
+            self.len
+        );
+
+        let footer_len = self.get_footer_len();
+
+        if mid == 0 || mid == self.len {
+            let empty = unsafe {
+                MaybeUninitDstArray::new_in(footer_len, 0, self.alloc.clone()).assume_init()
+            };
+
+            return if mid == 0 {
+                (empty, self)
+            } else {
+                (self, empty)
+            };
+        }
+
+        let front = MaybeUninitDstArray::new_untracked_in(footer_len, mid, self.alloc.clone());
+        let back =
+            MaybeUninitDstArray::new_untracked_in(footer_len, self.len - mid, self.alloc.clone());
+
+        unsafe {
+            self.copy_members_to(0, mid, front.ptr);
+            self.copy_members_to(mid, self.len - mid, back.ptr);
+
+            drop(self.free_forgetting_members());
+
+            (front.assume_init(), back.assume_init())
+        }
+    }
+
+    ///Consumes the array, dropping the members from `new_len` on and moving the rest into a
+    ///new array of exactly `new_len` members
+    ///
+    ///If `new_len` is at least the length of the array, the array is returned unchanged
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 5, |index| (index, [String::from("member")]));
+    ///let arr = arr.truncate_into(2);
+    ///
+    ///assert_eq!(arr.len(), 2);
+    ///assert!(arr.iter_headers().eq(&[0, 1]));
+    ///```
+    pub fn truncate_into(self, new_len: usize) -> DstArray<H, F, A> {
+        if new_len >= self.len {
+            return self;
+        }
+
+        let (ptr, stride) =
+            unsafe { DstData::alloc_self_array(self.get_footer_len(), new_len, &self.alloc) }
+                .unwrap_or_else(|err| err.handle());
+
+        let this = ManuallyDrop::new(self);
+
+        unsafe { this.copy_members_to(0, new_len, ptr) };
+
+        //Owns the moved members from here on, so they are dropped if a dropped member panics
+        let truncated = DstArray {
+            len: new_len,
+            stride,
+            ptr,
+            phantom: PhantomData,
+            alloc: unsafe { ptr::read(&this.alloc) },
+        };
+
+        unsafe {
+            for index in new_len..this.len {
+                drop_in_place(this.member_ptr(index).as_ptr());
+            }
+
+            let layout = DstData::array_layout_of_raw(this.ptr.as_ptr(), this.len, this.stride);
+            dealloc_layout(&truncated.alloc, this.ptr.cast::<u8>().as_ptr(), layout);
+        }
+
+        truncated
+    }
+
+    ///Consumes the array, moving the members `pred` returns `true` for into a new array of
+    ///exactly that many members and dropping the rest
+    ///
+    ///`pred` is called once on every member in order, before anything is moved or dropped
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 5, |index| (index, [String::from("member")]));
+    ///let even = arr.retain_into(|member| member.get_header() % 2 == 0);
+    ///
+    ///assert_eq!(even.len(), 3);
+    ///assert!(even.iter_headers().eq(&[0, 2, 4]));
+    ///```
+    pub fn retain_into(self, mut pred: impl FnMut(&DstData<H, F>) -> bool) -> DstArray<H, F, A> {
+        let keep = self.iter().map(&mut pred).collect::<Vec<_>>();
+        let len = keep.iter().filter(|&&keep| keep).count();
+
+        let (ptr, stride) =
+            unsafe { DstData::alloc_self_array(self.get_footer_len(), len, &self.alloc) }
+                .unwrap_or_else(|err| err.handle());
+
+        let this = ManuallyDrop::new(self);
+
+        let kept = (0..this.len).filter(|&index| keep[index]);
+        for (arr_index, index) in kept.enumerate() {
+            unsafe { this.copy_members_to(index, 1, ptr.byte_add(stride * arr_index)) };
+        }
+
+        //Owns the moved members from here on, so they are dropped if a rejected member panics
+        let retained = DstArray {
+            len,
+            stride,
+            ptr,
+            phantom: PhantomData,
+            alloc: unsafe { ptr::read(&this.alloc) },
+        };
+
+        unsafe {
+            for index in (0..this.len).filter(|&index| !keep[index]) {
+                drop_in_place(this.member_ptr(index).as_ptr());
+            }
+
+            let layout = DstData::array_layout_of_raw(this.ptr.as_ptr(), this.len, this.stride);
+            dealloc_layout(&retained.alloc, this.ptr.cast::<u8>().as_ptr(), layout);
+        }
+
+        retained
+    }
+
+    ///Clones the members `pred` returns `true` for into a new array of exactly that many
+    ///members, allocated from a clone of the allocator
+    ///
+    ///If a clone panics, the members cloned so far are dropped and the new allocation is freed
+    pub fn filter_clone(&self, mut pred: impl FnMut(&DstData<H, F>) -> bool) -> DstArray<H, F, A>
+    where
+        H: Clone,
+        F: Clone,
+        A: Clone,
+    {
+        let indices = (0..self.len)
+            .filter(|&index| pred(&self[index]))
+            .collect::<Vec<_>>();
+
+        let arr = MaybeUninitDstArray::new_untracked_in(
+            self.get_footer_len(),
+            indices.len(),
+            self.alloc.clone(),
+        );
+        let mut guard = ArrayPrefixGuard::new(arr.ptr, arr.stride);
+
+        for (arr_index, &index) in indices.iter().enumerate() {
+            unsafe {
+                self[index].clone_to_uninit(arr.get_element(arr_index).as_ptr().cast::<u8>());
+                guard.push_initialized();
+            }
+        }
+
+        guard.finish();
+
+        unsafe { arr.assume_init() }
+    }
+
+    ///Reverses the order of the members in place
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let mut arr = DstArray::from_fn(1, 3, |index| (index, [0u8]));
+    ///
+    ///arr.reverse();
+    ///
+    ///assert_eq!(*arr.get_header_ref(0), 2);
+    ///```
+    pub fn reverse(&mut self) {
+        self.as_mut_slice().reverse();
+    }
+
+    ///Copies the members in `src` to the members starting at `dest`, as
+    ///[`DstSliceMut::copy_within`]
+    #[track_caller]
+    pub fn copy_within(&mut self, src: impl RangeBounds<usize>, dest: usize)
+    where
+        H: Copy,
+        F: Copy,
+    {
+        self.as_mut_slice().copy_within(src, dest);
+    }
+
+    ///Binary searches an array sorted by `f`, as [`DstSlice::binary_search_by`]
+    pub fn binary_search_by<'a>(
+        &'a self,
+        f: impl FnMut(&'a DstData<H, F>) -> cmp::Ordering,
+    ) -> Result<usize, usize> {
+        self.as_slice().binary_search_by(f)
+    }
+
+    ///Binary searches an array sorted by the key `f` extracts, as
+    ///[`DstSlice::binary_search_by_key`]
+    pub fn binary_search_by_key<'a, K: Ord>(
+        &'a self,
+        key: &K,
+        f: impl FnMut(&'a DstData<H, F>) -> K,
+    ) -> Result<usize, usize> {
+        self.as_slice().binary_search_by_key(key, f)
+    }
+
+    ///Sorts the members in place with `compare`, as [`DstSliceMut::sort_unstable_by`]
+    pub fn sort_unstable_by(
+        &mut self,
+        compare: impl FnMut(&DstData<H, F>, &DstData<H, F>) -> cmp::Ordering,
+    ) {
+        self.as_mut_slice().sort_unstable_by(compare);
+    }
+
+    ///Sorts the members in place by the key `f` extracts from them, as
+    ///[`DstSliceMut::sort_unstable_by_key`]
+    pub fn sort_unstable_by_key<K: Ord>(&mut self, f: impl FnMut(&DstData<H, F>) -> K) {
+        self.as_mut_slice().sort_unstable_by_key(f);
+    }
+
+    ///Partitions the members around member `index`, as
+    ///[`DstSliceMut::select_nth_unstable_by`]
+    ///
+    ///# Panics
+    ///
+    ///Panics if `index` is out of bounds
+    #[track_caller]
+    pub fn select_nth_unstable_by(
+        &mut self,
+        index: usize,
+        compare: impl FnMut(&DstData<H, F>, &DstData<H, F>) -> cmp::Ordering,
+    ) -> DstPartition<'_, H, F> {
+        self.as_mut_slice().into_select_nth(index, compare)
+    }
+
+    ///Returns the indices of the members in the order `compare` sorts them into, keeping
+    ///equal members in their original order
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 4, |index| (index % 2, [index]));
+    ///
+    ///assert_eq!(arr.sorted_indices_by(|a, b| a.get_header().cmp(b.get_header())), [0, 2, 1, 3]);
+    ///```
+    pub fn sorted_indices_by(
+        &self,
+        mut compare: impl FnMut(&DstData<H, F>, &DstData<H, F>) -> cmp::Ordering,
+    ) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.len).collect();
+        order.sort_by(|&a, &b| compare(&self[a], &self[b]));
+
+        order
+    }
+
+    ///Sorts the members by the key `f` extracts from them, keeping equal members in their
+    ///original order
+    ///
+    ///`f` is called once per member, and the members are then moved straight to their final
+    ///position, so that each one is moved about once rather than swapped `O(log n)` times.
+    ///This is faster than [`DstArray::sort_unstable_by_key`] for large members, at the cost
+    ///of allocating the keys, an index per member and a scratch member
+    pub fn sort_by_cached_key<K: Ord>(&mut self, mut f: impl FnMut(&DstData<H, F>) -> K) {
+        let mut keys: Vec<(K, usize)> = self.iter().map(&mut f).zip(0..).collect();
+        keys.sort_unstable();
+
+        let order = keys.into_iter().map(|(_, index)| index).collect();
+
+        unsafe { self.as_mut_slice().apply_order(order) }
+    }
+
+    ///Splits the array into shared views of the members before `mid` and the members from
+    ///`mid` on
+    ///
+    ///# Panics
+    ///
+    ///Panics if `mid` is greater than the length of the array
+    #[track_caller]
+    pub fn split_at(&self, mid: usize) -> (DstSlice<'_, H, F>, DstSlice<'_, H, F>) {
+        self.as_slice().split_at(mid)
+    }
+
+    ///Splits the array into mutable views of the members before `mid` and the members from
+    ///`mid` on
+    ///
+    ///# Panics
+    ///
+    ///Panics if `mid` is greater than the length of the array
+    #[track_caller]
+    pub fn split_at_mut(&mut self, mid: usize) -> (DstSliceMut<'_, H, F>, DstSliceMut<'_, H, F>) {
+        self.as_mut_slice().split_at_mut(mid)
+    }
+
+    ///Equivalent to [`DstArray::slice_mut`] with `start..end`
+    #[track_caller]
+    pub fn get_mut_slice(&mut self, start: usize, end: usize) -> DstSliceMut<'_, H, F> {
+        self.slice_mut(start..end)
+    }
+
+    ///```
+    ///use custom_dst::MaybeUninitDstArray;
+    ///
+    ///let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(1, 3);
+    ///
+    ///for arr_index in 0..3 {
+    ///    dst_arr.write_header(arr_index, arr_index as u32);
+    ///    dst_arr.write_footer(arr_index, &[0]);
+    ///}
+    ///
+    ///let dst_arr = unsafe { dst_arr.assume_init() };
+    ///
+    ///let first = dst_arr.get_slice(0, 2);
+    ///let second = dst_arr.get_slice(1, 3);
+    ///
+    ///assert_eq!(*first[1].get_header(), 1);
+    ///assert_eq!(*second[0].get_header(), 1);
+    ///```
+    #[track_caller]
+    pub fn get_slice(&self, start: usize, end: usize) -> DstSlice<'_, H, F> {
+        self.slice(start..end)
+    }
+
+    ///Returns a pointer to the first member of the array, without giving up ownership of it
+    ///
+    ///The pointer is still valid for reading the footer length when the array is empty
+    pub fn as_ptr(&self) -> *const DstData<H, F> {
+        self.ptr.as_ptr().cast_const()
+    }
+
+    ///Returns `true` if both arrays are the same allocation, rather than merely equal
+    pub fn ptr_eq<B: Allocator>(&self, other: &DstArray<H, F, B>) -> bool {
+        ptr::eq(self.as_ptr(), other.as_ptr())
+    }
+
+    ///Returns an iterator over the members of the array
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(2, 3, |index| (index, [index; 2]));
+    ///
+    ///let headers: Vec<usize> = arr.iter().rev().map(|member| *member.get_header()).collect();
+    ///
+    ///assert_eq!(headers, [2, 1, 0]);
+    ///```
+    pub fn iter(&self) -> DstIter<'_, H, F> {
+        DstIter {
+            ptr: self.ptr,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns an iterator over mutable references to the members of the array
+    pub fn iter_mut(&mut self) -> DstIterMut<'_, H, F> {
+        DstIterMut {
+            ptr: self.ptr,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns an iterator over the headers of the members, which doesn't touch the footers
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(8, 100, |index| (index as u64, [0u8; 8]));
+    ///
+    ///assert_eq!(arr.iter_headers().sum::<u64>(), 4950);
+    ///```
+    pub fn iter_headers(&self) -> DstHeaders<'_, H, F> {
+        DstHeaders { iter: self.iter() }
+    }
+
+    ///Returns an iterator over mutable references to the headers of the members
+    pub fn iter_headers_mut(&mut self) -> DstHeadersMut<'_, H, F> {
+        DstHeadersMut {
+            iter: self.iter_mut(),
+        }
+    }
+
+    ///Returns an iterator over the footers of the members
+    pub fn iter_footers(&self) -> DstFooters<'_, H, F> {
+        DstFooters { iter: self.iter() }
+    }
+
+    ///Returns an iterator over mutable references to the footers of the members
+    pub fn iter_footers_mut(&mut self) -> DstFootersMut<'_, H, F> {
+        DstFootersMut {
+            iter: self.iter_mut(),
+        }
+    }
+
+    ///Returns an iterator over mutable references to the headers and footers of the members,
+    ///as [`DstData::get_parts_mut`]
+    pub fn iter_parts_mut(&mut self) -> DstPartsMut<'_, H, F> {
+        DstPartsMut {
+            iter: self.iter_mut(),
+        }
+    }
+
+    ///Returns an iterator over every footer element of every member in order, as if the
+    ///footers were concatenated
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(2, 3, |index| (index, [index * 2, index * 2 + 1]));
+    ///
+    ///assert!(arr.iter_all_footer_elements().copied().eq(0..6));
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if the array has more than `usize::MAX` footer elements in total, which only
+    ///zero-sized footer elements can, as the iterator couldn't report its exact length
+    #[track_caller]
+    pub fn iter_all_footer_elements(&self) -> DstFooterElements<'_, H, F> {
+        DstFooterElements {
+            cursor: unsafe { FooterCursor::new(self.ptr, self.len, self.stride) },
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns an iterator over mutable references to every footer element of every member
+    ///in order
+    ///
+    ///# Panics
+    ///
+    ///Panics under the same conditions as [`DstArray::iter_all_footer_elements`]
+    #[track_caller]
+    pub fn iter_all_footer_elements_mut(&mut self) -> DstFooterElementsMut<'_, H, F> {
+        DstFooterElementsMut {
+            cursor: unsafe { FooterCursor::new(self.ptr, self.len, self.stride) },
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn get_mut_arr_element(&mut self, index: usize) -> &mut DstData<H, F> {
+        check_index(index, self.len);
+
+        unsafe { self.member_ptr(index).as_mut() }
+    }
+
+    ///Returns mutable references to the members at every index in `indices` at once
+    ///
+    ///See [`DstSliceMut::get_disjoint_mut`]
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> Result<[&mut DstData<H, F>; N], GetDisjointMutError> {
+        self.as_mut_slice().into_disjoint_mut(indices)
+    }
+
+    pub fn swap(&mut self, arr: &mut DstArray<H, F, A>) {
+        std::mem::swap(&mut self.ptr, &mut arr.ptr);
+        std::mem::swap(&mut self.len, &mut arr.len);
+        std::mem::swap(&mut self.stride, &mut arr.stride);
+        std::mem::swap(&mut self.alloc, &mut arr.alloc);
+    }
+
+    pub fn get_arr_element(&self, index: usize) -> &DstData<H, F> {
+        check_index(index, self.len);
+
+        unsafe { self.member_ptr(index).as_ref() }
+    }
+}
+
+///```compile_fail,E0277
+///use custom_dst::DstArray;
+///use std::rc::Rc;
+///
+///let arr = DstArray::from_fn(1, 1, |_| (0u8, [Rc::new(0)]));
+///std::thread::spawn(move || drop(arr));
+///```
+unsafe impl<H: Send, F: Send, A: Allocator + Send> Send for DstArray<H, F, A> {}
+
+///```compile_fail,E0277
+///use custom_dst::DstArray;
+///use std::{cell::Cell, sync::Arc};
+///
+///let arr = Arc::new(DstArray::from_fn(1, 1, |_| (0u8, [Cell::new(0)])));
+///std::thread::spawn(move || arr.get_footer_ref(0)[0].get());
+///```
+unsafe impl<H: Sync, F: Sync, A: Allocator + Sync> Sync for DstArray<H, F, A> {}
+
+impl<H: Clone, F: Clone, A: Allocator + Clone> Clone for DstArray<H, F, A> {
+    ///Clones every member into a new allocation from a clone of the allocator
+    ///
+    ///`Copy` members are copied in one go per member. If a clone panics, the members cloned so
+    ///far are dropped and the new allocation is freed
+    fn clone(&self) -> Self {
+        let arr = MaybeUninitDstArray::new_untracked_in(
+            self.get_footer_len(),
+            self.len,
+            self.alloc.clone(),
+        );
+        let mut guard = ArrayPrefixGuard::new(arr.ptr, arr.stride);
+
+        for arr_index in 0..self.len {
+            unsafe {
+                self.get_arr_element(arr_index)
+                    .clone_to_uninit(arr.get_element(arr_index).as_ptr().cast::<u8>());
+                guard.push_initialized();
+            }
+        }
+
+        guard.finish();
+
+        unsafe { arr.assume_init() }
+    }
+
+    ///Clones `source` into `self`, reusing the allocation when both arrays have the same
+    ///number of members and footer length
+    ///
+    ///When the shapes match, every header and footer element is cloned in place with
+    ///[`Clone::clone_from`], so the old values are dropped as they are overwritten and nested
+    ///allocations can be reused too. If a clone panics, `self` is left with a mix of old and
+    ///new values, all of them valid. Otherwise a new array is cloned from `source` and the old
+    ///one is dropped
+    fn clone_from(&mut self, source: &Self) {
+        if self.len != source.len || self.get_footer_len() != source.get_footer_len() {
+            *self = source.clone();
+            return;
+        }
+
+        for (member, source) in self.iter_mut().zip(source) {
+            member.header.clone_from(&source.header);
+            member.footer.clone_from_slice(&source.footer);
+        }
+    }
+}
+
+impl<H, F, A: Allocator> Drop for DstArray<H, F, A> {
+    fn drop(&mut self) {
+        for index in 0..self.len {
+            unsafe {
+                drop_in_place(self.ptr.byte_add(self.get_stride() * index).as_ptr());
+            }
+        }
+
+        unsafe {
+            let layout = DstData::array_layout_of_raw(self.ptr.as_ptr(), self.len, self.stride);
+
+            dealloc_layout(&self.alloc, self.ptr.cast::<u8>().as_ptr(), layout);
+        }
+    }
+}
+
+impl<H, F, A: Allocator> Index<usize> for DstArray<H, F, A> {
+    type Output = DstData<H, F>;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &DstData<H, F> {
+        check_index(index, self.len);
+
+        unsafe { self.member_ptr(index).as_ref() }
+    }
+}
+
+impl<H, F, A: Allocator> IndexMut<usize> for DstArray<H, F, A> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut DstData<H, F> {
+        check_index(index, self.len);
+
+        unsafe { self.member_ptr(index).as_mut() }
+    }
+}
+
+impl<H, F, A: Allocator> fmt::Pointer for DstArray<H, F, A> {
+    ///Formats the address of the allocation
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Pointer::fmt(&self.as_ptr(), f)
+    }
+}
+
+impl<H, F> IntoIterator for DstArray<H, F> {
+    type Item = Dst<H, F>;
+    type IntoIter = DstIntoIter<H, F>;
+
+    ///Returns an iterator that moves each member into its own Dst
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 3, |index| (index.to_string(), [index]));
+    ///
+    ///let mut dsts = arr.into_iter();
+    ///let last = dsts.next_back().unwrap();
+    ///
+    ///assert_eq!(last.get_header_ref(), "2");
+    ///assert_eq!(dsts.len(), 2);
+    ///```
+    fn into_iter(self) -> DstIntoIter<H, F> {
+        let arr = ManuallyDrop::new(self);
+
+        DstIntoIter {
+            ptr: arr.ptr,
+            len: arr.len,
+            stride: arr.stride,
+            remaining: 0..arr.len,
+        }
+    }
+}
+
+impl<'a, H, F, A: Allocator> IntoIterator for &'a DstArray<H, F, A> {
+    type Item = &'a DstData<H, F>;
+    type IntoIter = DstIter<'a, H, F>;
+
+    fn into_iter(self) -> DstIter<'a, H, F> {
+        self.iter()
+    }
+}
+
+impl<'a, H, F, A: Allocator> IntoIterator for &'a mut DstArray<H, F, A> {
+    type Item = &'a mut DstData<H, F>;
+    type IntoIter = DstIterMut<'a, H, F>;
+
+    fn into_iter(self) -> DstIterMut<'a, H, F> {
+        self.iter_mut()
+    }
+}
+
+///A mutable view of a range of elements of a [`DstArray`]
+///
+///Like `&mut [T]`, the view can only be sent to another thread if the elements can be:
+///
+///```compile_fail,E0277
+///use custom_dst::DstSliceMut;
+///use std::rc::Rc;
+///
+///fn assert_send<T: Send>() {}
+///
+///assert_send::<DstSliceMut<'static, Rc<u32>, u8>>();
+///```
+///
+///```compile_fail,E0277
+///use custom_dst::DstSliceMut;
+///use std::cell::Cell;
+///
+///fn assert_sync<T: Sync>() {}
+///
+///assert_sync::<DstSliceMut<'static, u32, Cell<u8>>>();
+///```
+pub struct DstSliceMut<'a, H: Sized, F: Sized> {
+    start: NonNull<DstData<H, F>>,
+    len: usize,
+    stride: usize,
+    phantom: PhantomData<&'a mut DstData<H, F>>,
+}
+
+impl<'a, H, F> DstSliceMut<'a, H, F> {
+    ///Returns the number of members in the slice
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///Returns the number of footer elements of each member, which is known even when the
+    ///slice is empty
+    pub fn footer_len(&self) -> usize {
+        unsafe { DstData::get_len(self.start.as_ptr()) }
+    }
+
+    ///Returns a pointer to member `index`, which is one past the end if `index` is the length
+    ///
+    ///# Safety
+    ///
+    /// `index` must be at most the length of the slice
+    unsafe fn member_ptr(&self, index: usize) -> NonNull<DstData<H, F>> {
+        unsafe { self.start.byte_add(self.stride * index) }
+    }
+
+    ///Returns member `index`, or `None` if it is out of bounds
+    pub fn get(&self, index: usize) -> Option<&DstData<H, F>> {
+        if index < self.len {
+            Some(unsafe { self.member_ptr(index).as_ref() })
+        } else {
+            None
+        }
+    }
+
+    pub fn first(&self) -> Option<&DstData<H, F>> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&DstData<H, F>> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    ///Returns member `index` mutably, or `None` if it is out of bounds
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut DstData<H, F>> {
+        if index < self.len {
+            Some(unsafe { self.member_ptr(index).as_mut() })
+        } else {
+            None
+        }
+    }
+
+    pub fn first_mut(&mut self) -> Option<&mut DstData<H, F>> {
+        self.get_mut(0)
+    }
+
+    pub fn last_mut(&mut self) -> Option<&mut DstData<H, F>> {
+        self.get_mut(self.len.checked_sub(1)?)
+    }
+
+    ///Returns member `index` without checking that it is in bounds
+    ///
+    ///# Safety
+    ///
+    /// `index` must be less than the length of the slice. This is only checked in debug builds
+    #[track_caller]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &DstData<H, F> {
+        debug_assert!(
+            index < self.len,
+            "get_unchecked index {index} out of bounds"
+        );
+
+        unsafe { self.member_ptr(index).as_ref() }
+    }
+
+    ///Returns member `index` without checking that it is in bounds
+    ///
+    ///# Safety
+    ///
+    /// `index` must be less than the length of the slice. This is only checked in debug builds
+    #[track_caller]
+    pub unsafe fn get_unchecked_mut(&mut self, index: usize) -> &mut DstData<H, F> {
+        debug_assert!(
+            index < self.len,
+            "get_unchecked_mut index {index} out of bounds"
+        );
+
+        unsafe { self.member_ptr(index).as_mut() }
+    }
+
+    pub fn as_ptr(&self) -> *const DstData<H, F> {
+        self.start.as_ptr().cast_const()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut DstData<H, F> {
+        self.start.as_ptr()
+    }
+
+    ///Returns mutable references to the members at every index in `indices` at once
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///use std::slice::GetDisjointMutError;
+    ///
+    ///let mut arr = DstArray::from_fn(1, 3, |index| (index, [0u8]));
+    ///let mut slice = arr.as_mut_slice();
+    ///
+    ///let [a, b] = slice.get_disjoint_mut([2, 0]).unwrap();
+    ///std::mem::swap(a.get_header_mut(), b.get_header_mut());
+    ///
+    ///assert_eq!(*slice[0].get_header(), 2);
+    ///assert_eq!(
+    ///    slice.get_disjoint_mut([1, 1]).err(),
+    ///    Some(GetDisjointMutError::OverlappingIndices)
+    ///);
+    ///```
+    ///
+    ///# Errors
+    ///
+    ///Fails with [`GetDisjointMutError::IndexOutOfBounds`] if an index is out of bounds, and
+    ///with [`GetDisjointMutError::OverlappingIndices`] if an index appears more than once
+    pub fn get_disjoint_mut<const N: usize>(
+        &mut self,
+        indices: [usize; N],
+    ) -> Result<[&mut DstData<H, F>; N], GetDisjointMutError> {
+        self.reborrow().into_disjoint_mut(indices)
+    }
+
+    ///Implements [`DstSliceMut::get_disjoint_mut`], returning references that borrow the
+    ///array for as long as the slice did
+    fn into_disjoint_mut<const N: usize>(
+        self,
+        indices: [usize; N],
+    ) -> Result<[&'a mut DstData<H, F>; N], GetDisjointMutError> {
+        for (position, &index) in indices.iter().enumerate() {
+            if index >= self.len {
+                return Err(GetDisjointMutError::IndexOutOfBounds);
+            }
+
+            if indices[..position].contains(&index) {
+                return Err(GetDisjointMutError::OverlappingIndices);
+            }
+        }
+
+        Ok(indices.map(|index| unsafe { self.member_ptr(index).as_mut() }))
+    }
+
+    ///Splits the slice into the members before `mid` and the members from `mid` on
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let mut arr = DstArray::from_fn(1, 3, |index| (index, [0u8]));
+    ///
+    ///let (mut front, mut back) = arr.as_mut_slice().split_at_mut(1);
+    ///front[0].get_footer_mut()[0] = 1;
+    ///back[1].get_footer_mut()[0] = 2;
+    ///
+    ///assert_eq!(arr.get_footer_ref(0), [1]);
+    ///assert_eq!(arr.get_footer_ref(2), [2]);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `mid` is greater than the length of the slice
+    #[track_caller]
+    pub fn split_at_mut(self, mid: usize) -> (DstSliceMut<'a, H, F>, DstSliceMut<'a, H, F>) {
+        assert!(
+            mid <= self.len,
+            "mid {mid} out of range for slice of length {}",
+            self.len
+        );
+
+        unsafe { self.split_at_mut_unchecked(mid) }
+    }
+
+    ///Splits the slice at `mid` without checking it
+    ///
+    ///# Safety
+    ///
+    ///`mid` must not be greater than the length of the slice
+    pub unsafe fn split_at_mut_unchecked(
+        self,
+        mid: usize,
+    ) -> (DstSliceMut<'a, H, F>, DstSliceMut<'a, H, F>) {
+        (
+            DstSliceMut {
+                start: self.start,
+                len: mid,
+                stride: self.stride,
+                phantom: PhantomData,
+            },
+            DstSliceMut {
+                start: unsafe { self.start.byte_add(self.stride * mid) },
+                len: self.len - mid,
+                stride: self.stride,
+                phantom: PhantomData,
+            },
+        )
+    }
+
+    ///Splits the slice into its first member and the rest, or returns `None` if it is empty
+    ///
+    ///```
+    ///use custom_dst::{DstArray, DstSliceMut};
+    ///
+    ///fn number(slice: DstSliceMut<'_, usize, u8>, next: usize) {
+    ///    if let Some((first, rest)) = slice.split_first_mut() {
+    ///        *first.get_header_mut() = next;
+    ///        number(rest, next + 1);
+    ///    }
+    ///}
+    ///
+    ///let mut arr = DstArray::from_fn(1, 3, |_| (0, [0u8]));
+    ///number(arr.as_mut_slice(), 5);
+    ///
+    ///assert_eq!(*arr.get_header_ref(2), 7);
+    ///```
+    pub fn split_first_mut(self) -> Option<DstSplitEndMut<'a, H, F>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let (mut first, rest) = unsafe { self.split_at_mut_unchecked(1) };
+
+        Some((unsafe { first.start.as_mut() }, rest))
+    }
+
+    ///Splits the slice into its last member and the rest, or returns `None` if it is empty
+    pub fn split_last_mut(self) -> Option<DstSplitEndMut<'a, H, F>> {
+        let mid = self.len.checked_sub(1)?;
+
+        let (rest, mut last) = unsafe { self.split_at_mut_unchecked(mid) };
+
+        Some((unsafe { last.start.as_mut() }, rest))
+    }
+
+    ///Returns an iterator over the members of the slice
+    pub fn iter(&self) -> DstIter<'_, H, F> {
+        DstIter {
+            ptr: self.start,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns an iterator over mutable references to the members of the slice
+    pub fn iter_mut(&mut self) -> DstIterMut<'_, H, F> {
+        DstIterMut {
+            ptr: self.start,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns an iterator over mutable chunks of `chunk_size` members, the last of which may
+    ///be shorter
+    ///
+    ///# Panics
+    ///
+    ///Panics if `chunk_size` is 0
+    #[track_caller]
+    pub fn chunks_mut(&mut self, chunk_size: usize) -> DstChunksMut<'_, H, F> {
+        DstChunksMut::new(self.reborrow(), chunk_size)
+    }
+
+    ///Returns an iterator over mutable chunks of exactly `chunk_size` members, skipping the
+    ///members left over at the end
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let mut arr = DstArray::from_fn(1, 5, |index| (index, [0u8]));
+    ///let mut slice = arr.as_mut_slice();
+    ///
+    ///let mut chunks = slice.chunks_exact_mut(2);
+    ///for mut chunk in &mut chunks {
+    ///    chunk[1].get_footer_mut()[0] = 1;
+    ///}
+    ///
+    ///assert_eq!(chunks.remainder().len(), 1);
+    ///assert_eq!(arr.get_footer_ref(3), [1]);
+    ///assert_eq!(arr.get_footer_ref(4), [0]);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `chunk_size` is 0
+    #[track_caller]
+    pub fn chunks_exact_mut(&mut self, chunk_size: usize) -> DstChunksExactMut<'_, H, F> {
+        DstChunksExactMut::new(self.reborrow(), chunk_size)
+    }
+
+    ///Returns an iterator over mutable chunks of `chunk_size` members, starting from the end
+    ///
+    ///# Panics
+    ///
+    ///Panics if `chunk_size` is 0
+    #[track_caller]
+    pub fn rchunks_mut(&mut self, chunk_size: usize) -> DstRChunksMut<'_, H, F> {
+        DstRChunksMut::new(self.reborrow(), chunk_size)
+    }
+
+    ///Swaps members `a` and `b` in place
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let mut arr = DstArray::from_fn(1, 3, |index| (index, [index.to_string()]));
+    ///
+    ///arr.as_mut_slice().swap(0, 2);
+    ///
+    ///assert_eq!(*arr.get_header_ref(0), 2);
+    ///assert_eq!(arr.get_footer_ref(2), ["0"]);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `a` or `b` is out of bounds
+    #[track_caller]
+    pub fn swap(&mut self, a: usize, b: usize) {
+        check_index(a, self.len);
+        check_index(b, self.len);
+
+        unsafe { self.swap_unchecked(a, b) }
+    }
+
+    ///Swaps members `a` and `b` in place without checking the indices
+    ///
+    ///Every member has the same layout, so the members are swapped a stride of bytes at a
+    ///time, without a temporary allocation and without running any clones or destructors
+    ///
+    ///# Safety
+    ///
+    ///`a` and `b` must both be less than the length of the slice
+    pub unsafe fn swap_unchecked(&mut self, a: usize, b: usize) {
+        if a == b {
+            return;
+        }
+
+        unsafe {
+            ptr::swap_nonoverlapping(
+                self.start.byte_add(self.stride * a).cast::<u8>().as_ptr(),
+                self.start.byte_add(self.stride * b).cast::<u8>().as_ptr(),
+                self.stride,
+            );
+        }
+    }
+
+    ///Reverses the order of the members in place, by swapping them from both ends
+    pub fn reverse(&mut self) {
+        self.reverse_range(0..self.len);
+    }
+
+    ///Reverses the order of the members in `range`, which must be in bounds
+    fn reverse_range(&mut self, range: Range<usize>) {
+        let Range { start, end } = range;
+
+        for offset in 0..(end - start) / 2 {
+            unsafe { self.swap_unchecked(start + offset, end - 1 - offset) }
+        }
+    }
+
+    ///Copies the members in `src` to the members starting at `dest`, which may overlap them
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let mut arr = DstArray::from_fn(1, 5, |index| (index, [index as u8]));
+    ///
+    ///arr.as_mut_slice().copy_within(2.., 0);
+    ///
+    ///assert!(arr.iter_headers().eq(&[2, 3, 4, 3, 4]));
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `src` is out of bounds, or if `dest` is too far along to fit all of it
+    #[track_caller]
+    pub fn copy_within(&mut self, src: impl RangeBounds<usize>, dest: usize)
+    where
+        H: Copy,
+        F: Copy,
+    {
+        let Range { start, end } = check_range(src, self.len);
+        let count = end - start;
+
+        assert!(dest <= self.len - count, "dest is out of bounds");
+
+        unsafe {
+            ptr::copy(
+                self.member_ptr(start).cast::<u8>().as_ptr(),
+                self.member_ptr(dest).cast::<u8>().as_ptr(),
+                self.stride * count,
+            );
+        }
+    }
+
+    ///Rotates the members in place so that member `mid` becomes the first one
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let mut arr = DstArray::from_fn(1, 5, |index| (index, [0u8]));
+    ///
+    ///arr.as_mut_slice().rotate_left(2);
+    ///
+    ///let headers: Vec<usize> = arr.iter().map(|member| *member.get_header()).collect();
+    ///assert_eq!(headers, [2, 3, 4, 0, 1]);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `mid` is greater than the length of the slice
+    #[track_caller]
+    pub fn rotate_left(&mut self, mid: usize) {
+        assert!(
+            mid <= self.len,
+            "rotation by {mid} out of range for slice of length {}",
+            self.len
+        );
+
+        self.reverse_range(0..mid);
+        self.reverse_range(mid..self.len);
+        self.reverse();
+    }
+
+    ///Rotates the members in place so that the last `k` members come first
+    ///
+    ///# Panics
+    ///
+    ///Panics if `k` is greater than the length of the slice
+    #[track_caller]
+    pub fn rotate_right(&mut self, k: usize) {
+        assert!(
+            k <= self.len,
+            "rotation by {k} out of range for slice of length {}",
+            self.len
+        );
+
+        self.rotate_left(self.len - k);
+    }
+
+    ///Sorts the members with `compare`, which sees both headers and footers
+    ///
+    ///The sort is an in-place heapsort over whole members: it doesn't allocate, runs in
+    ///`O(n log n)` time, and isn't stable. If `compare` panics, every member is still in the
+    ///slice, in an unspecified order
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let mut arr = DstArray::from_fn(2, 4, |index| (index % 2, [index; 2]));
+    ///
+    ///arr.as_mut_slice().sort_unstable_by(|a, b| {
+    ///    b.get_header().cmp(a.get_header()).then(a.get_footer().cmp(b.get_footer()))
+    ///});
+    ///
+    ///let footers: Vec<usize> = arr.iter().map(|member| member.get_footer()[0]).collect();
+    ///assert_eq!(footers, [1, 3, 0, 2]);
+    ///```
+    pub fn sort_unstable_by(
+        &mut self,
+        mut compare: impl FnMut(&DstData<H, F>, &DstData<H, F>) -> cmp::Ordering,
+    ) {
+        let mut is_less = |slice: &Self, a: usize, b: usize| unsafe {
+            compare(
+                slice.start.byte_add(slice.stride * a).as_ref(),
+                slice.start.byte_add(slice.stride * b).as_ref(),
+            ) == cmp::Ordering::Less
+        };
+
+        //Moves `node` down the heap made of the first `end` members until its children are
+        //no greater than it
+        let mut sift_down = |slice: &mut Self, mut node: usize, end: usize| loop {
+            let mut child = 2 * node + 1;
+            if child >= end {
+                break;
+            }
+
+            if child + 1 < end && is_less(slice, child, child + 1) {
+                child += 1;
+            }
+            if !is_less(slice, node, child) {
+                break;
+            }
+
+            unsafe { slice.swap_unchecked(node, child) };
+            node = child;
+        };
+
+        for node in (0..self.len / 2).rev() {
+            sift_down(self, node, self.len);
+        }
+        for end in (1..self.len).rev() {
+            unsafe { self.swap_unchecked(0, end) };
+            sift_down(self, 0, end);
+        }
+    }
+
+    ///Sorts the members by the key `f` extracts from them, without preserving the order of
+    ///equal members
+    pub fn sort_unstable_by_key<K: Ord>(&mut self, mut f: impl FnMut(&DstData<H, F>) -> K) {
+        self.sort_unstable_by(|a, b| f(a).cmp(&f(b)));
+    }
+
+    ///Reorders the members so that member `index` is the one that would be there if the slice
+    ///were sorted by `compare`, with no greater member before it and no lesser member after
+    ///it
+    ///
+    ///Returns the members before `index`, the member at `index` and the members after it.
+    ///The partitioning is a quickselect over whole members that doesn't allocate and groups
+    ///members equal to the pivot so that duplicates don't slow it down. It runs in linear
+    ///time on typical input, and O(n²) time in the worst case
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let mut arr = DstArray::from_fn(1, 5, |index| ((index * 3) % 5, [0u8]));
+    ///
+    ///let (lower, median, upper) =
+    ///    arr.select_nth_unstable_by(2, |a, b| a.get_header().cmp(b.get_header()));
+    ///
+    ///assert_eq!(*median.get_header(), 2);
+    ///assert!(lower.iter().all(|member| *member.get_header() < 2));
+    ///assert!(upper.iter().all(|member| *member.get_header() > 2));
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `index` is out of bounds
+    #[track_caller]
+    pub fn select_nth_unstable_by(
+        &mut self,
+        index: usize,
+        compare: impl FnMut(&DstData<H, F>, &DstData<H, F>) -> cmp::Ordering,
+    ) -> DstPartition<'_, H, F> {
+        self.reborrow().into_select_nth(index, compare)
+    }
+
+    ///Implements [`DstSliceMut::select_nth_unstable_by`], returning parts that borrow the
+    ///array for as long as the slice did
+    #[track_caller]
+    fn into_select_nth(
+        mut self,
+        index: usize,
+        mut compare: impl FnMut(&DstData<H, F>, &DstData<H, F>) -> cmp::Ordering,
+    ) -> DstPartition<'a, H, F> {
+        check_index(index, self.len);
+
+        let (start, stride) = (self.start, self.stride);
+        let mut compare = |a: usize, b: usize| unsafe {
+            compare(
+                start.byte_add(stride * a).as_ref(),
+                start.byte_add(stride * b).as_ref(),
+            )
+        };
+
+        let mut low = 0;
+        let mut high = self.len;
+        while high - low > 1 {
+            //The median of the first, middle and last members, which keeps sorted and
+            //reverse sorted input linear
+            let mid = low + (high - low) / 2;
+            let last = high - 1;
+            let pivot = if compare(low, mid).is_lt() {
+                if compare(mid, last).is_lt() {
+                    mid
+                } else if compare(low, last).is_lt() {
+                    last
+                } else {
+                    low
+                }
+            } else if compare(low, last).is_lt() {
+                low
+            } else if compare(mid, last).is_lt() {
+                last
+            } else {
+                mid
+            };
+            unsafe { self.swap_unchecked(low, pivot) };
+
+            //Members in `low..equal` are less than the pivot, `equal..next` holds members equal
+            //to the pivot, so any of them can stand in for it, and those in `greater..high` are
+            //greater
+            let mut equal = low;
+            let mut next = low + 1;
+            let mut greater = high;
+            while next < greater {
+                match compare(next, equal) {
+                    cmp::Ordering::Less => {
+                        unsafe { self.swap_unchecked(equal, next) };
+                        equal += 1;
+                        next += 1;
+                    }
+                    cmp::Ordering::Equal => next += 1,
+                    cmp::Ordering::Greater => {
+                        greater -= 1;
+                        unsafe { self.swap_unchecked(next, greater) };
+                    }
+                }
+            }
+
+            if index < equal {
+                high = equal;
+            } else if index >= greater {
+                low = greater;
+            } else {
+                break;
+            }
+        }
+
+        let (lower, rest) = unsafe { self.split_at_mut_unchecked(index) };
+        let (mut nth, upper) = unsafe { rest.split_at_mut_unchecked(1) };
+
+        (lower, unsafe { nth.start.as_mut() }, upper)
+    }
+
+    ///Moves the members so that member `index` ends up where member `order[index]` was
+    ///
+    ///Each cycle of the permutation is walked once through a single scratch member, so every
+    ///member is moved once, plus one extra move per cycle
+    ///
+    ///# Safety
+    ///
+    ///`order` must be a permutation of `0..self.len`
+    unsafe fn apply_order(&mut self, mut order: Vec<usize>) {
+        let mut scratch = Vec::<mem::MaybeUninit<u8>>::with_capacity(self.stride);
+        let scratch = scratch.as_mut_ptr().cast::<u8>();
+        let member = |index: usize| {
+            unsafe { self.start.byte_add(self.stride * index) }
+                .cast::<u8>()
+                .as_ptr()
+        };
+
+        for cycle_start in 0..order.len() {
+            if order[cycle_start] == cycle_start {
+                continue;
+            }
+
+            unsafe {
+                ptr::copy_nonoverlapping(member(cycle_start), scratch, self.stride);
+
+                let mut hole = cycle_start;
+                loop {
+                    let source = order[hole];
+                    //A finished slot points at itself, so every cycle is only walked once
+                    order[hole] = hole;
+
+                    if source == cycle_start {
+                        ptr::copy_nonoverlapping(scratch, member(hole), self.stride);
+                        break;
+                    }
+
+                    ptr::copy_nonoverlapping(member(source), member(hole), self.stride);
+                    hole = source;
+                }
+            }
+        }
+    }
+
+    ///Returns a view of the same members that borrows `self`
+    fn reborrow(&mut self) -> DstSliceMut<'_, H, F> {
+        DstSliceMut {
+            start: self.start,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Moves the members out of `self`, leaving it empty
+    fn take(&mut self) -> DstSliceMut<'a, H, F> {
+        let empty = DstSliceMut {
+            start: self.start,
+            len: 0,
+            stride: self.stride,
+            phantom: PhantomData,
+        };
+
+        mem::replace(self, empty)
+    }
+
+    ///Returns a shared view of the same members, borrowing this one
+    pub fn as_shared(&self) -> DstSlice<'_, H, F> {
+        DstSlice {
+            start: self.start,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn as_shared_slice<'b>(self) -> DstSlice<'a, H, F>
+    where
+        'a: 'b,
+    {
+        DstSlice {
+            start: self.start,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, H, F> Index<usize> for DstSliceMut<'a, H, F> {
+    type Output = DstData<H, F>;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &DstData<H, F> {
+        check_index(index, self.len);
+
+        unsafe { self.member_ptr(index).as_ref() }
+    }
+}
+
+impl<'a, H, F> IndexMut<usize> for DstSliceMut<'a, H, F> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut DstData<H, F> {
+        check_index(index, self.len);
+
+        unsafe { self.member_ptr(index).as_mut() }
+    }
+}
+
+impl<'a, H, F> IntoIterator for DstSliceMut<'a, H, F> {
+    type Item = &'a mut DstData<H, F>;
+    type IntoIter = DstIterMut<'a, H, F>;
+
+    ///Consumes the slice, returning an iterator over references that live as long as the
+    ///slice's borrow of its array
+    fn into_iter(self) -> DstIterMut<'a, H, F> {
+        DstIterMut {
+            ptr: self.start,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'b, H, F> IntoIterator for &'b DstSliceMut<'_, H, F> {
+    type Item = &'b DstData<H, F>;
+    type IntoIter = DstIter<'b, H, F>;
+
+    fn into_iter(self) -> DstIter<'b, H, F> {
+        self.iter()
+    }
+}
+
+impl<'b, H, F> IntoIterator for &'b mut DstSliceMut<'_, H, F> {
+    type Item = &'b mut DstData<H, F>;
+    type IntoIter = DstIterMut<'b, H, F>;
+
+    fn into_iter(self) -> DstIterMut<'b, H, F> {
+        self.iter_mut()
+    }
+}
+
+unsafe impl<'a, H: Send, F: Send> Send for DstSliceMut<'a, H, F> {}
+
+unsafe impl<'a, H: Sync, F: Sync> Sync for DstSliceMut<'a, H, F> {}
+
+///The members before an index, the member at it and the members after it, as returned by
+///[`DstSliceMut::select_nth_unstable_by`]
+pub type DstPartition<'a, H, F> = (
+    DstSliceMut<'a, H, F>,
+    &'a mut DstData<H, F>,
+    DstSliceMut<'a, H, F>,
+);
+
+///One member at an end of a slice and the rest of it, as returned by
+///[`DstSliceMut::split_first_mut`] and [`DstSliceMut::split_last_mut`]
+pub type DstSplitEndMut<'a, H, F> = (&'a mut DstData<H, F>, DstSliceMut<'a, H, F>);
+
+///One member at an end of a slice and the rest of it, as returned by [`DstSlice::split_first`]
+///and [`DstSlice::split_last`]
+pub type DstSplitEnd<'a, H, F> = (&'a DstData<H, F>, DstSlice<'a, H, F>);
+
+///A shared view of a range of elements of a [`DstArray`]
+///
+///Like `&[T]`, the view is `Copy`, so it can be handed to several readers at once
+pub struct DstSlice<'a, H: Sized, F: Sized> {
+    start: NonNull<DstData<H, F>>,
+    len: usize,
+    stride: usize,
+    phantom: PhantomData<&'a DstData<H, F>>,
+}
+
+impl<'a, H, F> DstSlice<'a, H, F> {
+    ///Returns the number of members in the slice
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///Returns the number of footer elements of each member, which is known even when the
+    ///slice is empty
+    pub fn footer_len(&self) -> usize {
+        unsafe { DstData::get_len(self.start.as_ptr()) }
+    }
+
+    ///Returns a pointer to member `index`, which is one past the end if `index` is the length
+    ///
+    ///# Safety
+    ///
+    /// `index` must be at most the length of the slice
+    unsafe fn member_ptr(&self, index: usize) -> NonNull<DstData<H, F>> {
+        unsafe { self.start.byte_add(self.stride * index) }
+    }
+
+    ///Returns member `index`, or `None` if it is out of bounds
+    pub fn get(&self, index: usize) -> Option<&'a DstData<H, F>> {
+        if index < self.len {
+            Some(unsafe { self.member_ptr(index).as_ref() })
+        } else {
+            None
+        }
+    }
+
+    pub fn first(&self) -> Option<&'a DstData<H, F>> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&'a DstData<H, F>> {
+        self.get(self.len.checked_sub(1)?)
+    }
+
+    ///Returns member `index` without checking that it is in bounds
+    ///
+    ///# Safety
+    ///
+    /// `index` must be less than the length of the slice. This is only checked in debug builds
+    #[track_caller]
+    pub unsafe fn get_unchecked(&self, index: usize) -> &'a DstData<H, F> {
+        debug_assert!(
+            index < self.len,
+            "get_unchecked index {index} out of bounds"
+        );
+
+        unsafe { self.member_ptr(index).as_ref() }
+    }
+
+    pub fn as_ptr(&self) -> *const DstData<H, F> {
+        self.start.as_ptr().cast_const()
+    }
+}
+
+impl<'a, H, F> DstSlice<'a, H, F> {
+    ///Splits the slice into the members before `mid` and the members from `mid` on
+    ///
+    ///# Panics
+    ///
+    ///Panics if `mid` is greater than the length of the slice
+    #[track_caller]
+    pub fn split_at(self, mid: usize) -> (DstSlice<'a, H, F>, DstSlice<'a, H, F>) {
+        assert!(
+            mid <= self.len,
+            "mid {mid} out of range for slice of length {}",
+            self.len
+        );
+
+        unsafe { self.split_at_unchecked(mid) }
+    }
+
+    ///Splits the slice at `mid` without checking it
+    ///
+    ///# Safety
+    ///
+    ///`mid` must not be greater than the length of the slice
+    pub unsafe fn split_at_unchecked(self, mid: usize) -> (DstSlice<'a, H, F>, DstSlice<'a, H, F>) {
+        (
+            DstSlice {
+                start: self.start,
+                len: mid,
+                stride: self.stride,
+                phantom: PhantomData,
+            },
+            DstSlice {
+                start: unsafe { self.start.byte_add(self.stride * mid) },
+                len: self.len - mid,
+                stride: self.stride,
+                phantom: PhantomData,
+            },
+        )
+    }
+
+    ///Splits the slice into its first member and the rest, or returns `None` if it is empty
+    pub fn split_first(self) -> Option<DstSplitEnd<'a, H, F>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let (first, rest) = unsafe { self.split_at_unchecked(1) };
+
+        Some((unsafe { first.start.as_ref() }, rest))
+    }
+
+    ///Splits the slice into its last member and the rest, or returns `None` if it is empty
+    pub fn split_last(self) -> Option<DstSplitEnd<'a, H, F>> {
+        let mid = self.len.checked_sub(1)?;
+
+        let (rest, last) = unsafe { self.split_at_unchecked(mid) };
+
+        Some((unsafe { last.start.as_ref() }, rest))
+    }
+}
+
+impl<'a, H, F> DstSlice<'a, H, F> {
+    ///Returns an iterator over the members of the slice, which can outlive the slice itself
+    pub fn iter(&self) -> DstIter<'a, H, F> {
+        DstIter {
+            ptr: self.start,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, H, F> DstSlice<'a, H, F> {
+    ///Returns an iterator over chunks of `chunk_size` members, the last of which may be
+    ///shorter
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 5, |index| (index, [0u8]));
+    ///let lens = arr.as_slice().chunks(2).map(|chunk| chunk.len());
+    ///
+    ///assert!(lens.eq([2, 2, 1]));
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `chunk_size` is 0
+    #[track_caller]
+    pub fn chunks(&self, chunk_size: usize) -> DstChunks<'a, H, F> {
+        DstChunks::new(*self, chunk_size)
+    }
+
+    ///Returns an iterator over chunks of exactly `chunk_size` members, skipping the members
+    ///left over at the end
+    ///
+    ///# Panics
+    ///
+    ///Panics if `chunk_size` is 0
+    #[track_caller]
+    pub fn chunks_exact(&self, chunk_size: usize) -> DstChunksExact<'a, H, F> {
+        DstChunksExact::new(*self, chunk_size)
+    }
+
+    ///Binary searches a slice sorted by `f` for a member `f` maps to `Equal`
+    ///
+    ///Returns `Ok` with the index of a matching member, which may be any of them if there
+    ///are several, or `Err` with the index a matching member could be inserted at while
+    ///keeping the slice sorted, just like [`slice::binary_search_by`]
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 4, |index| (index * 10, [0u8]));
+    ///let slice = arr.as_slice();
+    ///
+    ///assert_eq!(slice.binary_search_by(|member| member.get_header().cmp(&20)), Ok(2));
+    ///assert_eq!(slice.binary_search_by_key(&25, |member| *member.get_header()), Err(3));
+    ///```
+    pub fn binary_search_by(
+        &self,
+        mut f: impl FnMut(&'a DstData<H, F>) -> cmp::Ordering,
+    ) -> Result<usize, usize> {
+        let mut low = 0;
+        let mut high = self.len;
+
+        while low < high {
+            let mid = low + (high - low) / 2;
+
+            match f(unsafe { self.start.byte_add(self.stride * mid).as_ref() }) {
+                cmp::Ordering::Less => low = mid + 1,
+                cmp::Ordering::Greater => high = mid,
+                cmp::Ordering::Equal => return Ok(mid),
+            }
+        }
+
+        Err(low)
+    }
+
+    ///Binary searches a slice sorted by the key `f` extracts for a member whose key is `key`,
+    ///as [`DstSlice::binary_search_by`]
+    pub fn binary_search_by_key<K: Ord>(
+        &self,
+        key: &K,
+        mut f: impl FnMut(&'a DstData<H, F>) -> K,
+    ) -> Result<usize, usize> {
+        self.binary_search_by(|member| f(member).cmp(key))
+    }
+
+    ///Returns an iterator over every run of `size` consecutive members, which overlap
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 4, |index| (index, [index * 10]));
+    ///
+    ///let deltas: Vec<usize> = arr
+    ///    .as_slice()
+    ///    .windows(2)
+    ///    .map(|pair| pair[1].get_footer()[0] - pair[0].get_footer()[0])
+    ///    .collect();
+    ///
+    ///assert_eq!(deltas, [10, 10, 10]);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `size` is 0
+    #[track_caller]
+    pub fn windows(&self, size: usize) -> DstWindows<'a, H, F> {
+        assert!(size != 0, "window size must be non-zero");
+
+        DstWindows { slice: *self, size }
+    }
+
+    ///Returns an iterator over chunks of `chunk_size` members, starting from the end
+    ///
+    ///```
+    ///use custom_dst::DstArray;
+    ///
+    ///let arr = DstArray::from_fn(1, 5, |index| (index, [0u8]));
+    ///
+    ///let lens: Vec<usize> = arr.as_slice().rchunks(2).map(|chunk| chunk.len()).collect();
+    ///
+    ///assert_eq!(lens, [2, 2, 1]);
+    ///```
+    ///
+    ///# Panics
+    ///
+    ///Panics if `chunk_size` is 0
+    #[track_caller]
+    pub fn rchunks(&self, chunk_size: usize) -> DstRChunks<'a, H, F> {
+        DstRChunks::new(*self, chunk_size)
+    }
+}
+
+impl<'a, H, F> IntoIterator for DstSlice<'a, H, F> {
+    type Item = &'a DstData<H, F>;
+    type IntoIter = DstIter<'a, H, F>;
+
+    fn into_iter(self) -> DstIter<'a, H, F> {
+        self.iter()
+    }
+}
+
+impl<'a, H, F> IntoIterator for &DstSlice<'a, H, F> {
+    type Item = &'a DstData<H, F>;
+    type IntoIter = DstIter<'a, H, F>;
+
+    fn into_iter(self) -> DstIter<'a, H, F> {
+        self.iter()
+    }
+}
+
+impl<H, F> Clone for DstSlice<'_, H, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H, F> Copy for DstSlice<'_, H, F> {}
+
+impl<'a, H, F> Index<usize> for DstSlice<'a, H, F> {
+    type Output = DstData<H, F>;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &DstData<H, F> {
+        check_index(index, self.len);
+
+        unsafe { self.member_ptr(index).as_ref() }
+    }
+}
+
+//A shared slice hands out `&DstData`, so like a `&T` it needs `Sync` to be sent or shared
+unsafe impl<'a, H: Sync, F: Sync> Send for DstSlice<'a, H, F> {}
+
+unsafe impl<'a, H: Sync, F: Sync> Sync for DstSlice<'a, H, F> {}
+
+///An iterator over mutable, non-overlapping chunks of a [`DstSliceMut`]
+///
+///Every chunk has `chunk_size` members, except for the last one which may be shorter, just
+///like [`slice::chunks_mut`]
+pub struct DstChunksMut<'a, H: Sized, F: Sized> {
+    slice: DstSliceMut<'a, H, F>,
+    chunk_size: usize,
+}
+
+impl<'a, H, F> DstChunksMut<'a, H, F> {
+    ///# Panics
+    ///
+    ///Panics if `size` is 0
+    #[track_caller]
+    pub fn new(slice: DstSliceMut<'a, H, F>, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        Self {
+            slice,
+            chunk_size: size,
+        }
+    }
+
+    ///Splits the slice at `mid`, keeping the back and returning the front
+    fn take_front(&mut self, mid: usize) -> DstSliceMut<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.take().split_at_mut_unchecked(mid) };
+        self.slice = snd;
+
+        fst
+    }
+
+    ///Splits the slice at `mid`, keeping the front and returning the back
+    fn take_back(&mut self, mid: usize) -> DstSliceMut<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.take().split_at_mut_unchecked(mid) };
+        self.slice = fst;
+
+        snd
+    }
+}
+
+impl<'a, H, F> Iterator for DstChunksMut<'a, H, F> {
+    type Item = DstSliceMut<'a, H, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            let chunksz = cmp::min(self.slice.len, self.chunk_size);
+
+            Some(self.take_front(chunksz))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len.div_ceil(self.chunk_size);
+
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    ///Skips the first `n` chunks in one step
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match n.checked_mul(self.chunk_size) {
+            Some(skip) if skip < self.slice.len => {
+                self.take_front(skip);
+
+                self.next()
+            }
+            _ => {
+                self.take_front(self.slice.len);
+
+                None
+            }
+        }
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstChunksMut<'a, H, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            let remainder = self.slice.len % self.chunk_size;
+            let chunksz = if remainder == 0 {
+                self.chunk_size
+            } else {
+                remainder
+            };
+
+            Some(self.take_back(self.slice.len - chunksz))
+        }
+    }
+
+    ///Skips the last `n` chunks in one step
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let len = self.len();
+        if n >= len {
+            self.take_front(self.slice.len);
+
+            return None;
+        }
+
+        //The first `len - n` chunks are kept, which is everything when `n` is 0
+        if let Some(kept) = (len - n).checked_mul(self.chunk_size) {
+            if kept < self.slice.len {
+                self.take_back(kept);
+            }
+        }
+
+        self.next_back()
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstChunksMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstChunksMut<'_, H, F> {}
+
+///An iterator over shared, non-overlapping chunks of a [`DstSlice`]
+///
+///Every chunk has `chunk_size` members, except for the last one which may be shorter, just
+///like [`slice::chunks`]
+pub struct DstChunks<'a, H: Sized, F: Sized> {
+    slice: DstSlice<'a, H, F>,
+    chunk_size: usize,
+}
+
+impl<'a, H, F> DstChunks<'a, H, F> {
+    ///# Panics
+    ///
+    ///Panics if `size` is 0
+    #[track_caller]
+    pub fn new(slice: DstSlice<'a, H, F>, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        Self {
+            slice,
+            chunk_size: size,
+        }
+    }
+
+    ///Splits the slice at `mid`, keeping the back and returning the front
+    fn take_front(&mut self, mid: usize) -> DstSlice<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.split_at_unchecked(mid) };
+        self.slice = snd;
+
+        fst
+    }
+
+    ///Splits the slice at `mid`, keeping the front and returning the back
+    fn take_back(&mut self, mid: usize) -> DstSlice<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.split_at_unchecked(mid) };
+        self.slice = fst;
+
+        snd
+    }
+}
+
+impl<'a, H, F> Iterator for DstChunks<'a, H, F> {
+    type Item = DstSlice<'a, H, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            let chunksz = cmp::min(self.slice.len, self.chunk_size);
+
+            Some(self.take_front(chunksz))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len.div_ceil(self.chunk_size);
+
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    ///Skips the first `n` chunks in one step
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match n.checked_mul(self.chunk_size) {
+            Some(skip) if skip < self.slice.len => {
+                self.take_front(skip);
+
+                self.next()
+            }
+            _ => {
+                self.take_front(self.slice.len);
+
+                None
+            }
+        }
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstChunks<'a, H, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            let remainder = self.slice.len % self.chunk_size;
+            let chunksz = if remainder == 0 {
+                self.chunk_size
+            } else {
+                remainder
+            };
+
+            Some(self.take_back(self.slice.len - chunksz))
+        }
+    }
+
+    ///Skips the last `n` chunks in one step
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let len = self.len();
+        if n >= len {
+            self.take_front(self.slice.len);
+
+            return None;
+        }
+
+        //The first `len - n` chunks are kept, which is everything when `n` is 0
+        if let Some(kept) = (len - n).checked_mul(self.chunk_size) {
+            if kept < self.slice.len {
+                self.take_back(kept);
+            }
+        }
+
+        self.next_back()
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstChunks<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstChunks<'_, H, F> {}
+
+impl<H, F> Clone for DstChunks<'_, H, F> {
+    fn clone(&self) -> Self {
+        DstChunks {
+            slice: self.slice,
+            chunk_size: self.chunk_size,
+        }
+    }
+}
+
+///An iterator over mutable chunks of exactly `chunk_size` members of a [`DstSliceMut`],
+///created by [`DstSliceMut::chunks_exact_mut`]
+///
+///The members left over at the end are skipped, and can be retrieved with
+///[`DstChunksExactMut::into_remainder`], just like [`slice::chunks_exact_mut`]
+pub struct DstChunksExactMut<'a, H: Sized, F: Sized> {
+    ///The members that will be yielded, a multiple of `chunk_size` of them
+    slice: DstSliceMut<'a, H, F>,
+    remainder: DstSliceMut<'a, H, F>,
+    chunk_size: usize,
+}
+
+impl<'a, H, F> DstChunksExactMut<'a, H, F> {
+    #[track_caller]
+    fn new(slice: DstSliceMut<'a, H, F>, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        let exact = slice.len - slice.len % size;
+        let (slice, remainder) = unsafe { slice.split_at_mut_unchecked(exact) };
+
+        Self {
+            slice,
+            remainder,
+            chunk_size: size,
+        }
+    }
+
+    ///Returns the members that don't fit in a chunk
+    pub fn remainder(&self) -> DstSlice<'_, H, F> {
+        self.remainder.as_shared()
+    }
+
+    ///Returns the members that don't fit in a chunk, for as long as the original borrow
+    pub fn into_remainder(self) -> DstSliceMut<'a, H, F> {
+        self.remainder
+    }
+
+    ///Splits the slice at `mid`, keeping the back and returning the front
+    fn take_front(&mut self, mid: usize) -> DstSliceMut<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.take().split_at_mut_unchecked(mid) };
+        self.slice = snd;
+
+        fst
+    }
+
+    ///Splits the slice at `mid`, keeping the front and returning the back
+    fn take_back(&mut self, mid: usize) -> DstSliceMut<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.take().split_at_mut_unchecked(mid) };
+        self.slice = fst;
+
+        snd
+    }
+}
+
+impl<'a, H, F> Iterator for DstChunksExactMut<'a, H, F> {
+    type Item = DstSliceMut<'a, H, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            Some(self.take_front(self.chunk_size))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len / self.chunk_size;
+
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    ///Skips the first `n` chunks in one step
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.take_front(self.slice.len);
+
+            return None;
+        }
+
+        self.take_front(n * self.chunk_size);
+
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstChunksExactMut<'a, H, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            Some(self.take_back(self.slice.len - self.chunk_size))
+        }
+    }
+
+    ///Skips the last `n` chunks in one step
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let len = self.len();
+        if n >= len {
+            self.take_front(self.slice.len);
+
+            return None;
+        }
+
+        self.take_back((len - n) * self.chunk_size);
+
+        self.next_back()
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstChunksExactMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstChunksExactMut<'_, H, F> {}
+
+///An iterator over shared chunks of exactly `chunk_size` members of a [`DstSlice`], created by
+///[`DstSlice::chunks_exact`]
+///
+///The members left over at the end are skipped, and can be retrieved with
+///[`DstChunksExact::remainder`], just like [`slice::chunks_exact`]
+pub struct DstChunksExact<'a, H: Sized, F: Sized> {
+    ///The members that will be yielded, a multiple of `chunk_size` of them
+    slice: DstSlice<'a, H, F>,
+    remainder: DstSlice<'a, H, F>,
+    chunk_size: usize,
+}
+
+impl<'a, H, F> DstChunksExact<'a, H, F> {
+    #[track_caller]
+    fn new(slice: DstSlice<'a, H, F>, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        let exact = slice.len - slice.len % size;
+        let (slice, remainder) = unsafe { slice.split_at_unchecked(exact) };
+
+        Self {
+            slice,
+            remainder,
+            chunk_size: size,
+        }
+    }
+
+    ///Returns the members that don't fit in a chunk
+    pub fn remainder(&self) -> DstSlice<'a, H, F> {
+        self.remainder
+    }
+
+    ///Splits the slice at `mid`, keeping the back and returning the front
+    fn take_front(&mut self, mid: usize) -> DstSlice<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.split_at_unchecked(mid) };
+        self.slice = snd;
+
+        fst
+    }
+
+    ///Splits the slice at `mid`, keeping the front and returning the back
+    fn take_back(&mut self, mid: usize) -> DstSlice<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.split_at_unchecked(mid) };
+        self.slice = fst;
+
+        snd
+    }
+}
+
+impl<'a, H, F> Iterator for DstChunksExact<'a, H, F> {
+    type Item = DstSlice<'a, H, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            Some(self.take_front(self.chunk_size))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len / self.chunk_size;
+
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    ///Skips the first `n` chunks in one step
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.take_front(self.slice.len);
+
+            return None;
+        }
+
+        self.take_front(n * self.chunk_size);
+
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstChunksExact<'a, H, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            Some(self.take_back(self.slice.len - self.chunk_size))
+        }
+    }
+
+    ///Skips the last `n` chunks in one step
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let len = self.len();
+        if n >= len {
+            self.take_front(self.slice.len);
+
+            return None;
+        }
+
+        self.take_back((len - n) * self.chunk_size);
+
+        self.next_back()
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstChunksExact<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstChunksExact<'_, H, F> {}
+
+impl<H, F> Clone for DstChunksExact<'_, H, F> {
+    fn clone(&self) -> Self {
+        DstChunksExact {
+            slice: self.slice,
+            remainder: self.remainder,
+            chunk_size: self.chunk_size,
+        }
+    }
+}
+
+///An iterator over mutable chunks of a [`DstSliceMut`] starting from the end, created by
+///[`DstSliceMut::rchunks_mut`]
+///
+///Every chunk has `chunk_size` members, except for the one at the front of the slice which
+///may be shorter and is yielded last, just like [`slice::rchunks_mut`]
+pub struct DstRChunksMut<'a, H: Sized, F: Sized> {
+    slice: DstSliceMut<'a, H, F>,
+    chunk_size: usize,
+}
+
+impl<'a, H, F> DstRChunksMut<'a, H, F> {
+    #[track_caller]
+    fn new(slice: DstSliceMut<'a, H, F>, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        Self {
+            slice,
+            chunk_size: size,
+        }
+    }
+
+    ///Splits the slice at `mid`, keeping the back and returning the front
+    fn take_front(&mut self, mid: usize) -> DstSliceMut<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.take().split_at_mut_unchecked(mid) };
+        self.slice = snd;
+
+        fst
+    }
+
+    ///Splits the slice at `mid`, keeping the front and returning the back
+    fn take_back(&mut self, mid: usize) -> DstSliceMut<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.take().split_at_mut_unchecked(mid) };
+        self.slice = fst;
+
+        snd
+    }
+}
+
+impl<'a, H, F> Iterator for DstRChunksMut<'a, H, F> {
+    type Item = DstSliceMut<'a, H, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            let chunksz = cmp::min(self.slice.len, self.chunk_size);
+
+            Some(self.take_back(self.slice.len - chunksz))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len.div_ceil(self.chunk_size);
+
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    ///Skips the last `n` chunks in one step
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match n.checked_mul(self.chunk_size) {
+            Some(skip) if skip < self.slice.len => {
+                self.take_back(self.slice.len - skip);
+
+                self.next()
+            }
+            _ => {
+                self.take_front(self.slice.len);
+
+                None
+            }
+        }
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstRChunksMut<'a, H, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            let remainder = self.slice.len % self.chunk_size;
+            let chunksz = if remainder == 0 {
+                self.chunk_size
+            } else {
+                remainder
+            };
+
+            Some(self.take_front(chunksz))
+        }
+    }
+
+    ///Skips the first `n` chunks in one step
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let len = self.len();
+        if n >= len {
+            self.take_front(self.slice.len);
+
+            return None;
+        }
+
+        //The last `len - n` chunks are kept, which is everything when `n` is 0
+        if let Some(kept) = (len - n).checked_mul(self.chunk_size) {
+            if kept < self.slice.len {
+                self.take_front(self.slice.len - kept);
+            }
+        }
+
+        self.next_back()
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstRChunksMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstRChunksMut<'_, H, F> {}
+
+///An iterator over shared chunks of a [`DstSlice`] starting from the end, created by
+///[`DstSlice::rchunks`]
+///
+///Every chunk has `chunk_size` members, except for the one at the front of the slice which
+///may be shorter and is yielded last, just like [`slice::rchunks`]
+pub struct DstRChunks<'a, H: Sized, F: Sized> {
+    slice: DstSlice<'a, H, F>,
+    chunk_size: usize,
+}
+
+impl<'a, H, F> DstRChunks<'a, H, F> {
+    #[track_caller]
+    fn new(slice: DstSlice<'a, H, F>, size: usize) -> Self {
+        assert!(size != 0, "chunk size must be non-zero");
+
+        Self {
+            slice,
+            chunk_size: size,
+        }
+    }
+
+    ///Splits the slice at `mid`, keeping the back and returning the front
+    fn take_front(&mut self, mid: usize) -> DstSlice<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.split_at_unchecked(mid) };
+        self.slice = snd;
+
+        fst
+    }
+
+    ///Splits the slice at `mid`, keeping the front and returning the back
+    fn take_back(&mut self, mid: usize) -> DstSlice<'a, H, F> {
+        let (fst, snd) = unsafe { self.slice.split_at_unchecked(mid) };
+        self.slice = fst;
+
+        snd
+    }
+}
+
+impl<'a, H, F> Iterator for DstRChunks<'a, H, F> {
+    type Item = DstSlice<'a, H, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            let chunksz = cmp::min(self.slice.len, self.chunk_size);
+
+            Some(self.take_back(self.slice.len - chunksz))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.slice.len.div_ceil(self.chunk_size);
+
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    ///Skips the last `n` chunks in one step
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match n.checked_mul(self.chunk_size) {
+            Some(skip) if skip < self.slice.len => {
+                self.take_back(self.slice.len - skip);
+
+                self.next()
+            }
+            _ => {
+                self.take_front(self.slice.len);
+
+                None
+            }
+        }
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstRChunks<'a, H, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.len == 0 {
+            None
+        } else {
+            let remainder = self.slice.len % self.chunk_size;
+            let chunksz = if remainder == 0 {
+                self.chunk_size
+            } else {
+                remainder
+            };
+
+            Some(self.take_front(chunksz))
+        }
+    }
+
+    ///Skips the first `n` chunks in one step
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        let len = self.len();
+        if n >= len {
+            self.take_front(self.slice.len);
+
+            return None;
+        }
+
+        //The last `len - n` chunks are kept, which is everything when `n` is 0
+        if let Some(kept) = (len - n).checked_mul(self.chunk_size) {
+            if kept < self.slice.len {
+                self.take_front(self.slice.len - kept);
+            }
+        }
+
+        self.next_back()
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstRChunks<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstRChunks<'_, H, F> {}
+
+impl<H, F> Clone for DstRChunks<'_, H, F> {
+    fn clone(&self) -> Self {
+        DstRChunks {
+            slice: self.slice,
+            chunk_size: self.chunk_size,
+        }
+    }
+}
+
+///An iterator over overlapping windows of `size` members of a [`DstSlice`], created by
+///[`DstSlice::windows`]
+pub struct DstWindows<'a, H: Sized, F: Sized> {
+    ///The members the remaining windows are taken from
+    slice: DstSlice<'a, H, F>,
+    size: usize,
+}
+
+impl<'a, H, F> DstWindows<'a, H, F> {
+    ///Returns the window of `size` members starting at member `start` of the slice
+    ///
+    ///# Safety
+    ///
+    ///`start + size` must not be greater than the length of the slice
+    unsafe fn window(&self, start: usize) -> DstSlice<'a, H, F> {
+        DstSlice {
+            start: unsafe { self.slice.start.byte_add(self.slice.stride * start) },
+            len: self.size,
+            stride: self.slice.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Drops the first `count` windows, or every window if there are fewer than `count`
+    fn skip_front(&mut self, count: usize) {
+        let count = cmp::min(count, self.slice.len);
+
+        self.slice = unsafe { self.slice.split_at_unchecked(count).1 };
+    }
+
+    ///Drops the last `count` windows, or every window if there are fewer than `count`
+    fn skip_back(&mut self, count: usize) {
+        let count = cmp::min(count, self.slice.len);
+
+        self.slice = unsafe { self.slice.split_at_unchecked(self.slice.len - count).0 };
+    }
+}
+
+impl<'a, H, F> Iterator for DstWindows<'a, H, F> {
+    type Item = DstSlice<'a, H, F>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.slice.len < self.size {
+            None
+        } else {
+            let window = unsafe { self.window(0) };
+            self.skip_front(1);
+
+            Some(window)
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.slice.len + 1).saturating_sub(self.size);
+
+        (len, Some(len))
+    }
+
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    ///Skips the first `n` windows in one step
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_front(n);
+
+        self.next()
+    }
+
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstWindows<'a, H, F> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.slice.len < self.size {
+            None
+        } else {
+            let window = unsafe { self.window(self.slice.len - self.size) };
+            self.skip_back(1);
+
+            Some(window)
+        }
+    }
+
+    ///Skips the last `n` windows in one step
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.skip_back(n);
+
+        self.next_back()
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstWindows<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstWindows<'_, H, F> {}
+
+impl<H, F> Clone for DstWindows<'_, H, F> {
+    fn clone(&self) -> Self {
+        DstWindows {
+            slice: self.slice,
+            size: self.size,
+        }
+    }
+}
+
+///An iterator over the members of a [`DstArray`] or a view of one, created by
+///[`DstArray::iter`]
+pub struct DstIter<'a, H: Sized, F: Sized> {
+    ///The next member from the front
+    ptr: NonNull<DstData<H, F>>,
+    ///The number of members left
+    len: usize,
+    stride: usize,
+    phantom: PhantomData<&'a DstData<H, F>>,
+}
+
+impl<'a, H, F> Iterator for DstIter<'a, H, F> {
+    type Item = &'a DstData<H, F>;
+
+    fn next(&mut self) -> Option<&'a DstData<H, F>> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let member = self.ptr;
+        self.len -= 1;
+        self.ptr = unsafe { self.ptr.byte_add(self.stride) };
+
+        Some(unsafe { member.as_ref() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstIter<'a, H, F> {
+    fn next_back(&mut self) -> Option<&'a DstData<H, F>> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+
+        Some(unsafe { self.ptr.byte_add(self.stride * self.len).as_ref() })
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstIter<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstIter<'_, H, F> {}
+
+impl<H, F> Clone for DstIter<'_, H, F> {
+    fn clone(&self) -> Self {
+        DstIter {
+            ptr: self.ptr,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+}
+
+unsafe impl<H: Sync, F: Sync> Send for DstIter<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstIter<'_, H, F> {}
+
+///An iterator over mutable references to the members of a [`DstArray`] or a view of one,
+///created by [`DstArray::iter_mut`]
+pub struct DstIterMut<'a, H: Sized, F: Sized> {
+    ///The next member from the front
+    ptr: NonNull<DstData<H, F>>,
+    ///The number of members left
+    len: usize,
+    stride: usize,
+    phantom: PhantomData<&'a mut DstData<H, F>>,
+}
+
+impl<'a, H, F> Iterator for DstIterMut<'a, H, F> {
+    type Item = &'a mut DstData<H, F>;
+
+    fn next(&mut self) -> Option<&'a mut DstData<H, F>> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let mut member = self.ptr;
+        self.len -= 1;
+        self.ptr = unsafe { self.ptr.byte_add(self.stride) };
+
+        Some(unsafe { member.as_mut() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstIterMut<'a, H, F> {
+    fn next_back(&mut self) -> Option<&'a mut DstData<H, F>> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.len -= 1;
+
+        Some(unsafe { self.ptr.byte_add(self.stride * self.len).as_mut() })
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstIterMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstIterMut<'_, H, F> {}
+
+unsafe impl<H: Send, F: Send> Send for DstIterMut<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstIterMut<'_, H, F> {}
+
+///An iterator that moves the members of a [`DstArray`] into standalone Dsts, created by
+///[`DstArray::into_iter`]
+///
+///Dropping the iterator drops the members it didn't yield and frees the array
+pub struct DstIntoIter<H: Sized, F: Sized> {
+    ptr: NonNull<DstData<H, F>>,
+    ///The number of members the array was allocated with
+    len: usize,
+    stride: usize,
+    ///The members that haven't been moved out yet
+    remaining: Range<usize>,
+}
+
+impl<H, F> DstIntoIter<H, F> {
+    ///Moves member `arr_index` into a new Dst, leaving the member moved-from
+    ///
+    ///# Safety
+    ///
+    ///Member `arr_index` must be initialized and must already have left `remaining`
+    unsafe fn move_member(&self, dst_ptr: NonNull<DstData<H, F>>, arr_index: usize) -> Dst<H, F> {
+        unsafe {
+            let member = self.ptr.byte_add(self.stride * arr_index);
+
+            ptr::copy_nonoverlapping(
+                member.cast::<u8>().as_ptr(),
+                dst_ptr.cast::<u8>().as_ptr(),
+                DstData::layout_of_raw(member.as_ptr()).size(),
+            );
+        }
+
+        Dst {
+            ptr: dst_ptr,
+            phantom: PhantomData,
+            alloc: Global,
+        }
+    }
+
+    fn alloc_member(&self) -> NonNull<DstData<H, F>> {
+        let footer_len = unsafe { DstData::get_len(self.ptr.as_ptr()) };
+
+        unsafe { DstData::alloc_self(footer_len, &Global) }.unwrap_or_else(|err| err.handle())
+    }
+}
+
+impl<H, F> Iterator for DstIntoIter<H, F> {
+    type Item = Dst<H, F>;
+
+    fn next(&mut self) -> Option<Dst<H, F>> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        //Allocated before the member leaves `remaining`, so that it is still dropped if the
+        //allocation fails
+        let dst_ptr = self.alloc_member();
+        let arr_index = self.remaining.next()?;
+
+        Some(unsafe { self.move_member(dst_ptr, arr_index) })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining.size_hint()
+    }
+}
+
+impl<H, F> DoubleEndedIterator for DstIntoIter<H, F> {
+    fn next_back(&mut self) -> Option<Dst<H, F>> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        let dst_ptr = self.alloc_member();
+        let arr_index = self.remaining.next_back()?;
+
+        Some(unsafe { self.move_member(dst_ptr, arr_index) })
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstIntoIter<H, F> {}
+
+impl<H, F> FusedIterator for DstIntoIter<H, F> {}
+
+impl<H, F> Drop for DstIntoIter<H, F> {
+    fn drop(&mut self) {
+        for arr_index in self.remaining.clone() {
+            unsafe { drop_in_place(self.ptr.byte_add(self.stride * arr_index).as_ptr()) }
+        }
+
+        unsafe {
+            let layout = DstData::array_layout_of_raw(self.ptr.as_ptr(), self.len, self.stride);
+
+            dealloc_layout(&Global, self.ptr.cast::<u8>().as_ptr(), layout);
+        }
+    }
+}
+
+unsafe impl<H: Send, F: Send> Send for DstIntoIter<H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstIntoIter<H, F> {}
+
+///An iterator over the headers of the members of a [`DstArray`], created by
+///[`DstArray::iter_headers`]
+pub struct DstHeaders<'a, H: Sized, F: Sized> {
+    iter: DstIter<'a, H, F>,
+}
+
+impl<'a, H, F> Iterator for DstHeaders<'a, H, F> {
+    type Item = &'a H;
+
+    fn next(&mut self) -> Option<&'a H> {
+        self.iter.next().map(|member| &member.header)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstHeaders<'a, H, F> {
+    fn next_back(&mut self) -> Option<&'a H> {
+        self.iter.next_back().map(|member| &member.header)
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstHeaders<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstHeaders<'_, H, F> {}
+
+impl<H, F> Clone for DstHeaders<'_, H, F> {
+    fn clone(&self) -> Self {
+        DstHeaders {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+///An iterator over mutable references to the headers of the members of a [`DstArray`], created by
+///[`DstArray::iter_headers_mut`]
+pub struct DstHeadersMut<'a, H: Sized, F: Sized> {
+    iter: DstIterMut<'a, H, F>,
+}
+
+impl<'a, H, F> Iterator for DstHeadersMut<'a, H, F> {
+    type Item = &'a mut H;
+
+    fn next(&mut self) -> Option<&'a mut H> {
+        self.iter.next().map(|member| &mut member.header)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstHeadersMut<'a, H, F> {
+    fn next_back(&mut self) -> Option<&'a mut H> {
+        self.iter.next_back().map(|member| &mut member.header)
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstHeadersMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstHeadersMut<'_, H, F> {}
+
+///An iterator over the footers of the members of a [`DstArray`], created by
+///[`DstArray::iter_footers`]
+pub struct DstFooters<'a, H: Sized, F: Sized> {
+    iter: DstIter<'a, H, F>,
+}
+
+impl<'a, H, F> Iterator for DstFooters<'a, H, F> {
+    type Item = &'a [F];
+
+    fn next(&mut self) -> Option<&'a [F]> {
+        self.iter.next().map(|member| &member.footer)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstFooters<'a, H, F> {
+    fn next_back(&mut self) -> Option<&'a [F]> {
+        self.iter.next_back().map(|member| &member.footer)
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstFooters<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstFooters<'_, H, F> {}
+
+impl<H, F> Clone for DstFooters<'_, H, F> {
+    fn clone(&self) -> Self {
+        DstFooters {
+            iter: self.iter.clone(),
+        }
+    }
+}
+
+///An iterator over mutable references to the footers of the members of a [`DstArray`], created by
+///[`DstArray::iter_footers_mut`]
+pub struct DstFootersMut<'a, H: Sized, F: Sized> {
+    iter: DstIterMut<'a, H, F>,
+}
+
+impl<'a, H, F> Iterator for DstFootersMut<'a, H, F> {
+    type Item = &'a mut [F];
+
+    fn next(&mut self) -> Option<&'a mut [F]> {
+        self.iter.next().map(|member| &mut member.footer)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstFootersMut<'a, H, F> {
+    fn next_back(&mut self) -> Option<&'a mut [F]> {
+        self.iter.next_back().map(|member| &mut member.footer)
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstFootersMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstFootersMut<'_, H, F> {}
+
+///An iterator over mutable references to the headers and footers of the members of a [`DstArray`], created by
+///[`DstArray::iter_parts_mut`]
+pub struct DstPartsMut<'a, H: Sized, F: Sized> {
+    iter: DstIterMut<'a, H, F>,
+}
+
+impl<'a, H, F> Iterator for DstPartsMut<'a, H, F> {
+    type Item = (&'a mut H, &'a mut [F]);
+
+    fn next(&mut self) -> Option<(&'a mut H, &'a mut [F])> {
+        self.iter.next().map(DstData::get_parts_mut)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstPartsMut<'a, H, F> {
+    fn next_back(&mut self) -> Option<(&'a mut H, &'a mut [F])> {
+        self.iter.next_back().map(DstData::get_parts_mut)
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstPartsMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstPartsMut<'_, H, F> {}
+
+///Walks every footer element of a run of members in order, from both ends
+struct FooterCursor<F> {
+    ///The footer of the member holding the next element from the front
+    front: NonNull<F>,
+    front_offset: usize,
+    ///The footer of the member holding the next element from the back
+    back: NonNull<F>,
+    ///One past the offset of the next element from the back
+    back_offset: usize,
+    footer_len: usize,
+    stride: usize,
+    remaining: usize,
+}
+
+impl<F> FooterCursor<F> {
+    ///# Safety
+    ///
+    ///`start` must point to `len` initialized members laid out `stride` bytes apart
+    #[track_caller]
+    unsafe fn new<H>(start: NonNull<DstData<H, F>>, len: usize, stride: usize) -> Self {
+        let footer_len = unsafe { DstData::get_len(start.as_ptr()) };
+
+        //An empty array may not have an allocation to project into
+        let (front, back) = if len == 0 {
+            (NonNull::dangling(), NonNull::dangling())
+        } else {
+            let front = unsafe {
+                NonNull::new_unchecked(DstData::get_footer_slice(start.as_ptr()).as_mut_ptr())
+            };
+
+            (front, unsafe { front.byte_add(stride * (len - 1)) })
+        };
+
+        FooterCursor {
+            front,
+            front_offset: 0,
+            back,
+            back_offset: footer_len,
+            footer_len,
+            stride,
+            remaining: len
+                .checked_mul(footer_len)
+                .expect("the array has more than usize::MAX footer elements"),
+        }
+    }
+
+    fn next(&mut self) -> Option<NonNull<F>> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let element = unsafe { self.front.add(self.front_offset) };
+        self.remaining -= 1;
+        self.front_offset += 1;
+
+        //Only stepped while elements remain, so that the pointer never leaves the array
+        if self.front_offset == self.footer_len && self.remaining != 0 {
+            self.front = unsafe { self.front.byte_add(self.stride) };
+            self.front_offset = 0;
+        }
+
+        Some(element)
+    }
+
+    fn next_back(&mut self) -> Option<NonNull<F>> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+        self.back_offset -= 1;
+        let element = unsafe { self.back.add(self.back_offset) };
+
+        if self.back_offset == 0 && self.remaining != 0 {
+            self.back = unsafe { self.back.byte_sub(self.stride) };
+            self.back_offset = self.footer_len;
+        }
+
+        Some(element)
+    }
+}
+
+///An iterator over every footer element of a [`DstArray`] in order, created by
+///[`DstArray::iter_all_footer_elements`]
+pub struct DstFooterElements<'a, H: Sized, F: Sized> {
+    cursor: FooterCursor<F>,
+    phantom: PhantomData<&'a DstData<H, F>>,
+}
+
+impl<'a, H, F> Iterator for DstFooterElements<'a, H, F> {
+    type Item = &'a F;
+
+    fn next(&mut self) -> Option<&'a F> {
+        self.cursor
+            .next()
+            .map(|element| unsafe { element.as_ref() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstFooterElements<'a, H, F> {
+    fn next_back(&mut self) -> Option<&'a F> {
+        self.cursor
+            .next_back()
+            .map(|element| unsafe { element.as_ref() })
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstFooterElements<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstFooterElements<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Send for DstFooterElements<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstFooterElements<'_, H, F> {}
+
+///An iterator over mutable references to every footer element of a [`DstArray`] in order,
+///created by [`DstArray::iter_all_footer_elements_mut`]
+pub struct DstFooterElementsMut<'a, H: Sized, F: Sized> {
+    cursor: FooterCursor<F>,
+    phantom: PhantomData<&'a mut DstData<H, F>>,
+}
+
+impl<'a, H, F> Iterator for DstFooterElementsMut<'a, H, F> {
+    type Item = &'a mut F;
+
+    fn next(&mut self) -> Option<&'a mut F> {
+        self.cursor
+            .next()
+            .map(|mut element| unsafe { element.as_mut() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<'a, H, F> DoubleEndedIterator for DstFooterElementsMut<'a, H, F> {
+    fn next_back(&mut self) -> Option<&'a mut F> {
+        self.cursor
+            .next_back()
+            .map(|mut element| unsafe { element.as_mut() })
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstFooterElementsMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstFooterElementsMut<'_, H, F> {}
+
+unsafe impl<H: Send, F: Send> Send for DstFooterElementsMut<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstFooterElementsMut<'_, H, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{
+        assert_no_leaks, counter_dst, next_random, panicking_footer, silent_panic, Aligned32,
+        DropCounter, LyingIter, Marker, OddHeader, PanicOnClone, SyncDropCounter,
+    };
+
+    use std::{
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        collections::{BTreeSet, HashSet},
+        hash::{BuildHasher, RandomState},
+        panic::{self, AssertUnwindSafe},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        thread,
+    };
+
+    #[test]
+    fn writing() {
+        let mut dst = MaybeUninitDst::<u32, u64>::new(2);
+
+        dst.write_header(2);
+
+        let header_ref = dst.get_header_ptr();
+
+        unsafe { assert!(*header_ref == 2) }
+
+        let footer = [1, 2];
+
+        dst.write_footer(&footer);
+
+        let footer_ref = dst.get_footer_ptr();
+
+        unsafe {
+            assert!((*footer_ref)[0] == 1);
+            assert!((*footer_ref)[1] == 2);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_footer_write() {
+        let mut dst = MaybeUninitDst::<u32, u64>::new(2);
+
+        let footer = [1, 2, 3];
+
+        dst.write_footer(&footer);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_element_write() {
+        let mut dst = MaybeUninitDst::<u32, u64>::new(2);
+
+        dst.write_footer_element(3, 1);
+    }
+
+    #[test]
+    fn element_write() {
+        let mut dst = MaybeUninitDst::<u32, u64>::new(2);
+
+        dst.write_footer_element(1, 1);
+
+        let footer_element_ref = dst.get_footer_element_ptr(1);
+
+        unsafe { assert!(*footer_element_ref == 1) }
+    }
+
+    #[test]
+    fn element_write2() {
+        let mut dst = MaybeUninitDst::<u32, u64>::new(3);
+
+        let footer = [1, 2, 3];
+
+        dst.write_footer(&footer);
+
+        dst.write_footer_element(1, 5);
+
+        let footer_ptr = dst.get_footer_ptr();
+
+        unsafe {
+            assert!((*footer_ptr)[0] == 1);
+            assert!((*footer_ptr)[1] == 5);
+            assert!((*footer_ptr)[2] == 3)
+        }
+    }
+
+    #[test]
+    fn assume_init() {
+        let mut dst = MaybeUninitDst::<u8, u64>::new(5);
+
+        dst.write_header(1);
+
+        let footer = [0, 1, 2, 3, 4];
+
+        dst.write_footer(&footer);
+
+        let dst = unsafe { dst.assume_init() };
+
+        assert!(dst.get_footer_len() == 5);
+
+        assert!(dst.get_footer_ref().eq(&footer));
+
+        assert!(*dst.get_header_ref() == 1);
+    }
+
+    #[test]
+    fn array() {
+        let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(2, 2);
+
+        let mut arr = [0, 1];
+
+        dst_arr.write_header(0, 0);
+        dst_arr.write_footer(0, &arr);
+
+        arr[1] = 5;
+
+        dst_arr.write_header(1, 1);
+        dst_arr.write_footer(1, &arr);
+
+        let dst_arr = unsafe { dst_arr.assume_init() };
+
+        assert!(*dst_arr.get_header_ref(0) == 0);
+        assert!(dst_arr.get_footer_ref(0)[0] == 0);
+        assert!(dst_arr.get_footer_ref(0)[1] == 1);
+
+        assert!(*dst_arr.get_header_ref(1) == 1);
+        assert!(dst_arr.get_footer_ref(1)[0] == 0);
+        assert!(dst_arr.get_footer_ref(1)[1] == 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn array_invalid() {
+        let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(2, 2);
+
+        dst_arr.write_header(2, 1);
+    }
+
+    #[test]
+    fn swap() {
+        let mut dst_arr1 = MaybeUninitDstArray::<u32, u8>::new(1, 1);
+
+        dst_arr1.write_header(0, 0);
+        dst_arr1.write_footer_element(0, 0, 0);
+
+        let mut dst_arr1 = unsafe { dst_arr1.assume_init() };
+
+        let mut dst_arr2 = MaybeUninitDstArray::<u32, u8>::new(1, 1);
+
+        dst_arr2.write_header(0, 1);
+        dst_arr2.write_footer_element(0, 0, 1);
+
+        let mut dst_arr2 = unsafe { dst_arr2.assume_init() };
+
+        let ptr1 = dst_arr1.ptr;
+        let ptr2 = dst_arr2.ptr;
+
+        assert!(*dst_arr1.get_header_ref(0) == 0);
+        assert!(*dst_arr2.get_header_ref(0) == 1);
+
+        dst_arr1.swap(&mut dst_arr2);
+
+        assert!(ptr::addr_eq(ptr1.as_ptr(), dst_arr2.ptr.as_ptr()));
+        assert!(ptr::addr_eq(ptr2.as_ptr(), dst_arr1.ptr.as_ptr()));
+
+        assert!(*dst_arr1.get_header_ref(0) == 1);
+        assert!(*dst_arr2.get_header_ref(0) == 0);
+    }
+
+    #[test]
+    fn dst_drops_once() {
+        let header_drops = Cell::new(0);
+        let footer_drops = Cell::new(0);
+
+        let mut dst = MaybeUninitDst::<DropCounter, DropCounter>::new(3);
+
+        dst.write_header(DropCounter(&header_drops));
+        for i in 0..3 {
+            dst.write_footer_element(i, DropCounter(&footer_drops));
+        }
+
+        let dst = unsafe { dst.assume_init() };
+
+        drop(dst);
+
+        assert!(header_drops.get() == 1);
+        assert!(footer_drops.get() == 3);
+    }
+
+    #[test]
+    fn dst_array_drops_once() {
+        let header_drops = Cell::new(0);
+        let footer_drops = Cell::new(0);
+
+        let mut dst_arr = MaybeUninitDstArray::<DropCounter, DropCounter>::new(2, 1);
+
+        dst_arr.write_header(0, DropCounter(&header_drops));
+        dst_arr.write_footer_element(0, 0, DropCounter(&footer_drops));
+        dst_arr.write_footer_element(0, 1, DropCounter(&footer_drops));
+
+        let dst_arr = unsafe { dst_arr.assume_init() };
+
+        drop(dst_arr);
+
+        assert!(header_drops.get() == 1);
+        assert!(footer_drops.get() == 2);
+    }
+
+    fn dst_array_drop_counts(array_size: usize) {
+        let header_drops = Cell::new(0);
+        let footer_drops = Cell::new(0);
+
+        let mut dst_arr = MaybeUninitDstArray::<DropCounter, DropCounter>::new(3, array_size);
+
+        for arr_index in 0..array_size {
+            dst_arr.write_header(arr_index, DropCounter(&header_drops));
+            for footer_index in 0..3 {
+                dst_arr.write_footer_element(arr_index, footer_index, DropCounter(&footer_drops));
+            }
+        }
+
+        let dst_arr = unsafe { dst_arr.assume_init() };
+
+        assert!(header_drops.get() == 0);
+        assert!(footer_drops.get() == 0);
+
+        drop(dst_arr);
+
+        assert!(header_drops.get() == array_size);
+        assert!(footer_drops.get() == array_size * 3);
+    }
+
+    #[test]
+    fn dst_array_drop_single() {
+        dst_array_drop_counts(1);
+    }
+
+    #[test]
+    fn dst_array_drop_pair() {
+        dst_array_drop_counts(2);
+    }
+
+    #[test]
+    fn dst_array_drop_many() {
+        dst_array_drop_counts(17);
+    }
+
+    #[test]
+    fn dst_array_drop_boxed() {
+        let mut dst_arr = MaybeUninitDstArray::<u32, Box<u32>>::new(2, 3);
+
+        for arr_index in 0..3 {
+            dst_arr.write_header(arr_index, arr_index as u32);
+            dst_arr.write_footer_element(arr_index, 0, Box::new(0));
+            dst_arr.write_footer_element(arr_index, 1, Box::new(1));
+        }
+
+        let dst_arr = unsafe { dst_arr.assume_init() };
+
+        assert!(*dst_arr.get_footer_ref(2)[1] == 1);
+    }
+
+    fn index_test_array() -> DstArray<u32, u8> {
+        let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(1, 2);
+
+        for arr_index in 0..2 {
+            dst_arr.write_header(arr_index, arr_index as u32);
+            dst_arr.write_footer_element(arr_index, 0, arr_index as u8);
+        }
+
+        unsafe { dst_arr.assume_init() }
+    }
+
+    #[test]
+    fn array_index_last() {
+        let mut dst_arr = index_test_array();
+
+        assert!(dst_arr[1].header == 1);
+        assert!(dst_arr[1].footer[0] == 1);
+
+        dst_arr[1].header = 5;
+
+        assert!(*dst_arr.get_header_ref(1) == 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 2 but the index is 2")]
+    fn array_index_at_len() {
+        let dst_arr = index_test_array();
+
+        let _ = &dst_arr[2];
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 2 but the index is 7")]
+    fn array_index_past_len() {
+        let dst_arr = index_test_array();
+
+        let _ = &dst_arr[7];
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 2 but the index is 2")]
+    fn array_index_mut_at_len() {
+        let mut dst_arr = index_test_array();
+
+        dst_arr[2].header = 0;
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 2 but the index is 7")]
+    fn array_index_mut_past_len() {
+        let mut dst_arr = index_test_array();
+
+        dst_arr[7].header = 0;
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 2 but the index is 2")]
+    fn slice_mut_index_at_len() {
+        let mut dst_arr = index_test_array();
+        let slice = dst_arr.get_mut_slice(0, 2);
+
+        let _ = &slice[2];
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 1 but the index is 4")]
+    fn slice_mut_index_past_len() {
+        let mut dst_arr = index_test_array();
+        let slice = dst_arr.get_mut_slice(1, 2);
+
+        let _ = &slice[4];
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 2 but the index is 2")]
+    fn slice_mut_index_mut_at_len() {
+        let mut dst_arr = index_test_array();
+        let mut slice = dst_arr.get_mut_slice(0, 2);
+
+        slice[2].header = 0;
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 1 but the index is 4")]
+    fn slice_mut_index_mut_past_len() {
+        let mut dst_arr = index_test_array();
+        let mut slice = dst_arr.get_mut_slice(1, 2);
+
+        slice[4].header = 0;
+    }
+
+    #[test]
+    fn uninit_drop_frees() {
+        let header_drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<DropCounter, u64>::new(1024);
+
+            dst.write_header(DropCounter(&header_drops));
+        });
+
+        //The header is leaked rather than dropped, since the Dst was never initialized
+        assert!(header_drops.get() == 0);
+    }
+
+    #[test]
+    fn uninit_assume_init_frees_once() {
+        let header_drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<DropCounter, u64>::new(2);
+
+            dst.write_header(DropCounter(&header_drops));
+            dst.write_footer(&[1, 2]);
+
+            let dst = unsafe { dst.assume_init() };
+
+            assert!(dst.get_footer_ref() == [1, 2]);
+        });
+
+        assert!(header_drops.get() == 1);
+    }
+
+    #[test]
+    fn uninit_panic_during_init_frees() {
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut dst = MaybeUninitDst::<u32, u64>::new(16);
+
+                dst.write_header(1);
+                dst.write_footer_element(0, 1);
+
+                silent_panic();
+            }));
+
+            assert!(result.is_err());
+        });
+    }
+
+    #[test]
+    fn uninit_raw_round_trip() {
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<u32, u64>::new(2);
+
+            dst.write_header(3);
+
+            let ptr = dst.into_raw();
+
+            let mut dst = unsafe { MaybeUninitDst::from_raw(ptr) };
+
+            dst.write_footer(&[4, 5]);
+
+            let dst = unsafe { dst.assume_init() };
+
+            assert!(*dst.get_header_ref() == 3);
+            assert!(dst.get_footer_ref() == [4, 5]);
+        });
+    }
+
+    #[test]
+    fn uninit_array_drop_frees() {
+        let header_drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut dst_arr = MaybeUninitDstArray::<DropCounter, u8>::new(4, 8);
+
+            dst_arr.write_header(0, DropCounter(&header_drops));
+            dst_arr.write_header(5, DropCounter(&header_drops));
+            dst_arr.write_footer(5, &[1, 2, 3, 4]);
+        });
+
+        assert!(header_drops.get() == 0);
+    }
+
+    #[test]
+    fn uninit_array_assume_init_frees_once() {
+        let header_drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut dst_arr = MaybeUninitDstArray::<DropCounter, u8>::new(1, 3);
+
+            for arr_index in 0..3 {
+                dst_arr.write_header(arr_index, DropCounter(&header_drops));
+                dst_arr.write_footer(arr_index, &[arr_index as u8]);
+            }
+
+            let dst_arr = unsafe { dst_arr.assume_init() };
+
+            assert!(dst_arr.get_footer_ref(2) == [2]);
+        });
+
+        assert!(header_drops.get() == 3);
+    }
+
+    #[test]
+    fn uninit_array_raw_round_trip() {
+        assert_no_leaks(|| {
+            let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(1, 2);
+
+            dst_arr.write_header(0, 7);
+            dst_arr.write_footer(0, &[7]);
+
+            let (ptr, array_size) = dst_arr.into_raw();
+
+            assert!(array_size == 2);
+
+            let mut dst_arr = unsafe { MaybeUninitDstArray::from_raw(ptr, array_size) };
+
+            dst_arr.write_header(1, 8);
+            dst_arr.write_footer(1, &[8]);
+
+            let dst_arr = unsafe { dst_arr.assume_init() };
+
+            assert!(*dst_arr.get_header_ref(0) == 7);
+            assert!(dst_arr.get_footer_ref(1) == [8]);
+        });
+    }
+
+    fn check_array_layout<H: Copy + PartialEq, F: Copy + PartialEq>(
+        count: usize,
+        array_size: usize,
+        header: impl Fn(usize) -> H,
+        footer: impl Fn(usize, usize) -> F,
+    ) {
+        let mut dst_arr = MaybeUninitDstArray::<H, F>::new(count, array_size);
+
+        for arr_index in 0..array_size {
+            dst_arr.write_header(arr_index, header(arr_index));
+            for footer_index in 0..count {
+                dst_arr.write_footer_element(
+                    arr_index,
+                    footer_index,
+                    footer(arr_index, footer_index),
+                );
+            }
+        }
+
+        let dst_arr = unsafe { dst_arr.assume_init() };
+
+        let element_layout = Layout::for_value(&dst_arr[0]);
+
+        assert!(dst_arr.get_stride() == element_layout.size());
+
+        for arr_index in 0..array_size {
+            let element = &dst_arr[arr_index];
+
+            let addr = (element as *const DstData<H, F>).addr();
+
+            assert!(addr.is_multiple_of(element_layout.align()));
+            assert!(element.header == header(arr_index));
+            for footer_index in 0..count {
+                assert!(element.footer[footer_index] == footer(arr_index, footer_index));
+            }
+        }
+    }
+
+    #[test]
+    fn array_layout_aligned_footer() {
+        check_array_layout(3, 5, |i| i as u8, |i, j| Aligned32((i * 3 + j) as u8));
+    }
+
+    #[test]
+    fn array_layout_odd_header() {
+        check_array_layout(3, 7, |i| OddHeader([i as u8; 3]), |i, j| (i * 3 + j) as u16);
+    }
+
+    #[test]
+    fn array_layout_odd_header_aligned_footer() {
+        check_array_layout(
+            2,
+            4,
+            |i| OddHeader([i as u8; 3]),
+            |i, j| Aligned32((i + j) as u8),
+        );
+    }
+
+    #[test]
+    fn array_layout_aligned_footer_odd_count() {
+        check_array_layout(1, 9, |i| i as u64, |i, j| Aligned32((i ^ j) as u8));
+    }
+
+    #[test]
+    #[should_panic(expected = "the size of the dst overflowed isize::MAX")]
+    fn array_overflowing_count() {
+        let _ = MaybeUninitDstArray::<u32, u64>::new(usize::MAX / 4, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "the size of the dst overflowed isize::MAX")]
+    fn array_overflowing_array_size() {
+        let _ = MaybeUninitDstArray::<u32, u64>::new(16, usize::MAX / 8);
+    }
+
+    #[test]
+    fn write_footer_cloned_strings() {
+        assert_no_leaks(|| {
+            let footer = [String::from("a"), String::from("bc")];
+
+            let mut dst = MaybeUninitDst::<u32, String>::new(2);
+
+            dst.write_header(0);
+            dst.write_footer_cloned(&footer);
+
+            let dst = unsafe { dst.assume_init() };
+
+            assert!(dst.get_footer_ref() == footer);
+        });
+    }
+
+    #[test]
+    fn write_footer_from_vec_moves() {
+        let footer_drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let footer = vec![DropCounter(&footer_drops), DropCounter(&footer_drops)];
+
+            let mut dst = MaybeUninitDst::<u32, DropCounter>::new(2);
+
+            dst.write_header(0);
+            dst.write_footer_from_vec(footer);
+
+            assert!(footer_drops.get() == 0);
+
+            let dst = unsafe { dst.assume_init() };
+
+            assert!(dst.get_footer_len() == 2);
+        });
+
+        assert!(footer_drops.get() == 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_footer_from_vec_wrong_len() {
+        let mut dst = MaybeUninitDst::<u32, String>::new(2);
+
+        dst.write_footer_from_vec(vec![String::new()]);
+    }
+
+    #[test]
+    fn array_write_footer_variants() {
+        let footer_drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let strings = [String::from("x"), String::from("y")];
+
+            let mut dst_arr = MaybeUninitDstArray::<u32, String>::new(2, 2);
+
+            dst_arr.write_header(0, 0);
+            dst_arr.write_footer_cloned(0, &strings);
+            dst_arr.write_header(1, 1);
+            dst_arr.write_footer_from_vec(1, vec![String::from("z"), String::from("w")]);
+
+            let dst_arr = unsafe { dst_arr.assume_init() };
+
+            assert!(dst_arr.get_footer_ref(0) == strings);
+            assert!(dst_arr.get_footer_ref(1) == ["z", "w"]);
+
+            let mut dst_arr = MaybeUninitDstArray::<u32, DropCounter>::new(1, 1);
+
+            dst_arr.write_header(0, 0);
+            dst_arr.write_footer_from_vec(0, vec![DropCounter(&footer_drops)]);
+
+            drop(unsafe { dst_arr.assume_init() });
+        });
+
+        assert!(footer_drops.get() == 1);
+    }
+
+    #[test]
+    fn chunked_init_across_threads() {
+        let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(2, 10);
+
+        for arr_index in 0..10 {
+            dst_arr.write_header(arr_index, 0);
+            dst_arr.write_footer(arr_index, &[0, 0]);
+        }
+
+        let mut dst_arr = unsafe { dst_arr.assume_init() };
+
+        let slice = dst_arr.get_mut_slice(0, 10);
+
+        std::thread::scope(|scope| {
+            for (chunk_index, mut chunk) in DstChunksMut::new(slice, 3).enumerate() {
+                scope.spawn(move || {
+                    for index in 0..chunk.len {
+                        let element = &mut chunk[index];
+
+                        element.header = (chunk_index * 3 + index) as u32;
+                        element.footer[1] = chunk_index as u8;
+                    }
+                });
+            }
+        });
+
+        for arr_index in 0..10 {
+            assert!(*dst_arr.get_header_ref(arr_index) == arr_index as u32);
+            assert!(dst_arr.get_footer_ref(arr_index)[1] == (arr_index / 3) as u8);
+        }
+    }
+
+    #[test]
+    fn uninit_pointer_flows() {
+        let mut dst = MaybeUninitDst::<u32, u16>::new(2);
+
+        unsafe {
+            dst.get_header_ptr_mut().write(9);
+            dst.get_footer_element_ptr_mut(0).write(1);
+            dst.get_footer_element_ptr_mut(1).write(2);
+        }
+
+        let read_only = &dst;
+
+        unsafe {
+            assert!(*read_only.get_header_ptr() == 9);
+            assert!(*read_only.get_footer_element_ptr(1) == 2);
+            assert!((*read_only.get_footer_ptr())[0] == 1);
+        }
+    }
+
+    #[test]
+    fn uninit_array_pointer_flows() {
+        let mut dst_arr = MaybeUninitDstArray::<u32, u16>::new(2, 2);
+
+        unsafe {
+            dst_arr.get_header_ptr_mut(1).write(9);
+            dst_arr.get_footer_element_ptr_mut(1, 0).write(1);
+            (*dst_arr.get_footer_ptr_mut(1))[1] = 2;
+        }
+
+        let read_only = &dst_arr;
+
+        unsafe {
+            assert!(*read_only.get_header_ptr(1) == 9);
+            assert!(*read_only.get_footer_element_ptr(1, 1) == 2);
+            assert!((*read_only.get_footer_ptr(1))[0] == 1);
+        }
+    }
+
+    #[test]
+    fn shared_slices_coexist() {
+        let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(1, 4);
+
+        for arr_index in 0..4 {
+            dst_arr.write_header(arr_index, arr_index as u32);
+            dst_arr.write_footer(arr_index, &[arr_index as u8]);
+        }
+
+        let dst_arr = unsafe { dst_arr.assume_init() };
+
+        let first = dst_arr.get_slice(0, 3);
+        let second = dst_arr.get_slice(2, 4);
+
+        assert!(first[2].header == second[0].header);
+        assert!(first[2].footer == second[0].footer);
+    }
+
+    #[test]
+    fn niche_optimized_handles() {
+        use std::mem::size_of;
+
+        assert!(size_of::<Option<Dst<u32, u8>>>() == size_of::<Dst<u32, u8>>());
+        assert!(size_of::<Option<DstArray<u32, u8>>>() == size_of::<DstArray<u32, u8>>());
+        assert!(
+            size_of::<Option<MaybeUninitDst<u32, u8>>>() == size_of::<MaybeUninitDst<u32, u8>>()
+        );
+        assert!(
+            size_of::<Option<MaybeUninitDstArray<u32, u8>>>()
+                == size_of::<MaybeUninitDstArray<u32, u8>>()
+        );
+    }
+
+    #[test]
+    fn covariant_handles() {
+        fn shorten_dst<'a>(dst: Dst<&'static str, &'static str>) -> Dst<&'a str, &'a str> {
+            dst
+        }
+
+        fn shorten_array<'a>(arr: DstArray<&'static str, u8>) -> DstArray<&'a str, u8> {
+            arr
+        }
+
+        let mut dst = MaybeUninitDst::<&'static str, &'static str>::new(1);
+
+        dst.write_header("header");
+        dst.write_footer(&["footer"]);
+
+        let dst = shorten_dst(unsafe { dst.assume_init() });
+
+        assert!(*dst.get_header_ref() == "header");
+        assert!(dst.get_footer_ref() == ["footer"]);
+
+        let mut dst_arr = MaybeUninitDstArray::<&'static str, u8>::new(0, 1);
+
+        dst_arr.write_header(0, "header");
+
+        let dst_arr = shorten_array(unsafe { dst_arr.assume_init() });
+
+        assert!(*dst_arr.get_header_ref(0) == "header");
+    }
+
+    #[test]
+    fn zst_header() {
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<(), u64>::new(3);
+
+            dst.write_header(());
+            dst.write_footer(&[1, 2, 3]);
+
+            let dst = unsafe { dst.assume_init() };
+
+            assert!((dst.get_header_ref() as *const ()).is_aligned());
+            assert!(dst.get_footer_ref() == [1, 2, 3]);
+            assert!(dst.get_footer_ref().as_ptr().addr() == dst.ptr.as_ptr().addr());
+        });
+    }
+
+    #[test]
+    fn zst_header_aligned_footer() {
+        let mut dst = MaybeUninitDst::<Marker, Aligned32>::new(2);
+
+        dst.write_header(Marker);
+        dst.write_footer(&[Aligned32(1), Aligned32(2)]);
+
+        let dst = unsafe { dst.assume_init() };
+
+        assert!(dst.get_footer_ref().as_ptr().addr() == dst.ptr.as_ptr().addr());
+        assert!(dst.get_footer_ref().as_ptr().is_aligned());
+        assert!(dst.get_footer_ref() == [Aligned32(1), Aligned32(2)]);
+    }
+
+    #[test]
+    fn zst_header_empty_footer() {
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<(), u64>::new(0);
+
+            dst.write_header(());
+            dst.write_footer(&[]);
+
+            let dst = unsafe { dst.assume_init() };
+
+            assert!((dst.get_header_ref() as *const ()).is_aligned());
+            assert!(dst.get_footer_ref().is_empty());
+        });
+    }
+
+    #[test]
+    fn zst_header_zst_footer() {
+        let footer_drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<Marker, Marker>::new(4);
+
+            dst.write_header(Marker);
+            dst.write_footer(&[Marker; 4]);
+
+            let dst = unsafe { dst.assume_init() };
+
+            assert!(*dst.get_header_ref() == Marker);
+            assert!(dst.get_footer_len() == 4);
+
+            let mut dst = MaybeUninitDst::<(), DropCounter>::new(0);
+
+            dst.write_header(());
+
+            drop(unsafe { dst.assume_init() });
+        });
+
+        assert!(footer_drops.get() == 0);
+    }
+
+    #[test]
+    fn zst_header_array() {
+        assert_no_leaks(|| {
+            let mut dst_arr = MaybeUninitDstArray::<(), u16>::new(2, 3);
+
+            for arr_index in 0..3 {
+                dst_arr.write_header(arr_index, ());
+                dst_arr.write_footer(arr_index, &[arr_index as u16, 7]);
+            }
+
+            let dst_arr = unsafe { dst_arr.assume_init() };
+
+            assert!(dst_arr.get_stride() == 4);
+            for arr_index in 0..3 {
+                assert!((dst_arr.get_header_ref(arr_index) as *const ()).is_aligned());
+                assert!(dst_arr.get_footer_ref(arr_index) == [arr_index as u16, 7]);
+            }
+        });
+    }
+
+    #[test]
+    fn zst_element_array() {
+        let header_drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut dst_arr = MaybeUninitDstArray::<Marker, Marker>::new(3, 4);
+
+            for arr_index in 0..4 {
+                dst_arr.write_header(arr_index, Marker);
+                dst_arr.write_footer(arr_index, &[Marker; 3]);
+            }
+
+            let dst_arr = unsafe { dst_arr.assume_init() };
+
+            assert!(dst_arr.get_stride() == 0);
+            assert!(dst_arr.get_footer_ref(3).len() == 3);
+
+            let mut dst_arr = MaybeUninitDstArray::<DropCounter, ()>::new(0, 2);
+
+            dst_arr.write_header(0, DropCounter(&header_drops));
+            dst_arr.write_header(1, DropCounter(&header_drops));
+
+            drop(unsafe { dst_arr.assume_init() });
+        });
+
+        assert!(header_drops.get() == 2);
+    }
+
+    #[test]
+    fn empty_footer() {
+        let header_drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<u32, u64>::new(0);
+
+            dst.write_header(4);
+            dst.write_footer(&[]);
+
+            assert!(dst.get_footer_len() == 0);
+
+            let mut dst = unsafe { dst.assume_init() };
+
+            assert!(*dst.get_header_ref() == 4);
+            assert!(dst.get_footer_ref().is_empty());
+            assert!(dst.get_footer_ref_mut().is_empty());
+            assert!(dst.get_footer_len() == 0);
+
+            let mut dst = MaybeUninitDst::<DropCounter, String>::new(0);
+
+            dst.write_header(DropCounter(&header_drops));
+            dst.write_footer_cloned(&[]);
+            dst.write_footer_from_vec(Vec::new());
+
+            drop(unsafe { dst.assume_init() });
+        });
+
+        assert!(header_drops.get() == 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_footer_element_write() {
+        let mut dst = MaybeUninitDst::<u32, u64>::new(0);
+
+        dst.write_footer_element(0, 1);
+    }
+
+    #[test]
+    fn array_empty_footers() {
+        assert_no_leaks(|| {
+            let mut dst_arr = MaybeUninitDstArray::<u32, u64>::new(0, 3);
+
+            for arr_index in 0..3 {
+                dst_arr.write_header(arr_index, arr_index as u32);
+                dst_arr.write_footer(arr_index, &[]);
+            }
+
+            let dst_arr = unsafe { dst_arr.assume_init() };
+
+            assert!(dst_arr.get_footer_len() == 0);
+            for arr_index in 0..3 {
+                assert!(*dst_arr.get_header_ref(arr_index) == arr_index as u32);
+                assert!(dst_arr.get_footer_ref(arr_index).is_empty());
+            }
+        });
+    }
+
+    fn empty_array() -> DstArray<DropCounter<'static>, u64> {
+        unsafe { MaybeUninitDstArray::new(4, 0).assume_init() }
+    }
+
+    #[test]
+    fn empty_array_construct_and_drop() {
+        assert_no_leaks(|| {
+            let dst_arr = empty_array();
+
+            assert!(dst_arr.is_empty());
+            assert!(dst_arr.get_footer_len() == 4);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 0 but the index is 0")]
+    fn empty_array_index() {
+        let dst_arr = empty_array();
+
+        let _ = &dst_arr[0];
+    }
+
+    #[test]
+    fn empty_array_slicing() {
+        let mut dst_arr = empty_array();
+
+        let slice = dst_arr.get_mut_slice(0, 0);
+
+        assert!(DstChunksMut::new(slice, 2).next().is_none());
+
+        let slice = dst_arr.get_slice(0, 0);
+
+        assert!(slice.len == 0);
+    }
+
+    #[test]
+    fn empty_sub_slices() {
+        let mut dst_arr = index_test_array();
+
+        assert!(!dst_arr.is_empty());
+        assert!(dst_arr.get_mut_slice(2, 2).len == 0);
+        assert!(dst_arr.get_slice(1, 1).len == 0);
+    }
+
+    #[test]
+    fn try_new_layout_overflow() {
+        assert!(matches!(
+            MaybeUninitDst::<u32, u64>::try_new(usize::MAX),
+            Err(DstError::LayoutOverflow)
+        ));
+        assert!(matches!(
+            Dst::<u32, u64>::try_new_uninit(isize::MAX as usize / 4),
+            Err(DstError::LayoutOverflow)
+        ));
+        assert!(matches!(
+            MaybeUninitDstArray::<u32, u64>::try_new(16, usize::MAX / 8),
+            Err(DstError::LayoutOverflow)
+        ));
+    }
+
+    #[test]
+    fn try_new_alloc_failure() {
+        let allocator = TestAllocator::default();
+        allocator.fail.set(true);
+
+        match MaybeUninitDst::<u32, u64, _>::try_new_in(4, &allocator) {
+            Err(DstError::AllocFailed(layout)) => {
+                assert!(layout == DstData::<u32, u64>::layout_of(4).unwrap())
+            }
+            _ => panic!("the allocator fails every allocation"),
+        }
+
+        assert!(matches!(
+            MaybeUninitDstArray::<u32, u64, _>::try_new_in(4, 2, &allocator),
+            Err(DstError::AllocFailed(_))
+        ));
+        assert!(allocator.allocations.get() == 0);
+    }
+
+    #[test]
+    fn try_new_success() {
+        assert_no_leaks(|| {
+            let mut dst = Dst::<u32, u64>::try_new_uninit(2).unwrap();
+
+            dst.write_header(1);
+            dst.write_footer(&[2, 3]);
+
+            let dst = unsafe { dst.assume_init() };
+
+            assert!(dst.get_footer_ref() == [2, 3]);
+
+            let dst_arr = MaybeUninitDstArray::<u32, u64>::try_new(2, 3).unwrap();
+
+            drop(dst_arr);
+        });
+    }
+
+    #[test]
+    fn dst_error_messages() {
+        let layout = Layout::new::<u64>();
+
+        assert!(DstError::LayoutOverflow.to_string().contains("overflowed"));
+        assert!(
+            DstError::AllocFailed(layout).to_string() == "failed to allocate 8 bytes aligned to 8"
+        );
+        assert!(DstError::BudgetTooSmall(16)
+            .to_string()
+            .contains("16 bytes"));
+
+        let error: Box<dyn Error> = Box::new(DstError::LayoutOverflow);
+
+        assert!(error.source().is_none());
+    }
+
+    #[test]
+    fn write_footer_cloned_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let footer = panicking_footer(5, 3, &clones, &drops);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut dst = MaybeUninitDst::<u32, PanicOnClone>::new(5);
+
+                dst.write_footer_cloned(&footer);
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(clones.get() == 3);
+        assert!(drops.get() == 3);
+
+        drop(footer);
+
+        assert!(drops.get() == 8);
+    }
+
+    #[test]
+    fn array_write_footer_cloned_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let footer = panicking_footer(4, 6, &clones, &drops);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut dst_arr = MaybeUninitDstArray::<u32, PanicOnClone>::new(4, 2);
+
+                dst_arr.write_footer_cloned(0, &footer);
+                dst_arr.write_footer_cloned(1, &footer);
+            }));
+
+            assert!(result.is_err());
+        });
+
+        //The first element's footer is complete, so it is leaked rather than dropped along with
+        //the uninitialized array, while the second element's partial footer is dropped
+        assert!(clones.get() == 6);
+        assert!(drops.get() == 2);
+    }
+
+    #[test]
+    fn write_footer_cloned_without_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let footer = panicking_footer(3, usize::MAX, &clones, &drops);
+
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<u32, PanicOnClone>::new(3);
+
+            dst.write_header(0);
+            dst.write_footer_cloned(&footer);
+
+            assert!(drops.get() == 0);
+
+            drop(unsafe { dst.assume_init() });
+        });
+
+        assert!(clones.get() == 3);
+        assert!(drops.get() == 3);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "footer elements at [(0, 1)]")]
+    fn assume_init_missing_footer_element() {
+        let mut dst = MaybeUninitDst::<u32, u16>::new(2);
+
+        dst.write_header(0);
+        dst.write_footer_element(0, 1);
+
+        let _ = unsafe { dst.assume_init() };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "missing headers at [1]")]
+    fn array_assume_init_missing_header() {
+        let mut dst_arr = MaybeUninitDstArray::<u32, u16>::new(1, 2);
+
+        dst_arr.write_header(0, 0);
+        dst_arr.write_footer(0, &[0]);
+        dst_arr.write_footer(1, &[1]);
+
+        let _ = unsafe { dst_arr.assume_init() };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn tracking_zero_sized_slots() {
+        let mut dst = MaybeUninitDst::<u8, ()>::new(usize::MAX);
+        dst.write_header(1);
+
+        let dst = unsafe { dst.assume_init() };
+        assert!(dst.get_footer_len() == usize::MAX);
+
+        //The footer elements of both members outnumber usize::MAX
+        let dst_arr = MaybeUninitDstArray::<(), ()>::new(usize::MAX, 2);
+        let dst_arr = unsafe { dst_arr.assume_init() };
+        assert!(dst_arr.len() == 2);
+
+        struct ZeroSizedDrop;
+
+        impl Drop for ZeroSizedDrop {
+            fn drop(&mut self) {}
+        }
+
+        let mut dst = MaybeUninitDst::<ZeroSizedDrop, ZeroSizedDrop>::new(2);
+        dst.write_header(ZeroSizedDrop);
+        dst.write_footer_from_vec(vec![ZeroSizedDrop, ZeroSizedDrop]);
+        let _ = unsafe { dst.assume_init() };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "missing headers at [2]")]
+    fn tracking_bits_past_first_word() {
+        let mut dst_arr = MaybeUninitDstArray::<u8, u8>::new(1, 70);
+
+        for arr_index in (0..70).filter(|&arr_index| arr_index != 2) {
+            dst_arr.write_header(arr_index, 0);
+        }
+        for arr_index in 0..70 {
+            dst_arr.write_footer(arr_index, &[0]);
+        }
+
+        let _ = unsafe { dst_arr.assume_init() };
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "overwrote the initialized header of member 0")]
+    fn overwriting_drop_header() {
+        let drops = Cell::new(0);
+        let mut dst = MaybeUninitDst::<DropCounter, u8>::new(0);
+
+        dst.write_header(DropCounter(&drops));
+        dst.write_header(DropCounter(&drops));
+    }
+
+    #[test]
+    fn overwriting_copy_parts() {
+        let mut dst = MaybeUninitDst::<u32, u16>::new(2);
+
+        dst.write_header(0);
+        dst.write_header(1);
+        dst.write_footer(&[1, 2]);
+        dst.write_footer_element(1, 3);
+
+        let dst = unsafe { dst.assume_init() };
+
+        assert!(*dst.get_header_ref() == 1);
+        assert!(dst.get_footer_ref() == [1, 3]);
+    }
+
+    #[test]
+    #[cfg(feature = "init-tracking")]
+    fn try_assume_init_reports_missing_parts() {
+        assert_no_leaks(|| {
+            let mut dst_arr = MaybeUninitDstArray::<u32, u16>::new(2, 2);
+
+            dst_arr.write_header(0, 0);
+            dst_arr.write_footer(0, &[0, 1]);
+            dst_arr.write_footer_element(1, 1, 2);
+
+            let Err(report) = dst_arr.try_assume_init() else {
+                panic!("the second member was assumed initialized");
+            };
+
+            assert!(report.missing_headers == [1]);
+            assert!(report.missing_footer_elements == [(1, 0)]);
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "init-tracking")]
+    fn try_assume_init_success() {
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<u32, u16>::new(2);
+
+            dst.write_header(0);
+            unsafe { dst.get_footer_ptr_mut().as_mut_ptr().write_bytes(0, 2) };
+
+            let dst = dst.try_assume_init().unwrap();
+
+            assert!(dst.get_footer_ref() == [0, 0]);
+        });
+    }
+
+    #[test]
+    fn element_mut_writes() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut dst_arr = MaybeUninitDstArray::<DropCounter, u16>::new(2, 2);
+
+            for arr_index in 0..2 {
+                let mut element = dst_arr.element_mut(arr_index);
+
+                assert!(element.get_footer_len() == 2);
+
+                element.write_header(DropCounter(&drops));
+                element.write_footer_element(0, arr_index as u16);
+                unsafe { element.get_footer_element_ptr_mut(1).write(5) };
+            }
+
+            //Dropping the writers must not free or drop anything
+            assert!(drops.get() == 0);
+
+            let dst_arr = unsafe { dst_arr.assume_init() };
+
+            assert!(dst_arr.get_footer_ref(1) == [1, 5]);
+        });
+
+        assert!(drops.get() == 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn element_mut_out_of_bounds() {
+        let mut dst_arr = MaybeUninitDstArray::<u32, u16>::new(1, 2);
+
+        dst_arr.element_mut(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 2 but the index is 2")]
+    fn footer_element_ptr_out_of_bounds() {
+        let dst = MaybeUninitDst::<u32, u16>::new(2);
+
+        let _ = dst.get_footer_element_ptr(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "index out of bounds: the len is 1 but the index is 1")]
+    fn array_footer_element_ptr_out_of_bounds() {
+        let dst_arr = MaybeUninitDstArray::<u32, u16>::new(1, 2);
+
+        let _ = dst_arr.get_footer_element_ptr(1, 1);
+    }
+
+    #[test]
+    fn new_matches_manual_construction() {
+        let mut manual = MaybeUninitDst::<u32, u16>::new(3);
+
+        manual.write_header(7);
+        manual.write_footer(&[1, 2, 3]);
+
+        let manual = unsafe { manual.assume_init() };
+        let dst = Dst::new(7, &[1u16, 2, 3]);
+
+        assert!(dst.get_header_ref() == manual.get_header_ref());
+        assert!(dst.get_footer_ref() == manual.get_footer_ref());
+    }
+
+    #[test]
+    fn new_empty_footer() {
+        assert_no_leaks(|| {
+            let dst = Dst::<String, String>::new(String::from("header"), &[]);
+
+            assert!(dst.get_header_ref() == "header");
+            assert!(dst.get_footer_len() == 0);
+        });
+    }
+
+    #[test]
+    fn new_drop_footer() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let footer = panicking_footer(3, usize::MAX, &clones, &drops);
+
+        assert_no_leaks(|| {
+            let dst = Dst::new(String::from("header"), &footer);
+
+            assert!(dst.get_footer_len() == 3);
+            assert!(drops.get() == 0);
+        });
+
+        assert!(clones.get() == 3);
+        assert!(drops.get() == 3);
+    }
+
+    #[test]
+    fn new_clone_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let footer = panicking_footer(4, 2, &clones, &drops);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                Dst::new(String::from("header"), &footer)
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(clones.get() == 2);
+        assert!(drops.get() == 2);
+    }
+
+    #[test]
+    fn from_vec_drops_once() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let footer = (0..4).map(|_| DropCounter(&drops)).collect::<Vec<_>>();
+
+            let dst = Dst::from_vec(DropCounter(&drops), footer);
+
+            assert!(dst.get_footer_len() == 4);
+            assert!(drops.get() == 0);
+        });
+
+        assert!(drops.get() == 5);
+    }
+
+    #[test]
+    fn from_vec_empty() {
+        assert_no_leaks(|| {
+            let dst = Dst::<u32, String>::from_vec(3, Vec::with_capacity(8));
+
+            assert!(*dst.get_header_ref() == 3);
+            assert!(dst.get_footer_len() == 0);
+        });
+    }
+
+    #[test]
+    fn new_with_prefix_sums() {
+        let values = [3u64, 1, 4, 1, 5];
+        let mut sum = 0;
+
+        let dst = Dst::new_with(values.len(), values.len(), |index| {
+            sum += values[index];
+            sum
+        });
+
+        assert!(*dst.get_header_ref() == 5);
+        assert!(dst.get_footer_ref() == [3, 4, 8, 9, 14]);
+    }
+
+    #[test]
+    fn new_with_panic() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                Dst::new_with(DropCounter(&drops), 5, |index| {
+                    if index == 3 {
+                        silent_panic();
+                    }
+
+                    DropCounter(&drops)
+                })
+            }));
+
+            assert!(result.is_err());
+        });
+
+        //The three written elements and the header
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn try_new_with_error() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let result = Dst::try_new_with(DropCounter(&drops), 5, |index| {
+                if index == 2 {
+                    Err(index)
+                } else {
+                    Ok(DropCounter(&drops))
+                }
+            });
+
+            assert!(matches!(result, Err(2)));
+        });
+
+        assert!(drops.get() == 3);
+    }
+
+    #[test]
+    fn try_new_with_success() {
+        let dst =
+            Dst::try_new_with((), 3, |index| u8::try_from(index * 100)).unwrap_or_else(|_| {
+                panic!("every element fits in a u8");
+            });
+
+        assert!(dst.get_footer_ref() == [0, 100, 200]);
+    }
+
+    #[test]
+    fn from_header_and_iter_zip() {
+        let dst = Dst::from_header_and_iter((), [1u8, 2, 3].into_iter().zip([4u16, 5, 6]));
+
+        assert!(dst.get_footer_ref() == [(1, 4), (2, 5), (3, 6)]);
+    }
+
+    #[test]
+    fn from_header_and_iter_too_few() {
+        let drops = Cell::new(0);
+
+        let iter = LyingIter {
+            remaining: 3,
+            error: 2,
+            drops: &drops,
+        };
+
+        //The panic message allocates, so leaks can't be checked here
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            Dst::from_header_and_iter(DropCounter(&drops), iter)
+        }));
+
+        assert!(result.is_err());
+
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn from_header_and_iter_too_many() {
+        let drops = Cell::new(0);
+
+        let iter = LyingIter {
+            remaining: 3,
+            error: -1,
+            drops: &drops,
+        };
+
+        //The panic message allocates, so leaks can't be checked here
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            Dst::from_header_and_iter(DropCounter(&drops), iter)
+        }));
+
+        assert!(result.is_err());
+
+        //The header, the two written elements and the extra item
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn from_header_and_iter_unchecked_leaves_extra_items() {
+        let mut iter = [1, 2, 3, 4].into_iter();
+
+        let dst = unsafe { Dst::from_header_and_iter_unchecked((), iter.by_ref().take(2)) };
+
+        assert!(dst.get_footer_ref() == [1, 2]);
+        assert!(iter.next() == Some(3));
+    }
+
+    #[test]
+    fn new_zeroed_is_zero() {
+        assert_no_leaks(|| {
+            let dst = unsafe { Dst::<(u32, u64), u16>::new_zeroed(100) };
+
+            assert!(*dst.get_header_ref() == (0, 0));
+            assert!(dst.get_footer_ref().iter().all(|&element| element == 0));
+
+            let empty = unsafe { Dst::<(), u8>::new_zeroed(0) };
+            assert!(empty.get_footer_len() == 0);
+        });
+    }
+
+    #[test]
+    fn zeroed_with_written_parts() {
+        let mut dst = MaybeUninitDst::<u32, u8>::new_zeroed(4);
+
+        dst.write_header(9);
+        dst.write_footer_element(2, 5);
+
+        let dst = unsafe { dst.assume_zero_valid() };
+
+        assert!(*dst.get_header_ref() == 9);
+        assert!(dst.get_footer_ref() == [0, 0, 5, 0]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "missing headers at [0]")]
+    fn zeroed_is_not_written() {
+        let dst = MaybeUninitDst::<u32, u8>::new_zeroed(0);
+
+        let _ = unsafe { dst.assume_init() };
+    }
+
+    #[test]
+    fn into_parts_moves_everything() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let (header, footer) = counter_dst(&drops).into_parts();
+
+            assert!(footer.len() == 3);
+            assert!(drops.get() == 0);
+
+            drop(header);
+            drop(footer);
+        });
+
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn into_header_drops_footer() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let header = counter_dst(&drops).into_header();
+
+            assert!(drops.get() == 3);
+
+            drop(header);
+        });
+
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn into_footer_vec_drops_header() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let footer = counter_dst(&drops).into_footer_vec();
+
+            assert!(drops.get() == 1);
+            assert!(footer.len() == 3);
+        });
+
+        assert!(drops.get() == 4);
+
+        let empty = Dst::<String, String>::new(String::from("header"), &[]);
+        assert!(empty.into_footer_vec().is_empty());
+    }
+
+    #[test]
+    fn box_round_trip() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let boxed = counter_dst(&drops).into_box();
+
+            assert!(boxed.get_footer().len() == 3);
+
+            let dst = Dst::from(boxed);
+
+            assert!(dst.get_footer_len() == 3);
+            assert!(drops.get() == 0);
+
+            drop(dst.into_box());
+        });
+
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn box_round_trip_odd_layouts() {
+        assert_no_leaks(|| {
+            let dst = Dst::new(OddHeader([1, 2, 3]), &[Aligned32(4)]);
+            let dst = Dst::from(dst.into_box());
+
+            assert!(dst.get_header_ref().0 == [1, 2, 3]);
+            assert!(dst.get_footer_ref()[0].0 == 4);
+
+            let zst = Dst::from(Dst::new(Marker, &[Marker; 3]).into_box());
+            assert!(zst.get_footer_len() == 3);
+        });
+    }
+
+    #[test]
+    fn raw_round_trip() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let ptr = counter_dst(&drops).into_raw();
+
+            assert!(ptr::metadata(ptr) == 3);
+            assert!(drops.get() == 0);
+
+            drop(unsafe { Dst::from_raw(ptr) });
+        });
+
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn raw_parts_round_trip() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let (ptr, len) = counter_dst(&drops).into_raw_parts();
+
+            assert!(len == 3);
+            assert!(drops.get() == 0);
+
+            let dst = unsafe { Dst::<DropCounter, DropCounter>::from_raw_parts(ptr, len) };
+            assert!(dst.get_footer_len() == 3);
+        });
+
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn leak_outlives_binding() {
+        let leaked: &'static mut DstData<u32, u8> = {
+            let dst = Dst::new(1, &[2, 3]);
+
+            dst.leak()
+        };
+
+        *leaked.get_header_mut() += 1;
+
+        assert!(*leaked.get_header() == 2);
+        assert!(leaked.get_footer() == [2, 3]);
+
+        //Reclaimed so the allocation isn't reported by Miri
+        drop(unsafe { Dst::from_raw(leaked) });
+    }
+
+    #[test]
+    fn filled_clones_all_but_last() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let element = panicking_footer(1, usize::MAX, &clones, &drops)
+            .pop()
+            .unwrap();
+
+        assert_no_leaks(|| {
+            let dst = Dst::filled((), element, 4);
+
+            assert!(dst.get_footer_len() == 4);
+            assert!(clones.get() == 3);
+            assert!(drops.get() == 0);
+        });
+
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn filled_empty() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let element = panicking_footer(1, usize::MAX, &clones, &drops)
+            .pop()
+            .unwrap();
+
+        assert_no_leaks(|| {
+            let dst = Dst::filled(String::from("header"), element, 0);
+
+            assert!(dst.get_footer_len() == 0);
+            assert!(drops.get() == 1);
+        });
+
+        assert!(clones.get() == 0);
+    }
+
+    #[test]
+    fn filled_clone_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let element = panicking_footer(1, 2, &clones, &drops).pop().unwrap();
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                Dst::filled(DropCounter(&drops), element, 5)
+            }));
+
+            assert!(result.is_err());
+        });
+
+        //The two clones, the original element and the header
+        assert!(drops.get() == 4);
+    }
+
+    thread_local! {
+        static DEFAULTS_UNTIL_PANIC: Cell<usize> = const { Cell::new(usize::MAX) };
+    }
+
+    ///Allocates on every default, and panics once `DEFAULTS_UNTIL_PANIC` runs out
+    struct CountedDefault(Box<u8>);
+
+    impl Default for CountedDefault {
+        fn default() -> Self {
+            DEFAULTS_UNTIL_PANIC.with(|remaining| {
+                if remaining.get() == 0 {
+                    silent_panic();
+                }
+                remaining.set(remaining.get() - 1);
+            });
+
+            CountedDefault(Box::new(0))
+        }
+    }
+
+    #[test]
+    fn from_default_values() {
+        let dst = Dst::<(u32, bool), CountedDefault>::from_default(3);
+
+        assert!(*dst.get_header_ref() == (0, false));
+        assert!(dst.get_footer_ref().iter().all(|element| *element.0 == 0));
+
+        let empty = Dst::<Vec<u8>, CountedDefault>::from_default(0);
+        assert!(empty.get_header_ref().is_empty());
+    }
+
+    #[test]
+    fn from_default_panic() {
+        assert_no_leaks(|| {
+            DEFAULTS_UNTIL_PANIC.with(|remaining| remaining.set(2));
+
+            let result =
+                panic::catch_unwind(|| Dst::<CountedDefault, CountedDefault>::from_default(4));
+
+            DEFAULTS_UNTIL_PANIC.with(|remaining| remaining.set(usize::MAX));
+
+            assert!(result.is_err());
+        });
+    }
+
+    ///Forwards to the global allocator, recording every allocation that hasn't been freed
+    #[derive(Default)]
+    struct TestAllocator {
+        live: RefCell<Vec<(usize, Layout)>>,
+        allocations: Cell<usize>,
+        fail: Cell<bool>,
+    }
+
+    unsafe impl Allocator for &TestAllocator {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            if self.fail.get() {
+                return Err(AllocError);
+            }
+
+            let ptr = Global.allocate(layout)?;
+
+            self.allocations.set(self.allocations.get() + 1);
+            self.live.borrow_mut().push((ptr.addr().get(), layout));
+
+            Ok(ptr)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            let mut live = self.live.borrow_mut();
+            let index = live
+                .iter()
+                .position(|&allocation| allocation == (ptr.addr().get(), layout))
+                .expect("freed memory that wasn't allocated with the same layout");
+            live.swap_remove(index);
+
+            unsafe { Global.deallocate(ptr, layout) };
+        }
+    }
+
+    #[test]
+    fn dst_in_allocator() {
+        let allocator = TestAllocator::default();
+        let drops = Cell::new(0);
+
+        {
+            let mut dst = Dst::new_uninit_in(2, &allocator);
+
+            dst.write_header(DropCounter(&drops));
+            dst.write_footer_from_vec(vec![String::from("a"), String::from("b")]);
+
+            let dst = unsafe { dst.assume_init() };
+
+            assert!(allocator.live.borrow().len() == 1);
+            assert!(dst.get_footer_ref() == ["a", "b"]);
+
+            let cloned = Dst::new_in(5u8, dst.get_footer_ref(), &allocator);
+            let zeroed = unsafe {
+                MaybeUninitDst::<u64, u8, _>::new_zeroed_in(3, &allocator).assume_zero_valid()
+            };
+
+            assert!(allocator.live.borrow().len() == 3);
+            assert!(cloned.get_footer_ref() == ["a", "b"]);
+            assert!(zeroed.get_footer_ref() == [0, 0, 0]);
+        }
+
+        assert!(allocator.allocations.get() == 3);
+        assert!(allocator.live.borrow().is_empty());
+        assert!(drops.get() == 1);
+    }
+
+    #[test]
+    fn dst_in_conversions() {
+        let allocator = TestAllocator::default();
+
+        let dst = Dst::from_vec_in('h', vec![1u32, 2], &allocator);
+        let dst = Dst::from(dst.into_box());
+
+        let (header, footer) = dst.into_parts();
+        assert!(header == 'h' && footer == [1, 2]);
+
+        let header = Dst::new_in(String::from("header"), &[0u8; 4], &allocator).into_header();
+        assert!(header == "header");
+
+        assert!(allocator.allocations.get() == 2);
+        assert!(allocator.live.borrow().is_empty());
+    }
+
+    #[test]
+    fn array_in_allocator() {
+        let allocator = TestAllocator::default();
+
+        {
+            let mut dst_arr = MaybeUninitDstArray::new_in(1, 3, &allocator);
+
+            for arr_index in 0..3 {
+                dst_arr.write_header(arr_index, arr_index as u16);
+                dst_arr.write_footer(arr_index, &[Aligned32(arr_index as u8)]);
+            }
+
+            let mut dst_arr = unsafe { dst_arr.assume_init() };
+            let mut other = unsafe {
+                let mut other = MaybeUninitDstArray::new_in(1, 1, &allocator);
+                other.write_header(0, 9);
+                other.write_footer(0, &[Aligned32(9)]);
+                other.assume_init()
+            };
+
+            dst_arr.swap(&mut other);
+
+            assert!(dst_arr.len() == 1 && other.len() == 3);
+            assert!(allocator.live.borrow().len() == 2);
+
+            //An uninitialized array is freed with the same layout too
+            drop(MaybeUninitDstArray::<u64, u8, _>::new_in(5, 2, &allocator));
+        }
+
+        assert!(allocator.allocations.get() == 3);
+        assert!(allocator.live.borrow().is_empty());
+    }
+
+    #[test]
+    fn try_new_in_alloc_failure() {
+        let allocator = TestAllocator {
+            fail: Cell::new(true),
+            ..TestAllocator::default()
+        };
+
+        assert!(matches!(
+            MaybeUninitDst::<u32, u64, _>::try_new_in(4, &allocator),
+            Err(DstError::AllocFailed(layout)) if layout.size() == 40
+        ));
+        assert!(matches!(
+            MaybeUninitDstArray::<u32, u64, _>::try_new_in(1, 2, &allocator),
+            Err(DstError::AllocFailed(layout)) if layout.size() == 32
+        ));
+
+        //Zero-sized Dsts never reach the allocator
+        assert!(Dst::<(), u8, _>::try_new_uninit_in(0, &allocator).is_ok());
+    }
+
+    #[test]
+    fn try_clone_alloc_failure() {
+        let allocator = TestAllocator::default();
+
+        {
+            let dst = Dst::new_in(String::from("header"), &[String::from("a")], &allocator);
+
+            allocator.fail.set(true);
+            assert!(matches!(
+                dst.try_clone(),
+                Err(DstError::AllocFailed(layout)) if Ok(layout) == DstData::<String, String>::layout_of(1)
+            ));
+
+            allocator.fail.set(false);
+            let clone = dst.try_clone().unwrap_or_else(|err| panic!("{err}"));
+
+            assert!(clone.get_footer_ref() == ["a"]);
+            assert!(allocator.live.borrow().len() == 2);
+        }
+
+        assert!(allocator.live.borrow().is_empty());
+    }
+
+    #[test]
+    fn capacity_for_matches_layout() {
+        fn check<H, F>() {
+            for total_bytes in 0..200 {
+                let count = DstData::<H, F>::capacity_for(total_bytes);
+                let fits = |count| {
+                    DstData::<H, F>::layout_of(count)
+                        .is_ok_and(|layout| layout.size() <= total_bytes)
+                };
+
+                if fits(0) {
+                    assert!(fits(count));
+                    assert!(!fits(count + 1));
+                } else {
+                    assert!(count == 0);
+                }
+            }
+        }
+
+        check::<u8, u64>();
+        check::<u64, u8>();
+        check::<OddHeader, u16>();
+        check::<Aligned32, u8>();
+        check::<u8, Aligned32>();
+        check::<(), OddHeader>();
+
+        assert!(DstData::<u8, ()>::capacity_for(1) == usize::MAX);
+        assert!(DstData::<u8, u8>::capacity_for(usize::MAX) == isize::MAX as usize - 1);
+    }
+
+    #[test]
+    fn new_fitting() {
+        assert_no_leaks(|| {
+            let dst = MaybeUninitDst::<u8, u32>::new_fitting(4096).unwrap();
+            assert!(dst.get_footer_len() == 1023);
+
+            let dst = MaybeUninitDst::<u8, u32>::new_fitting(4).unwrap();
+            assert!(dst.get_footer_len() == 0);
+        });
+
+        assert!(
+            MaybeUninitDst::<Aligned32, u8>::new_fitting(31).err()
+                == Some(DstError::BudgetTooSmall(32))
+        );
+        assert!(MaybeUninitDstArray::<u8, u32>::capacity_for(3, 4096) == 256);
+        assert!(MaybeUninitDstArray::<u8, u32>::capacity_for(3, 15) == 0);
+    }
+
+    #[test]
+    fn clone_dst() {
+        assert_no_leaks(|| {
+            let dst = Dst::from_vec(String::from("header"), vec![vec![1u8], vec![2, 3], vec![]]);
+            let mut clone = dst.clone();
+
+            clone.get_footer_ref_mut()[2].push(4);
+
+            assert!(clone.get_header_ref() == dst.get_header_ref());
+            assert!(dst.get_footer_ref() == [vec![1], vec![2, 3], vec![]]);
+            assert!(clone.get_footer_ref() == [vec![1], vec![2, 3], vec![4]]);
+
+            let bytes = Dst::new(7u16, &[1u8, 2, 3, 4, 5]).clone();
+            assert!(bytes.get_footer_ref() == [1, 2, 3, 4, 5]);
+
+            let empty = Dst::<u8, u8>::new(1, &[]).clone();
+            assert!(empty.get_footer_len() == 0);
+        });
+    }
+
+    #[test]
+    fn clone_dst_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let dst = Dst::from_vec(0u32, panicking_footer(5, 8, &clones, &drops));
+
+        assert_no_leaks(|| {
+            let clone = dst.clone();
+            assert!(clone.get_footer_len() == 5);
+        });
+
+        assert!(drops.get() == 5);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| dst.clone()));
+
+            assert!(result.is_err());
+        });
+
+        assert!(clones.get() == 8);
+        assert!(drops.get() == 8);
+    }
+
+    #[test]
+    fn array_from_fn() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let arr = DstArray::from_fn(2, 3, |arr_index| {
+                (
+                    arr_index,
+                    (0..2).map(move |footer_index| vec![arr_index; footer_index]),
+                )
+            });
+
+            assert!(arr.len() == 3);
+            assert!(arr.get_footer_ref(2) == [vec![], vec![2]]);
+
+            let empty = DstArray::<u8, u8>::from_fn(4, 0, |_| -> (u8, [u8; 4]) { unreachable!() });
+            assert!(empty.get_footer_len() == 4);
+
+            let arr = DstArray::from_fn(3, 2, |_| {
+                (DropCounter(&drops), [(); 3].map(|_| DropCounter(&drops)))
+            });
+            drop(arr);
+        });
+
+        assert!(drops.get() == 8);
+    }
+
+    #[test]
+    fn array_from_fn_panic() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                DstArray::from_fn(2, 4, |arr_index| {
+                    if arr_index == 2 {
+                        silent_panic();
+                    }
+
+                    (
+                        DropCounter(&drops),
+                        [DropCounter(&drops), DropCounter(&drops)],
+                    )
+                })
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(drops.get() == 6);
+
+        //A footer that panics partway drops its own elements and the header it came with
+        drops.set(0);
+        let drops = &drops;
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                DstArray::from_fn(3, 2, |arr_index| {
+                    (
+                        DropCounter(drops),
+                        (0..3).map(move |footer_index| {
+                            if arr_index == 1 && footer_index == 2 {
+                                silent_panic();
+                            }
+
+                            DropCounter(drops)
+                        }),
+                    )
+                })
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(drops.get() == 7);
+    }
+
+    #[test]
+    fn array_from_dsts() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let dsts = (0..3).map(|_| counter_dst(&drops)).collect();
+            let Ok(arr) = DstArray::from_dsts(dsts) else {
+                panic!("the footers have the same length");
+            };
+
+            assert!(arr.len() == 3);
+            assert!(arr.get_footer_len() == 3);
+            assert!(drops.get() == 0);
+
+            drop(arr);
+            assert!(drops.get() == 12);
+
+            let mut dsts: Vec<_> = (0..3).map(|_| counter_dst(&drops)).collect();
+            dsts.push(Dst::from_vec(
+                DropCounter(&drops),
+                vec![DropCounter(&drops)],
+            ));
+
+            let Err(err) = DstArray::from_dsts(dsts) else {
+                panic!("the last footer is shorter");
+            };
+
+            assert!(err.index == 3);
+            assert!(err.to_string() == "the footer of dst 3 has 1 elements but the first has 3");
+            assert!(drops.get() == 12);
+
+            drop(err);
+            assert!(drops.get() == 26);
+
+            let empty = DstArray::<u8, u8>::from_dsts(Vec::new());
+            assert!(empty.is_ok_and(|empty| empty.is_empty()));
+        });
+    }
+
+    #[test]
+    fn array_new_filled() {
+        assert_no_leaks(|| {
+            let template = Dst::new(
+                String::from("slot"),
+                &[String::from("a"), String::from("b")],
+            );
+            let mut arr = DstArray::new_filled(template.get_data(), 3);
+
+            arr.get_footer_ref_mut(1)[0].push('!');
+
+            assert!(arr.get_header_ref(2) == "slot");
+            assert!(arr.get_footer_ref(0) == ["a", "b"]);
+            assert!(arr.get_footer_ref(1) == ["a!", "b"]);
+
+            let bytes = DstArray::new_filled(Dst::new(1u8, &[2u64, 3]).get_data(), 5);
+            assert!((0..5).all(|arr_index| bytes.get_footer_ref(arr_index) == [2, 3]));
+        });
+    }
+
+    #[test]
+    fn array_new_filled_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let template = Dst::from_vec(0u8, panicking_footer(3, 7, &clones, &drops));
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                DstArray::new_filled(template.get_data(), 4);
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(clones.get() == 7);
+        assert!(drops.get() == 7);
+    }
+
+    #[test]
+    fn array_bulk_writes() {
+        //Miri is too slow for the full size
+        const LEN: usize = if cfg!(miri) { 100 } else { 10_000 };
+
+        assert_no_leaks(|| {
+            let headers: Vec<String> = (0..LEN).map(|arr_index| arr_index.to_string()).collect();
+            let footer = [1u16, 2, 3];
+
+            let mut bulk = MaybeUninitDstArray::<String, u16>::new(3, LEN);
+            bulk.write_headers(&headers);
+            bulk.write_footer_all(&footer);
+            let bulk = unsafe { bulk.assume_init() };
+
+            let mut looped = MaybeUninitDstArray::<String, u16>::new(3, LEN);
+            for (arr_index, header) in headers.iter().enumerate() {
+                looped.write_header(arr_index, header.clone());
+                looped.write_footer(arr_index, &footer);
+            }
+            let looped = unsafe { looped.assume_init() };
+
+            assert!((0..LEN).all(|arr_index| {
+                bulk.get_header_ref(arr_index) == looped.get_header_ref(arr_index)
+                    && bulk.get_footer_ref(arr_index) == looped.get_footer_ref(arr_index)
+            }));
+
+            let mut shared = MaybeUninitDstArray::<Vec<u8>, String>::new(1, 4);
+            shared.write_header_all(vec![1, 2]);
+            shared.write_footer_all(&[String::from("footer")]);
+            let shared = unsafe { shared.assume_init() };
+
+            assert!(shared.get_header_ref(3) == &[1, 2]);
+            assert!(shared.get_footer_ref(3) == ["footer"]);
+
+            let mut empty = MaybeUninitDstArray::<String, String>::new(1, 0);
+            empty.write_header_all(String::new());
+            empty.write_footer_all(&[String::new()]);
+            unsafe { empty.assume_init() };
+        });
+    }
+
+    #[test]
+    fn array_bulk_writes_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let header = panicking_footer(1, 2, &clones, &drops);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut arr = MaybeUninitDstArray::<PanicOnClone, u8>::new(0, 4);
+
+                arr.write_header_all(header[0].clone());
+            }));
+
+            assert!(result.is_err());
+        });
+
+        //The header passed in and the one clone written before the panic
+        assert!(clones.get() == 2);
+        assert!(drops.get() == 2);
+
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+        let footer = panicking_footer(2, 5, &clones, &drops);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut arr = MaybeUninitDstArray::<u8, PanicOnClone>::new(2, 4);
+
+                arr.write_footer_all(&footer);
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(clones.get() == 5);
+        assert!(drops.get() == 5);
+    }
+
+    #[test]
+    fn array_init_each_cleanup() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                MaybeUninitDstArray::new(1, 5).init_each(|arr_index| {
+                    if arr_index == 3 {
+                        silent_panic();
+                    }
+
+                    (DropCounter(&drops), [DropCounter(&drops)])
+                })
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(drops.get() == 6);
+
+        drops.set(0);
+        assert_no_leaks(|| {
+            let result = MaybeUninitDstArray::new(1, 5).try_init_each(|arr_index| {
+                if arr_index == 2 {
+                    return Err(arr_index);
+                }
+
+                Ok((DropCounter(&drops), [DropCounter(&drops)]))
+            });
+
+            assert!(matches!(result, Err(2)));
+        });
+
+        assert!(drops.get() == 4);
+
+        drops.set(0);
+        assert_no_leaks(|| {
+            let result = MaybeUninitDstArray::new(1, 3)
+                .try_init_each(|_| Ok::<_, ()>((DropCounter(&drops), [DropCounter(&drops)])));
+
+            assert!(result.is_ok_and(|arr| arr.len() == 3));
+        });
+
+        assert!(drops.get() == 6);
+    }
+
+    #[test]
+    fn array_init_each_wrong_footer_len() {
+        let drops = Cell::new(0);
+
+        for footer_len in [1, 3] {
+            drops.set(0);
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                MaybeUninitDstArray::new(2, 4).init_each(|arr_index| {
+                    let len = if arr_index == 2 { footer_len } else { 2 };
+
+                    (
+                        DropCounter(&drops),
+                        (0..len).map(|_| DropCounter(&drops)).collect::<Vec<_>>(),
+                    )
+                })
+            }));
+
+            assert!(result.is_err());
+            assert!(drops.get() == 7 + footer_len);
+        }
+    }
+
+    #[test]
+    fn write_footer_from_iter_errors() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::new(3);
+
+            let too_few = (0..2).map(|_| DropCounter(&drops));
+            assert!(dst.write_footer_from_iter(too_few) == Err(FillError::TooFew(2)));
+            assert!(drops.get() == 2);
+
+            let too_many = (0..5).map(|_| DropCounter(&drops));
+            assert!(dst.write_footer_from_iter(too_many) == Err(FillError::TooMany));
+            assert!(drops.get() == 6);
+
+            //The footer is unwritten again, so writing it is not an overwrite
+            assert!(dst
+                .write_footer_from_iter((0..3).map(|_| DropCounter(&drops)))
+                .is_ok());
+            dst.write_header(DropCounter(&drops));
+
+            drop(unsafe { dst.assume_init() });
+            assert!(drops.get() == 10);
+
+            let mut arr = MaybeUninitDstArray::<u8, String>::new(2, 2);
+            for arr_index in 0..2 {
+                arr.write_header(arr_index, 0);
+                assert!(arr
+                    .write_footer_from_iter(arr_index, ["a", "b"].map(String::from))
+                    .is_ok());
+            }
+
+            let arr = unsafe { arr.assume_init() };
+            assert!(arr.get_footer_ref(1) == ["a", "b"]);
+        });
+
+        assert!(FillError::TooFew(1).to_string().contains("after 1 items"));
+    }
+
+    #[test]
+    fn partial_footer_writes() {
+        assert_no_leaks(|| {
+            let mut dst = MaybeUninitDst::<u8, u32>::new(10);
+
+            dst.write_header(0);
+            dst.write_footer_slice_at(0, &[1, 2, 3]);
+            dst.write_footer_slice_at(2, &[4, 5]);
+            unsafe { dst.zero_footer_range(4..7) };
+            dst.fill_footer_range(7..10, 9);
+            dst.write_footer_slice_at(10, &[]);
+
+            let dst = unsafe { dst.assume_init() };
+            assert!(dst.get_footer_ref() == [1, 2, 4, 5, 0, 0, 0, 9, 9, 9]);
+
+            let mut dst = MaybeUninitDst::<(), String>::new(3);
+
+            dst.write_header(());
+            dst.fill_footer_range(0..2, String::from("a"));
+            dst.fill_footer_range(2..2, String::from("unused"));
+            dst.write_footer_element(2, String::from("b"));
+
+            let dst = unsafe { dst.assume_init() };
+            assert!(dst.get_footer_ref() == ["a", "a", "b"]);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn write_footer_slice_at_out_of_bounds() {
+        let mut dst = MaybeUninitDst::<u8, u8>::new(4);
+
+        dst.write_footer_slice_at(3, &[1, 2]);
+    }
+
+    #[test]
+    fn fill_footer_range_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        let value = panicking_footer(1, 2, &clones, &drops).remove(0);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                let mut dst = MaybeUninitDst::<u8, PanicOnClone>::new(4);
+
+                dst.fill_footer_range(0..4, value);
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(clones.get() == 2);
+        assert!(drops.get() == 3);
+    }
+
+    #[test]
+    fn dst_equality() {
+        let allocator = TestAllocator::default();
+
+        let a = Dst::new(String::from("header"), &[1, 2, 3]);
+        let b = Dst::new_in(String::from("header"), &[1, 2, 3], &allocator);
+
+        assert_eq!(a, b);
+        assert!(a == *b.get_data() && a == b.get_data());
+        assert!(*a.get_data() == b);
+        assert!(a == DstRef::from(b.get_data()));
+
+        assert!(a != Dst::new(String::from("header"), &[1, 2]));
+        assert!(a != Dst::new(String::from("other"), &[1, 2, 3]));
+        assert!(Dst::new((), &[0u8; 0]) == Dst::new((), &[]));
+
+        let nan = Dst::new(0u8, &[1.0, f32::NAN]);
+        assert!(nan != nan.clone());
+        assert!(Dst::new(0u8, &[1.0, -0.0]) == Dst::new(0u8, &[1.0, 0.0]));
+
+        assert!(format!("{a:?}") == r#"DstData { header: "header", footer: [1, 2, 3] }"#);
+    }
+
+    #[test]
+    fn dst_hash_set_dedup() {
+        let mut set = HashSet::new();
+
+        assert!(set.insert(Dst::new(1u32, b"abc")));
+        assert!(set.insert(Dst::new(1u32, b"ab")));
+        assert!(set.insert(Dst::new(2u32, b"abc")));
+        assert!(!set.insert(Dst::from_vec(1u32, b"abc".to_vec())));
+
+        assert!(set.len() == 3);
+        assert!(set.contains(&Dst::new(2u32, b"abc")));
+    }
+
+    #[test]
+    fn equal_dsts_hash_equally() {
+        let state = RandomState::new();
+
+        for len in 0..8 {
+            for header in 0..4u8 {
+                let footer: Vec<u16> = (0..len).map(|element| element * 3).collect();
+
+                let a = Dst::new(header, &footer);
+                let b = Dst::from_vec(header, footer);
+
+                assert!(a == b);
+                assert!(state.hash_one(&a) == state.hash_one(&b));
+                assert!(state.hash_one(&a) == state.hash_one(a.get_data()));
+            }
+        }
+    }
+
+    #[test]
+    fn dst_ordering_matches_tuples() {
+        let values: Vec<(u8, Vec<i16>)> = vec![
+            (1, vec![]),
+            (1, vec![0]),
+            (1, vec![0, 5]),
+            (1, vec![1]),
+            (0, vec![9, 9, 9]),
+            (2, vec![-1]),
+            (1, vec![0, -5]),
+        ];
+
+        for a in &values {
+            for b in &values {
+                let dst_a = Dst::new(a.0, &a.1);
+                let dst_b = Dst::new(b.0, &b.1);
+
+                assert!(dst_a.cmp(&dst_b) == a.cmp(b));
+                assert!(dst_a.get_data().partial_cmp(dst_b.get_data()) == a.partial_cmp(b));
+            }
+        }
+
+        let set: BTreeSet<_> = values.iter().map(|(h, f)| Dst::new(*h, f)).collect();
+        let mut sorted = values.clone();
+        sorted.sort();
+
+        assert!(set.iter().map(|dst| dst.clone().into_parts()).eq(sorted));
+
+        let nan = Dst::new(0u8, &[f64::NAN]);
+        assert!(nan.partial_cmp(&Dst::new(0u8, &[1.0])).is_none());
+        assert!(nan < Dst::new(1u8, &[1.0]));
+    }
+
+    #[test]
+    fn clone_dst_array() {
+        assert_no_leaks(|| {
+            let arr = DstArray::from_fn(2, 3, |arr_index| {
+                (arr_index.to_string(), [vec![arr_index], vec![]])
+            });
+            let mut clone = arr.clone();
+
+            clone.get_footer_ref_mut(0)[1].push(7);
+
+            assert!(clone.len() == 3);
+            assert!((1..3).all(|arr_index| clone[arr_index] == arr[arr_index]));
+            assert!(clone[0] != arr[0]);
+
+            let bytes = DstArray::new_filled(Dst::new(1u8, &[2u8, 3, 4]).get_data(), 100);
+            let bytes_clone = bytes.clone();
+            assert!((0..100).all(|arr_index| bytes_clone[arr_index] == bytes[arr_index]));
+
+            let empty = DstArray::<u8, u8>::new_filled(Dst::new(0, &[1]).get_data(), 0).clone();
+            assert!(empty.is_empty() && empty.get_footer_len() == 1);
+        });
+    }
+
+    #[test]
+    fn clone_dst_array_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        //Cloning the template into the array takes 8 clones, then 6 more succeed
+        let template = Dst::from_vec(0u8, panicking_footer(2, 14, &clones, &drops));
+        let arr = DstArray::new_filled(template.get_data(), 4);
+        drop(template);
+
+        assert!(clones.get() == 8);
+        drops.set(0);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| arr.clone()));
+
+            assert!(result.is_err());
+        });
+
+        assert!(clones.get() == 14);
+        assert!(drops.get() == 6);
+    }
+
+    #[test]
+    fn hash_set_probed_with_dst_data() {
+        let set: HashSet<_> = (0..4u32)
+            .map(|header| Dst::new(header, &[header; 2]))
+            .collect();
+        let arr = DstArray::from_fn(2, 6, |arr_index| (arr_index as u32, [arr_index as u32; 2]));
+
+        //Looking up a borrowed DstData doesn't need an owned Dst
+        assert!((0..4).all(|arr_index| set.contains(arr.get_arr_element(arr_index))));
+        assert!(!set.contains(arr.get_arr_element(5)));
+
+        let mut dst = Dst::new(0u8, &[1u8, 2]);
+
+        AsMut::<[u8]>::as_mut(&mut dst)[0] = 3;
+        assert!(AsRef::<[u8]>::as_ref(&dst) == [3, 2]);
+        assert!(AsRef::<DstData<u8, u8>>::as_ref(&dst).get_footer() == [3, 2]);
+
+        let data: &mut DstData<u8, u8> = dst.borrow_mut();
+        *data.get_header_mut() = 4;
+        assert!(*dst.get_header_ref() == 4);
+    }
+
+    #[test]
+    fn cow_dst_data() {
+        assert_no_leaks(|| {
+            let arr = DstArray::from_fn(2, 2, |arr_index| {
+                (arr_index, [String::from("a"), String::from("b")])
+            });
+
+            let mut cow = Cow::Borrowed(arr.get_arr_element(1));
+            cow.to_mut().get_footer_ref_mut()[0].push('!');
+
+            assert!(matches!(cow, Cow::Owned(_)));
+            assert!(cow.get_footer() == ["a!", "b"]);
+            assert!(arr.get_footer_ref(1) == ["a", "b"]);
+
+            let owned: Dst<_, _> = arr.get_arr_element(0).to_owned();
+            assert!(owned == arr.get_arr_element(0));
+        });
+    }
+
+    #[test]
+    fn send_and_sync_bounds() {
+        fn assert_send<T: Send>() {}
+        fn assert_sync<T: Sync>() {}
+
+        assert_send::<Dst<u64, u8>>();
+        assert_sync::<Dst<u64, u8>>();
+        assert_send::<Dst<Cell<u8>, String>>();
+        assert_send::<DstArray<Vec<u8>, u32>>();
+        assert_sync::<DstArray<Vec<u8>, u32>>();
+        assert_send::<DstSlice<'_, u8, u8>>();
+        assert_sync::<DstSlice<'_, u8, u8>>();
+
+        let arr = Arc::new(DstArray::from_fn(2, 4, |arr_index| {
+            (arr_index, [arr_index * 2, 1])
+        }));
+        let handles: Vec<_> = (0..4)
+            .map(|arr_index| {
+                let arr = Arc::clone(&arr);
+
+                thread::spawn(move || arr.get_footer_ref(arr_index).iter().sum::<usize>())
+            })
+            .collect();
+
+        let sums: Vec<_> = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        assert!(sums == [1, 3, 5, 7]);
+
+        let dst = Dst::new(0u64, &[1u8, 2]);
+        let footer = thread::spawn(move || dst.into_footer_vec()).join().unwrap();
+        assert!(footer == [1, 2]);
+    }
+
+    #[test]
+    fn footer_indexing() {
+        let mut dst = Dst::new('h', &[0u8, 1, 2, 3, 4]);
+        let data = dst.get_data();
+
+        assert!(data[4] == 4);
+        assert!(data[1..3] == [1, 2]);
+        assert!(data[3..] == [3, 4]);
+        assert!(data[..2] == [0, 1]);
+        assert!(data[..] == [0, 1, 2, 3, 4]);
+        assert!(data[1..=2] == [1, 2]);
+        assert!(data[..=0] == [0]);
+
+        assert!(data[2..2].is_empty());
+        assert!(data[5..].is_empty());
+        assert!(data[..0].is_empty());
+
+        dst[1..3].copy_from_slice(&[9, 9]);
+        dst.get_data_mut()[4] = 7;
+        assert!(dst[..] == [0, 9, 9, 3, 7]);
+
+        let empty = Dst::<char, u8>::new('h', &[]);
+        assert!(empty[..].is_empty() && empty[0..0].is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 3 but the index is 3")]
+    fn footer_index_out_of_bounds() {
+        let dst = Dst::new((), &[1, 2, 3]);
+
+        let _ = dst.get_data()[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 4 out of range for slice of length 3")]
+    fn footer_range_out_of_bounds() {
+        let dst = Dst::new((), &[1, 2, 3]);
+
+        let _ = &dst[1..4];
+    }
+
+    #[test]
+    fn range_slicing() {
+        let mut arr = DstArray::from_fn(1, 4, |index| (index, [index as u8]));
+
+        assert!(arr.slice(..).len == 4);
+        assert!(arr.slice(1..).len == 3);
+        assert!(arr.slice(..3).len == 3);
+        assert!(arr.slice(..=3).len == 4);
+        assert!(arr.slice(1..=1).len == 1);
+        assert!(*arr.slice(2..)[0].get_header() == 2);
+        assert!(*arr.slice(..=3)[3].get_header() == 3);
+
+        assert!(arr.slice(0..0).len == 0);
+        assert!(arr.slice(4..).len == 0);
+        assert!(arr.slice(4..4).len == 0);
+        assert!(arr.slice(..0).len == 0);
+        assert!(arr.slice((Bound::Excluded(0), Bound::Included(0))).len == 0);
+
+        let mut slice = arr.slice_mut(1..3);
+        *slice[1].get_header_mut() = 10;
+        assert!(slice.len == 2);
+        assert!(*arr.get_header_ref(2) == 10);
+        assert!(arr.slice_mut(..).len == 4);
+        assert!(arr.slice_mut(4..).len == 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range end index 5 out of range for slice of length 4")]
+    fn range_slicing_end_past_len() {
+        let arr = DstArray::from_fn(1, 4, |index| (index, [0u8]));
+
+        arr.slice(..=4);
+    }
+
+    #[test]
+    #[should_panic(expected = "range start index 5 out of range for slice of length 4")]
+    fn range_slicing_start_past_len() {
+        let mut arr = DstArray::from_fn(1, 4, |index| (index, [0u8]));
+
+        arr.slice_mut(5..);
+    }
+
+    #[test]
+    #[should_panic(expected = "slice index starts at 3 but ends at 2")]
+    fn range_slicing_start_after_end() {
+        let arr = DstArray::from_fn(1, 4, |index| (index, [0u8]));
+
+        #[allow(clippy::reversed_empty_ranges)]
+        arr.slice(3..2);
+    }
+
+    #[test]
+    fn array_iteration() {
+        let mut arr = DstArray::from_fn(2, 5, |index| (index, [index as u8, 0]));
+
+        let forward: Vec<usize> = arr.iter().map(|member| *member.get_header()).collect();
+        assert!(forward == [0, 1, 2, 3, 4]);
+
+        let reverse: Vec<usize> = arr
+            .iter()
+            .rev()
+            .map(|member| *member.get_header())
+            .collect();
+        assert!(reverse == [4, 3, 2, 1, 0]);
+
+        for (index, member) in (0..).zip(&arr) {
+            assert!(*member.get_header() == index);
+            assert!(member.get_footer()[0] == index as u8);
+        }
+
+        let mut iter = arr.iter();
+        assert!(iter.len() == 5);
+        assert!(*iter.next_back().unwrap().get_header() == 4);
+        assert!(*iter.next().unwrap().get_header() == 0);
+        assert!(iter.len() == 3);
+        iter.by_ref().for_each(drop);
+        assert!(iter.next().is_none() && iter.next_back().is_none());
+
+        for member in &mut arr {
+            member.get_footer_mut()[1] = *member.get_header() as u8 * 2;
+        }
+        for member in arr.iter_mut().rev().take(2) {
+            *member.get_header_mut() += 10;
+        }
+
+        let mut members: Vec<_> = arr.iter_mut().collect();
+        members.swap(0, 1);
+        *members[0].get_header_mut() += 100;
+        *members[1].get_header_mut() += 200;
+
+        assert!(*arr.get_header_ref(0) == 200 && *arr.get_header_ref(1) == 101);
+        assert!(*arr.get_header_ref(4) == 14 && *arr.get_header_ref(2) == 2);
+        assert!(arr.get_footer_ref(3) == [3, 6]);
+
+        assert!(empty_array().iter().next().is_none());
+        assert!(empty_array().iter_mut().next_back().is_none());
+    }
+
+    #[test]
+    fn array_into_iter() {
+        let drops = Cell::new(0);
+        let drops = &drops;
+        let counted = |index: usize| {
+            (
+                (index, DropCounter(drops)),
+                [DropCounter(drops), DropCounter(drops)],
+            )
+        };
+
+        let arr = DstArray::from_fn(2, 4, counted);
+        let dsts: Vec<_> = arr.into_iter().collect();
+        assert!(drops.get() == 0);
+        assert!(dsts.len() == 4);
+        assert!(dsts[3].get_header_ref().0 == 3);
+        drop(dsts);
+        assert!(drops.get() == 12);
+
+        drops.set(0);
+        let mut iter = DstArray::from_fn(2, 5, counted).into_iter();
+        let first = iter.next().unwrap();
+        let last = iter.next_back().unwrap();
+        assert!(first.get_header_ref().0 == 0 && last.get_header_ref().0 == 4);
+        assert!(iter.len() == 3);
+        drop(iter);
+        assert!(drops.get() == 9);
+        drop((first, last));
+        assert!(drops.get() == 15);
+
+        drops.set(0);
+        drop(DstArray::from_fn(2, 3, counted).into_iter());
+        assert!(drops.get() == 9);
+
+        let mut empty = empty_array().into_iter();
+        assert!(empty.len() == 0 && empty.next().is_none());
+    }
+
+    #[test]
+    fn chunks_mut_matches_slice_chunks() {
+        fn headers(chunk: DstSliceMut<'_, usize, u8>) -> Vec<usize> {
+            (0..chunk.len)
+                .map(|index| *chunk[index].get_header())
+                .collect()
+        }
+
+        for len in 0..12 {
+            let mut arr = DstArray::from_fn(1, len, |index| (index, [0u8]));
+            let mut expected: Vec<usize> = (0..len).collect();
+
+            for chunk_size in 1..14 {
+                let chunks = DstChunksMut::new(arr.slice_mut(..), chunk_size);
+                let slice_chunks = expected.chunks_mut(chunk_size);
+                assert!(chunks.len() == slice_chunks.len());
+                assert!(
+                    chunks.map(headers).collect::<Vec<_>>()
+                        == slice_chunks.map(|c| c.to_vec()).collect::<Vec<_>>()
+                );
+
+                let chunks = DstChunksMut::new(arr.slice_mut(..), chunk_size);
+                let slice_chunks = expected.chunks_mut(chunk_size);
+                assert!(
+                    chunks.rev().map(headers).collect::<Vec<_>>()
+                        == slice_chunks.rev().map(|c| c.to_vec()).collect::<Vec<_>>()
+                );
+
+                for n in 0..len + 2 {
+                    let mut chunks = DstChunksMut::new(arr.slice_mut(..), chunk_size);
+                    let mut slice_chunks = expected.chunks_mut(chunk_size);
+                    assert!(chunks.nth(n).map(headers) == slice_chunks.nth(n).map(|c| c.to_vec()));
+                    assert!(chunks.len() == slice_chunks.len());
+                    assert!(
+                        chunks.next_back().map(headers)
+                            == slice_chunks.next_back().map(|c| c.to_vec())
+                    );
+
+                    let mut chunks = DstChunksMut::new(arr.slice_mut(..), chunk_size);
+                    let mut slice_chunks = expected.chunks_mut(chunk_size);
+                    assert!(
+                        chunks.nth_back(n).map(headers)
+                            == slice_chunks.nth_back(n).map(|c| c.to_vec())
+                    );
+                    assert!(chunks.len() == slice_chunks.len());
+                    assert!(chunks.next().map(headers) == slice_chunks.next().map(|c| c.to_vec()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_mut_zero_size() {
+        let mut arr = DstArray::from_fn(1, 2, |index| (index, [0u8]));
+
+        DstChunksMut::new(arr.slice_mut(..), 0);
+    }
+
+    #[test]
+    fn slice_chunks_methods() {
+        let mut arr = DstArray::from_fn(1, 7, |index| (index, [0u8]));
+        let expected = (0..7).collect::<Vec<_>>();
+
+        let chunks = arr.as_slice().chunks(3);
+        assert!(chunks.len() == 3);
+        for (chunk, slice_chunk) in chunks.zip(expected.chunks(3)) {
+            assert!(chunk.iter().map(DstData::get_header).eq(slice_chunk));
+        }
+
+        for (chunk_index, mut chunk) in arr.as_mut_slice().chunks_mut(3).enumerate() {
+            for member in &mut chunk {
+                member.get_footer_mut()[0] = chunk_index as u8;
+            }
+        }
+        assert!((0..7).all(|index| arr.get_footer_ref(index) == [index as u8 / 3]));
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn slice_chunks_zero_size() {
+        let arr = DstArray::from_fn(1, 2, |index| (index, [0u8]));
+
+        let _ = arr.as_slice().chunks(0);
+    }
+
+    #[test]
+    fn pointer_identity() {
+        let dst = Dst::new(1u8, &[2u16, 3]);
+        let copy = dst.clone();
+
+        assert!(dst.ptr_eq(&dst) && !dst.ptr_eq(&copy));
+        assert!(dst == copy);
+        assert!(dst.ptr_eq_data(dst.get_data()) && !dst.ptr_eq_data(copy.get_data()));
+        assert!(format!("{dst:p}") == format!("{:p}", dst.as_ptr()));
+        assert!(format!("{dst:p}") != format!("{copy:p}"));
+
+        let arr = DstArray::from_fn(1, 2, |index| (index, [0u8]));
+        let arr_copy = arr.clone();
+
+        assert!(arr.ptr_eq(&arr) && !arr.ptr_eq(&arr_copy));
+        assert!(ptr::eq(arr.as_ptr(), &arr[0]));
+        assert!(format!("{arr:p}") == format!("{:p}", arr.as_ptr()));
+    }
+
+    #[test]
+    fn collect_parts() {
+        let arr: DstArray<char, u16> = "abc"
+            .chars()
+            .zip(0..)
+            .map(|(header, index)| (header, vec![index, index * 2]))
+            .collect();
+        assert!(arr.len() == 3 && arr.get_footer_len() == 2);
+        assert!(*arr.get_header_ref(2) == 'c' && arr.get_footer_ref(2) == [2, 4]);
+
+        let footers = [[1, 2, 3], [4, 5, 6]];
+        let arr: DstArray<usize, u8> = footers
+            .iter()
+            .enumerate()
+            .map(|(index, footer)| (index, &footer[..]))
+            .collect();
+        assert!(arr.get_footer_ref(1) == [4, 5, 6]);
+
+        let empty: DstArray<u8, u8> = std::iter::empty::<(u8, Vec<u8>)>().collect();
+        assert!(empty.is_empty() && empty.get_footer_len() == 0);
+
+        let single: DstArray<u8, String> = [(1, vec!["x".to_owned()])].into_iter().collect();
+        assert!(single.len() == 1 && single.get_footer_ref(0) == ["x"]);
+
+        let single_empty: DstArray<u8, u8> = [(1, &[][..])].into_iter().collect();
+        assert!(single_empty.len() == 1 && single_empty.get_footer_len() == 0);
+    }
+
+    #[test]
+    fn collect_parts_mismatch() {
+        let mut rest_consumed = false;
+        let parts = [(0, vec![1]), (1, vec![2]), (2, vec![]), (3, vec![4])]
+            .into_iter()
+            .inspect(|(header, _)| rest_consumed |= *header == 3);
+
+        let Err(err) = DstArray::<u8, u8>::try_from_parts(parts) else {
+            panic!("the third footer is empty");
+        };
+
+        assert!((err.index, err.expected, err.found) == (2, 1, 0));
+        assert!(err.parts.len() == 3 && err.parts[1].1 == [2]);
+        assert!(!rest_consumed);
+
+        let panicked = panic::catch_unwind(|| {
+            let _: DstArray<u8, u8> = [(0, &[1][..]), (1, &[2, 3][..])].into_iter().collect();
+        });
+        let Err(payload) = panicked else {
+            panic!("collecting mismatched footers should panic");
+        };
+        assert!(
+            payload.downcast_ref::<String>().map(String::as_str)
+                == Some("the footer of part 1 has 2 elements but the first has 1")
+        );
+    }
+
+    #[test]
+    fn shared_slices_across_threads() {
+        let mut arr = DstArray::from_fn(4, 10, |index| (index as u64, [index as u64; 4]));
+        let slice = arr.as_slice();
+
+        let sums: Vec<u64> = thread::scope(|scope| {
+            let workers: Vec<_> = DstChunks::new(slice, 3)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        (0..chunk.len)
+                            .map(|index| chunk[index].get_footer().iter().sum::<u64>())
+                            .sum()
+                    })
+                })
+                .collect();
+            let whole = scope.spawn(move || (0..slice.len).map(|index| slice[index].header).sum());
+
+            let mut sums: Vec<u64> = workers.into_iter().map(|w| w.join().unwrap()).collect();
+            sums.push(whole.join().unwrap());
+            sums
+        });
+
+        assert!(sums == [12, 48, 84, 36, 45]);
+
+        let chunks = DstChunks::new(arr.slice(1..), 4);
+        assert!(chunks.len() == 3);
+        assert!(
+            chunks
+                .clone()
+                .rev()
+                .map(|chunk| chunk.len)
+                .collect::<Vec<_>>()
+                == [1, 4, 4]
+        );
+        assert!(DstChunks::new(arr.slice(..0), 4).next().is_none());
+
+        let mut slice_mut = arr.slice_mut(2..5);
+        let shared = slice_mut.as_shared();
+        assert!(shared.len == 3 && shared[0].header == 2);
+        slice_mut[0].header = 20;
+        assert!(arr.as_slice()[2].header == 20);
+    }
+
+    #[test]
+    fn slice_accessors() {
+        let mut arr = DstArray::from_fn(3, 4, |index| (index, [index as u8; 3]));
+
+        let slice = arr.slice(1..3);
+        assert!(slice.len() == 2 && !slice.is_empty() && slice.footer_len() == 3);
+        assert!(slice.get(1).map(DstData::get_header) == Some(&2));
+        assert!(slice.get(2).is_none());
+        assert!(*slice.first().unwrap().get_header() == 1);
+        assert!(*slice.last().unwrap().get_header() == 2);
+
+        let mut slice = arr.slice_mut(2..);
+        assert!(slice.len() == 2 && slice.footer_len() == 3);
+        slice.first_mut().unwrap().get_footer_mut()[0] = 20;
+        slice.last_mut().unwrap().get_footer_mut()[0] = 30;
+        *slice.get_mut(1).unwrap().get_header_mut() = 31;
+        assert!(slice.get_mut(2).is_none());
+        assert!(slice.get(0).unwrap().get_footer() == [20, 2, 2]);
+        assert!(arr.get_footer_ref(3) == [30, 3, 3] && *arr.get_header_ref(3) == 31);
+
+        for start in [0, 2, 4] {
+            let slice = arr.slice(start..start);
+            assert!(slice.is_empty() && slice.footer_len() == 3);
+            assert!(slice.get(0).is_none() && slice.first().is_none() && slice.last().is_none());
+
+            let mut slice = arr.slice_mut(start..start);
+            assert!(slice.is_empty() && slice.footer_len() == 3);
+            assert!(slice.get(0).is_none() && slice.first().is_none() && slice.last().is_none());
+            assert!(slice.get_mut(0).is_none());
+            assert!(slice.first_mut().is_none() && slice.last_mut().is_none());
+        }
+
+        let mut empty = empty_array();
+        assert!(empty.as_slice().footer_len() == 4 && empty.as_slice().last().is_none());
+        assert!(empty.as_mut_slice().last_mut().is_none());
+    }
+
+    #[test]
+    fn split_slices() {
+        let mut arr = DstArray::from_fn(2, 6, |index| (index, [0u32; 2]));
+
+        let (mut front, mut back) = arr.split_at_mut(2);
+        assert!(front.len() == 2 && back.len() == 4);
+        thread::scope(|scope| {
+            scope.spawn(|| (0..front.len()).for_each(|index| front[index].footer[0] = 1));
+            scope.spawn(|| (0..back.len()).for_each(|index| back[index].footer[1] = 2));
+        });
+        for index in 0..6 {
+            let expected = if index < 2 { [1, 0] } else { [0, 2] };
+            assert!(arr.get_footer_ref(index) == expected);
+        }
+
+        let (front, back) = arr.split_at_mut(0);
+        assert!(front.is_empty() && back.len() == 6);
+        let (front, back) = arr.split_at_mut(6);
+        assert!(front.len() == 6 && back.is_empty() && back.footer_len() == 2);
+
+        let (front, back) = arr.as_mut_slice().split_at_mut(3);
+        let (middle, last) = back.split_at_mut(2);
+        assert!(front.len() == 3 && middle.len() == 2 && last.len() == 1);
+        assert!(*last[0].get_header() == 5 && *middle[0].get_header() == 3);
+
+        let (front, back) = arr.split_at(4);
+        assert!(*front.last().unwrap().get_header() == 3 && *back[0].get_header() == 4);
+        let (front, back) = unsafe { back.split_at_unchecked(2) };
+        assert!(front.len() == 2 && back.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "mid 7 out of range for slice of length 6")]
+    fn split_slice_past_len() {
+        let mut arr = DstArray::from_fn(2, 6, |index| (index, [0u32; 2]));
+
+        arr.split_at_mut(7);
+    }
+
+    #[test]
+    fn slice_iteration() {
+        let mut arr = DstArray::from_fn(3, 7, |index| (index, [index as u16; 3]));
+
+        let slice = arr.slice(2..6);
+        let headers: Vec<usize> = slice.iter().map(|member| *member.get_header()).collect();
+        assert!(headers == [2, 3, 4, 5]);
+        assert!(slice.iter().len() == 4);
+        assert!(*slice.into_iter().next_back().unwrap().get_header() == 5);
+        for (index, member) in (2..).zip(&slice) {
+            assert!(member.get_footer() == [index as u16; 3]);
+        }
+
+        let leaked = {
+            let slice = arr.slice(..);
+            slice.iter()
+        };
+        assert!(leaked.rev().map(|member| member.header).eq((0..7).rev()));
+
+        let mut slice = arr.slice_mut(1..);
+        for member in &mut slice {
+            member.footer[0] += 100;
+        }
+        assert!((&slice).into_iter().all(|member| member.footer[0] >= 100));
+        assert!(slice.iter_mut().rev().len() == 6);
+
+        let members: Vec<&mut DstData<usize, u16>> = arr.slice_mut(..).into_iter().collect();
+        assert!(members.len() == 7);
+
+        for (chunk_index, chunk) in DstChunksMut::new(arr.as_mut_slice(), 3).enumerate() {
+            for (index, member) in chunk.into_iter().enumerate().rev() {
+                member.footer[2] = (chunk_index * 10 + index) as u16;
+            }
+        }
+        for chunk in DstChunks::new(arr.as_slice(), 2) {
+            for member in chunk {
+                let index = member.header;
+                assert!(member.footer[2] == (index / 3 * 10 + index % 3) as u16);
+            }
+        }
+
+        assert!(arr.slice(3..3).iter().next().is_none());
+        assert!(arr.slice_mut(7..).into_iter().next_back().is_none());
+    }
+
+    #[test]
+    fn chunks_exact_matches_slice_chunks_exact() {
+        fn headers<'a>(chunk: impl IntoIterator<Item = &'a DstData<usize, u8>>) -> Vec<usize> {
+            chunk.into_iter().map(|member| member.header).collect()
+        }
+
+        for len in 0..12 {
+            let mut arr = DstArray::from_fn(1, len, |index| (index, [0u8]));
+            let mut expected: Vec<usize> = (0..len).collect();
+
+            for chunk_size in 1..14 {
+                let mut as_slice = arr.as_mut_slice();
+                let chunks = as_slice.chunks_exact_mut(chunk_size);
+                let slice_chunks = expected.chunks_exact_mut(chunk_size);
+                assert!(chunks.len() == slice_chunks.len());
+                assert!(headers(chunks.remainder())
+                    .into_iter()
+                    .eq(len - len % chunk_size..len));
+                assert!(
+                    chunks.rev().map(|c| headers(&c)).collect::<Vec<_>>()
+                        == slice_chunks.rev().map(|c| c.to_vec()).collect::<Vec<_>>()
+                );
+
+                for n in 0..len + 2 {
+                    let mut chunks = as_slice.chunks_exact_mut(chunk_size);
+                    let mut slice_chunks = expected.chunks_exact_mut(chunk_size);
+                    assert!(
+                        chunks.nth(n).map(|c| headers(&c))
+                            == slice_chunks.nth(n).map(|c| c.to_vec())
+                    );
+                    assert!(chunks.len() == slice_chunks.len());
+                    assert!(
+                        chunks.nth_back(0).map(|c| headers(&c))
+                            == slice_chunks.nth_back(0).map(|c| c.to_vec())
+                    );
+                    assert!(headers(&chunks.into_remainder()) == slice_chunks.into_remainder());
+                }
+
+                let shared = arr.as_slice();
+                let chunks = shared.chunks_exact(chunk_size);
+                let slice_chunks = expected.chunks_exact(chunk_size);
+                assert!(chunks.len() == slice_chunks.len());
+                assert!(headers(chunks.remainder()) == slice_chunks.remainder());
+                assert!(
+                    chunks.map(headers).collect::<Vec<_>>()
+                        == slice_chunks.map(<[usize]>::to_vec).collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn chunks_exact_zero_size() {
+        let arr = DstArray::from_fn(1, 2, |index| (index, [0u8]));
+
+        arr.as_slice().chunks_exact(0);
+    }
+
+    #[test]
+    fn rchunks_matches_slice_rchunks() {
+        fn headers<'a>(chunk: impl IntoIterator<Item = &'a DstData<usize, u8>>) -> Vec<usize> {
+            chunk.into_iter().map(|member| member.header).collect()
+        }
+
+        for len in 0..12 {
+            let mut arr = DstArray::from_fn(1, len, |index| (index, [0u8]));
+            let mut expected: Vec<usize> = (0..len).collect();
+
+            for chunk_size in 1..14 {
+                let mut as_slice = arr.as_mut_slice();
+                let chunks = as_slice.rchunks_mut(chunk_size);
+                let slice_chunks = expected.rchunks_mut(chunk_size);
+                assert!(chunks.len() == slice_chunks.len());
+                assert!(
+                    chunks.map(|c| headers(&c)).collect::<Vec<_>>()
+                        == slice_chunks.map(|c| c.to_vec()).collect::<Vec<_>>()
+                );
+
+                for n in 0..len + 2 {
+                    let mut chunks = as_slice.rchunks_mut(chunk_size);
+                    let mut slice_chunks = expected.rchunks_mut(chunk_size);
+                    assert!(
+                        chunks.nth(n).map(|c| headers(&c))
+                            == slice_chunks.nth(n).map(|c| c.to_vec())
+                    );
+                    assert!(chunks.len() == slice_chunks.len());
+                    assert!(
+                        chunks.next_back().map(|c| headers(&c))
+                            == slice_chunks.next_back().map(|c| c.to_vec())
+                    );
+
+                    let mut chunks = as_slice.rchunks_mut(chunk_size);
+                    let mut slice_chunks = expected.rchunks_mut(chunk_size);
+                    assert!(
+                        chunks.nth_back(n).map(|c| headers(&c))
+                            == slice_chunks.nth_back(n).map(|c| c.to_vec())
+                    );
+                    assert!(chunks.len() == slice_chunks.len());
+                    assert!(
+                        chunks.next().map(|c| headers(&c))
+                            == slice_chunks.next().map(|c| c.to_vec())
+                    );
+                }
+
+                let shared = arr.as_slice();
+                let slice_chunks = expected.rchunks(chunk_size);
+                assert!(
+                    shared
+                        .rchunks(chunk_size)
+                        .rev()
+                        .map(headers)
+                        .collect::<Vec<_>>()
+                        == slice_chunks
+                            .rev()
+                            .map(<[usize]>::to_vec)
+                            .collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "chunk size must be non-zero")]
+    fn rchunks_zero_size() {
+        let mut arr = DstArray::from_fn(1, 2, |index| (index, [0u8]));
+
+        arr.as_mut_slice().rchunks_mut(0);
+    }
+
+    #[test]
+    fn windows_match_model() {
+        type Model = Vec<(usize, Vec<u16>)>;
+
+        fn parts(window: DstSlice<'_, usize, u16>) -> Model {
+            window
+                .iter()
+                .map(|member| (member.header, member.footer.to_vec()))
+                .collect()
+        }
+
+        for len in 0..8 {
+            let model: Model = (0..len)
+                .map(|index| (index, vec![index as u16; 2]))
+                .collect();
+            let arr: DstArray<usize, u16> = model.iter().cloned().collect();
+            let slice = arr.as_slice();
+
+            for size in 1..10 {
+                let windows = slice.windows(size);
+                assert!(windows.len() == model.windows(size).len());
+                assert!(windows
+                    .map(parts)
+                    .eq(model.windows(size).map(<[_]>::to_vec)));
+                assert!(slice
+                    .windows(size)
+                    .rev()
+                    .map(parts)
+                    .eq(model.windows(size).rev().map(<[_]>::to_vec)));
+
+                for n in 0..len + 2 {
+                    let mut windows = slice.windows(size);
+                    let mut model_windows = model.windows(size);
+                    assert!(windows.nth(n).map(parts) == model_windows.nth(n).map(<[_]>::to_vec));
+                    assert!(
+                        windows.nth_back(n / 2).map(parts)
+                            == model_windows.nth_back(n / 2).map(<[_]>::to_vec)
+                    );
+                    assert!(windows.len() == model_windows.len());
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn windows_zero_size() {
+        let arr = DstArray::from_fn(1, 2, |index| (index, [0u8]));
+
+        arr.as_slice().windows(0);
+    }
+
+    #[test]
+    fn slice_swap() {
+        let drops = Cell::new(0);
+        let drops = &drops;
+        let mut arr = DstArray::from_fn(3, 5, |index| {
+            (
+                (index, DropCounter(drops)),
+                [
+                    (index as u8, DropCounter(drops)),
+                    (0, DropCounter(drops)),
+                    (0, DropCounter(drops)),
+                ],
+            )
+        });
+
+        let mut slice = arr.as_mut_slice();
+        slice.swap(0, 4);
+        slice.swap(1, 3);
+        slice.swap(2, 2);
+        unsafe { slice.swap_unchecked(3, 0) };
+        assert!(drops.get() == 0);
+
+        let order: Vec<usize> = arr.iter().map(|member| member.header.0).collect();
+        assert!(order == [1, 3, 2, 4, 0]);
+        for member in &arr {
+            assert!(member.footer[0].0 as usize == member.header.0);
+        }
+
+        drop(arr);
+        assert!(drops.get() == 20);
+
+        let mut padded = DstArray::from_fn(1, 2, |index| (index as u8, [index as u32 + 10]));
+        padded.as_mut_slice().swap(0, 1);
+        assert!(padded[0] == Dst::new(1u8, &[11u32]) && padded[1] == Dst::new(0u8, &[10u32]));
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 2 but the index is 2")]
+    fn slice_swap_out_of_bounds() {
+        let mut arr = DstArray::from_fn(1, 2, |index| (index, [0u8]));
+
+        arr.as_mut_slice().swap(0, 2);
+    }
+
+    #[test]
+    fn reverse_slices() {
+        let drops = Cell::new(0);
+        let drops = &drops;
+
+        for len in 0..6 {
+            let mut arr = DstArray::from_fn(2, len, |index| {
+                (
+                    (index, DropCounter(drops)),
+                    [index as u32, index as u32 * 2],
+                )
+            });
+
+            arr.reverse();
+            let order: Vec<usize> = arr.iter().map(|member| member.header.0).collect();
+            assert!(order.into_iter().eq((0..len).rev()));
+            assert!(arr
+                .iter()
+                .all(|member| member.footer[0] as usize == member.header.0));
+
+            arr.reverse();
+            assert!(arr.iter().map(|member| member.header.0).eq(0..len));
+
+            if len > 2 {
+                arr.slice_mut(1..).reverse();
+                assert!(arr[0].header.0 == 0 && arr[1].header.0 == len - 1);
+            }
+            assert!(drops.get() == 0);
+
+            drop(arr);
+            assert!(drops.get() == len);
+            drops.set(0);
+        }
+    }
+
+    #[test]
+    fn rotate_slices() {
+        let drops = Cell::new(0);
+        let drops = &drops;
+
+        for (len, k) in [
+            (0, 0),
+            (1, 1),
+            (6, 0),
+            (6, 6),
+            (6, 4),
+            (6, 3),
+            (7, 3),
+            (9, 6),
+        ] {
+            let mut arr = DstArray::from_fn(2, len, |index| {
+                ((index, DropCounter(drops)), [index as u8; 2])
+            });
+            let mut expected: Vec<usize> = (0..len).collect();
+
+            arr.as_mut_slice().rotate_left(k);
+            expected.rotate_left(k);
+            assert!(arr
+                .iter()
+                .map(|member| member.header.0)
+                .eq(expected.iter().copied()));
+            assert!(arr
+                .iter()
+                .all(|member| member.footer[1] as usize == member.header.0));
+
+            arr.as_mut_slice().rotate_right(k);
+            expected.rotate_right(k);
+            assert!(arr.iter().map(|member| member.header.0).eq(0..len));
+
+            arr.slice_mut(len / 2..).rotate_right(len / 4);
+            expected[len / 2..].rotate_right(len / 4);
+            assert!(arr
+                .iter()
+                .map(|member| member.header.0)
+                .eq(expected.iter().copied()));
+            assert!(drops.get() == 0);
+
+            drop(arr);
+            assert!(drops.get() == len);
+            drops.set(0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "rotation by 4 out of range for slice of length 3")]
+    fn rotate_past_len() {
+        let mut arr = DstArray::from_fn(1, 3, |index| (index, [0u8]));
+
+        arr.as_mut_slice().rotate_right(4);
+    }
+
+    #[test]
+    fn sort_unstable_matches_model() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+
+        for len in [0, 1, 2, 3, 10, 33, 100] {
+            for key_range in [1, 4, 1000] {
+                let mut model: Vec<(u64, Vec<u32>)> = (0..len)
+                    .map(|_| {
+                        let key = next_random(&mut state) % key_range;
+                        (key, vec![next_random(&mut state) as u32 % 5; 3])
+                    })
+                    .collect();
+                let mut arr: DstArray<u64, u32> = model.iter().cloned().collect();
+
+                arr.sort_unstable_by(|a, b| a.cmp(b));
+                model.sort();
+                assert!(arr
+                    .iter()
+                    .map(|member| (member.header, member.footer.to_vec()))
+                    .eq(model.iter().cloned()));
+
+                arr.sort_unstable_by_key(|member| cmp::Reverse(member.header));
+                let keys: Vec<u64> = arr.iter().map(|member| member.header).collect();
+                assert!(keys.windows(2).all(|pair| pair[0] >= pair[1]));
+                assert!(keys.iter().eq(model.iter().rev().map(|(key, _)| key)));
+            }
+        }
+    }
+
+    #[test]
+    fn sort_unstable_moves_without_dropping() {
+        let drops = Cell::new(0);
+        let drops = &drops;
+        let mut arr = DstArray::from_fn(2, 20, |index| {
+            (
+                (index * 7 % 20, DropCounter(drops)),
+                [DropCounter(drops), DropCounter(drops)],
+            )
+        });
+
+        arr.as_mut_slice()
+            .sort_unstable_by_key(|member| member.header.0);
+        assert!(arr.iter().map(|member| member.header.0).eq(0..20));
+        assert!(drops.get() == 0);
+
+        drop(arr);
+        assert!(drops.get() == 60);
+    }
+
+    #[test]
+    fn sort_by_cached_key_matches_model() {
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+
+        for len in [0, 1, 2, 7, 50, 128] {
+            for key_range in [1, 3, 1000] {
+                let mut model: Vec<(u64, Vec<u64>)> = (0..len)
+                    .map(|index| (next_random(&mut state) % key_range, vec![index; 4]))
+                    .collect();
+                let mut arr: DstArray<u64, u64> = model.iter().cloned().collect();
+
+                let order = arr.sorted_indices_by(|a, b| a.header.cmp(&b.header));
+                let mut model_order: Vec<usize> = (0..model.len()).collect();
+                model_order.sort_by_key(|&index| model[index].0);
+                assert!(order == model_order);
+
+                arr.sort_by_cached_key(|member| member.header);
+                model.sort_by_key(|(key, _)| *key);
+                assert!(arr
+                    .iter()
+                    .map(|member| (member.header, member.footer.to_vec()))
+                    .eq(model.iter().cloned()));
+            }
+        }
+    }
+
+    #[test]
+    fn sort_by_cached_key_calls_once_per_member() {
+        let drops = Cell::new(0);
+        let drops = &drops;
+        let mut arr = DstArray::from_fn(3, 30, |index| {
+            (
+                (30 - index) % 7,
+                [DropCounter(drops), DropCounter(drops), DropCounter(drops)],
+            )
+        });
+
+        let mut calls = 0;
+        arr.sort_by_cached_key(|member| {
+            calls += 1;
+            member.header
+        });
 
-        assert!(ptr <= unsafe { self.ptr.byte_add(self.get_stride() * self.len) });
+        assert!(calls == 30 && drops.get() == 0);
+        assert!(arr.iter().map(|member| member.header).is_sorted());
 
-        unsafe { &mut *ptr }
+        drop(arr);
+        assert!(drops.get() == 90);
     }
-}
 
-pub struct DstSliceMut<'a, H: Sized, F: Sized> {
-    start: *mut DstData<H, F>,
-    len: usize,
-    stride: usize,
-    phantom: PhantomData<&'a mut DstData<H, F>>,
-}
+    #[test]
+    fn binary_search() {
+        let keys = [1, 3, 3, 3, 5, 8, 8, 13];
+        let arr: DstArray<u32, u8> = keys.iter().map(|&key| (key, vec![key as u8])).collect();
+
+        for probe in 0..15 {
+            let found = arr.binary_search_by_key(&probe, |member| member.header);
+            match keys.binary_search(&probe) {
+                Ok(_) => assert!(found.is_ok_and(|index| keys[index] == probe)),
+                Err(index) => assert!(found == Err(index)),
+            }
 
-impl<'a, H, F> DstSliceMut<'a, H, F> {
-    pub fn as_ptr(&self) -> *const DstData<H, F> {
-        self.start as *const DstData<H, F>
-    }
+            let slice = arr.slice(2..6);
+            let found = slice.binary_search_by(|member| member.header.cmp(&probe));
+            match keys[2..6].binary_search(&probe) {
+                Ok(_) => assert!(found.is_ok_and(|index| keys[2 + index] == probe)),
+                Err(index) => assert!(found == Err(index)),
+            }
+        }
 
-    pub fn as_mut_ptr(&mut self) -> *mut DstData<H, F> {
-        self.start
+        assert!(arr.binary_search_by_key(&0, |member| member.header) == Err(0));
+        assert!(arr.binary_search_by_key(&14, |member| member.header) == Err(8));
+        assert!(arr.binary_search_by_key(&1, |member| member.header) == Ok(0));
+        assert!(arr.binary_search_by_key(&13, |member| member.header) == Ok(7));
+
+        let found = arr.binary_search_by(|member| member.footer[0].cmp(&3));
+        assert!(matches!(found, Ok(1..=3)));
+
+        let empty = empty_array();
+        assert!(empty.binary_search_by(|_| cmp::Ordering::Equal) == Err(0));
+        assert!(
+            empty
+                .as_slice()
+                .binary_search_by_key(&0, |member| member.footer[0])
+                == Err(0)
+        );
     }
 
-    pub fn as_shared_slice<'b>(self) -> DstSlice<'a, H, F>
-    where
-        'a: 'b,
-    {
-        DstSlice {
-            start: self.start as *const DstData<H, F>,
-            len: self.len,
-            stride: self.stride,
-            phantom: PhantomData,
+    #[test]
+    fn select_nth_matches_model() {
+        let mut state = 0x0123_4567_89ab_cdef;
+
+        for len in [1, 2, 3, 9, 64, 101] {
+            for key_range in [1, 5, 1000] {
+                let keys: Vec<u64> = (0..len)
+                    .map(|_| next_random(&mut state) % key_range)
+                    .collect();
+
+                for index in [0, len / 2, len - 1] {
+                    let mut arr: DstArray<u64, u64> =
+                        keys.iter().map(|&key| (key, vec![key * 2])).collect();
+                    let mut expected = keys.clone();
+                    let (_, expected_nth, _) = expected.select_nth_unstable(index);
+
+                    let (lower, nth, upper) =
+                        arr.select_nth_unstable_by(index, |a, b| a.header.cmp(&b.header));
+                    assert!(nth.header == *expected_nth && nth.footer == [nth.header * 2]);
+                    assert!(lower.len() == index && upper.len() == len - index - 1);
+                    assert!(lower.iter().all(|member| member.header <= nth.header));
+                    assert!(upper.iter().all(|member| member.header >= nth.header));
+
+                    let mut headers: Vec<u64> = arr.iter().map(|member| member.header).collect();
+                    headers.sort();
+                    let mut sorted = keys.clone();
+                    sorted.sort();
+                    assert!(headers == sorted);
+                }
+            }
         }
     }
-}
-
-impl<'a, H, F> Index<usize> for DstSliceMut<'a, H, F> {
-    type Output = DstData<H, F>;
-
-    fn index(&self, index: usize) -> &DstData<H, F> {
-        assert!(index < self.len);
 
-        let ptr = unsafe { self.start.byte_add(self.stride * index) };
+    #[test]
+    #[should_panic(expected = "the len is 3 but the index is 3")]
+    fn select_nth_out_of_bounds() {
+        let mut arr = DstArray::from_fn(1, 3, |index| (index, [0u8]));
 
-        unsafe { &*ptr }
+        arr.select_nth_unstable_by(3, |a, b| a.header.cmp(&b.header));
     }
-}
 
-impl<'a, H, F> IndexMut<usize> for DstSliceMut<'a, H, F> {
-    fn index_mut(&mut self, index: usize) -> &mut DstData<H, F> {
-        assert!(index < self.len);
+    #[test]
+    fn fill_arrays() {
+        let mut bytes = DstArray::from_fn(5, 3, |index| (index, [index as u8; 5]));
+        bytes.fill_footers(9);
+        assert!(bytes.iter().all(|member| member.footer == [9; 5]));
+        bytes.fill_footers_with(|arr_index, footer_index| (arr_index * 10 + footer_index) as u8);
+        assert!(bytes.get_footer_ref(2) == [20, 21, 22, 23, 24]);
+        bytes.fill_headers(4);
+        assert!(bytes.iter().all(|member| member.header == 4));
+
+        let old = Cell::new(0);
+        let new = Cell::new(0);
+        let (old, new) = (&old, &new);
+        let mut arr = DstArray::from_fn(3, 4, |_| {
+            (DropCounter(old), [(); 3].map(|_| DropCounter(old)))
+        });
+
+        arr.fill_footers(DropCounter(new));
+        assert!(old.get() == 12 && new.get() == 1);
+        arr.fill_footers_with(|_, _| DropCounter(old));
+        assert!(old.get() == 12 && new.get() == 13);
+        arr.fill_headers(DropCounter(new));
+        assert!(old.get() == 16 && new.get() == 14);
+
+        drop(arr);
+        assert!(old.get() == 28 && new.get() == 18);
+
+        let mut empty = DstArray::from_fn(1, 0, |_| (DropCounter(new), [0u8]));
+        empty.fill_footers(1);
+        empty.fill_headers(DropCounter(new));
+        assert!(new.get() == 19);
+    }
 
-        let ptr = unsafe { self.start.byte_add(self.stride * index) };
+    #[test]
+    fn clone_from_reuses_matching_arrays() {
+        let drops = Cell::new(0);
+        let drops = &drops;
+        let source = DstArray::from_fn(2, 3, |index| {
+            (
+                (index, DropCounter(drops)),
+                [(index * 2, DropCounter(drops)), (0, DropCounter(drops))],
+            )
+        });
 
-        unsafe { &mut *ptr }
-    }
-}
+        let mut target = DstArray::from_fn(2, 3, |_| {
+            (
+                (9, DropCounter(drops)),
+                [(9, DropCounter(drops)), (9, DropCounter(drops))],
+            )
+        });
+        let allocation = target.as_ptr();
 
-impl<'a, H, F> SplitSliceMutExt<'a, H, F> for *mut DstSliceMut<'a, H, F> {
-    unsafe fn split_at_mut(
-        self,
-        len: usize,
-        mid: usize,
-    ) -> (DstSliceMut<'a, H, F>, DstSliceMut<'a, H, F>) {
-        assert!(mid <= len);
+        target.clone_from(&source);
+        assert!(ptr::eq(target.as_ptr(), allocation));
+        assert!(drops.get() == 9);
+        assert!(target.iter().map(|member| member.header.0).eq(0..3));
+        assert!(target.get_footer_ref(2)[0].0 == 4);
 
-        unsafe { Self::split_at_mut_unchecked(self, len, mid) }
-    }
+        drops.set(0);
+        let mut longer = DstArray::from_fn(2, 4, |_| {
+            (
+                (9, DropCounter(drops)),
+                [(9, DropCounter(drops)), (9, DropCounter(drops))],
+            )
+        });
+        longer.clone_from(&source);
+        assert!(drops.get() == 12);
+        assert!(longer.len() == 3 && longer.get_footer_ref(1)[0].0 == 2);
 
-    unsafe fn split_at_mut_unchecked(
-        self,
-        len: usize,
-        mid: usize,
-    ) -> (DstSliceMut<'a, H, F>, DstSliceMut<'a, H, F>) {
-        unsafe {
+        drops.set(0);
+        let mut wider = DstArray::from_fn(3, 3, |_| {
             (
-                DstSliceMut {
-                    start: (*self).start,
-                    len: mid,
-                    stride: (*self).stride,
-                    phantom: PhantomData,
-                },
-                DstSliceMut {
-                    start: (*self).start.byte_add((*self).stride * mid),
-                    len: len - mid,
-                    stride: (*self).stride,
-                    phantom: PhantomData,
-                },
+                (9, DropCounter(drops)),
+                [(); 3].map(|_| (9, DropCounter(drops))),
             )
-        }
+        });
+        wider.clone_from(&source);
+        assert!(drops.get() == 12);
+        assert!(wider.get_footer_len() == 2 && wider.get_header_ref(2).0 == 2);
+
+        drops.set(0);
+        drop((source, target, longer, wider));
+        assert!(drops.get() == 36);
     }
-}
 
-unsafe impl<'a, H, F> Send for DstSliceMut<'a, H, F> {}
+    #[test]
+    fn projection_iterators() {
+        let mut arr = DstArray::from_fn(3, 5, |index| (index as u32, [index as u16; 3]));
 
-pub struct DstSlice<'a, H: Sized, F: Sized> {
-    start: *const DstData<H, F>,
-    len: usize,
-    stride: usize,
-    phantom: PhantomData<&'a DstData<H, F>>,
-}
+        assert!(arr.iter_headers().copied().eq(0..5));
+        assert!(arr.iter_headers().rev().copied().eq((0..5).rev()));
+        assert!(arr.iter_headers().len() == 5 && arr.iter_footers().len() == 5);
+        assert!(arr.iter_footers().nth(3) == Some(&[3, 3, 3][..]));
 
-impl<'a, H, F> DstSlice<'a, H, F> {
-    pub fn as_ptr(&mut self) -> *const DstData<H, F> {
-        self.start
-    }
-}
+        for header in arr.iter_headers_mut() {
+            *header *= 10;
+        }
+        for footer in arr.iter_footers_mut().rev().take(2) {
+            footer[1] = 100;
+        }
+        for (header, footer) in arr.iter_parts_mut() {
+            footer[2] = *header as u16 + 1;
+        }
 
-impl<'a, H, F> Index<usize> for DstSlice<'a, H, F> {
-    type Output = DstData<H, F>;
+        assert!(arr.iter_headers().copied().eq([0, 10, 20, 30, 40]));
+        assert!(arr.get_footer_ref(4) == [4, 100, 41] && arr.get_footer_ref(2) == [2, 2, 21]);
+        assert!(arr.iter_parts_mut().len() == 5);
 
-    fn index(&self, index: usize) -> &DstData<H, F> {
-        assert!(index < self.len);
+        let mut empty = empty_array();
+        assert!(empty.iter_headers().next().is_none());
+        assert!(empty.iter_footers_mut().next_back().is_none());
+        assert!(empty.iter_parts_mut().next().is_none());
+    }
 
-        let ptr = unsafe { self.start.byte_add(self.stride * index) };
+    #[test]
+    fn flattened_footer_elements() {
+        for (footer_len, len) in [(0, 4), (3, 0), (1, 5), (4, 3)] {
+            let mut arr = DstArray::from_fn(footer_len, len, |index| {
+                (
+                    index,
+                    (0..footer_len).map(move |offset| (index * 10 + offset) as u8),
+                )
+            });
+            let concatenated: Vec<u8> = arr.iter_footers().flatten().copied().collect();
+
+            let elements = arr.iter_all_footer_elements();
+            assert!(elements.len() == len * footer_len);
+            assert!(elements.copied().eq(concatenated.iter().copied()));
+            assert!(arr
+                .iter_all_footer_elements()
+                .rev()
+                .copied()
+                .eq(concatenated.iter().rev().copied()));
+
+            let mut elements = arr.iter_all_footer_elements();
+            let mut expected = concatenated.iter();
+            for step in 0..concatenated.len() + 2 {
+                if step % 3 == 0 {
+                    assert!(elements.next_back() == expected.next_back());
+                } else {
+                    assert!(elements.next() == expected.next());
+                }
+                assert!(elements.len() == expected.len());
+            }
 
-        unsafe { &*ptr }
+            for (position, element) in arr.iter_all_footer_elements_mut().enumerate() {
+                *element = position as u8;
+            }
+            for (arr_index, footer) in arr.iter_footers().enumerate() {
+                assert!(footer
+                    .iter()
+                    .copied()
+                    .eq((0..footer_len).map(|offset| (arr_index * footer_len + offset) as u8)));
+            }
+            if let Some(last) = arr.iter_all_footer_elements_mut().next_back() {
+                *last = 200;
+                assert!(arr.get_footer_ref(len - 1)[footer_len - 1] == 200);
+            }
+        }
     }
-}
 
-impl<'a, H, F> SplitSliceExt<'a, H, F> for *mut DstSliceMut<'a, H, F> {
-    unsafe fn split_at(self, len: usize, mid: usize) -> (DstSlice<'a, H, F>, DstSlice<'a, H, F>) {
-        assert!(mid <= len);
+    #[test]
+    #[should_panic(expected = "the array has more than usize::MAX footer elements")]
+    fn iter_all_footer_elements_overflow() {
+        let arr = unsafe { MaybeUninitDstArray::<(), ()>::new(usize::MAX, 2).assume_init() };
 
-        unsafe { Self::split_at_unchecked(self, len, mid) }
+        let _ = arr.iter_all_footer_elements();
     }
 
-    unsafe fn split_at_unchecked(
-        self,
-        len: usize,
-        mid: usize,
-    ) -> (DstSlice<'a, H, F>, DstSlice<'a, H, F>) {
-        unsafe {
+    #[test]
+    fn array_par_init() {
+        let init = |arr_index: usize| {
             (
-                DstSlice {
-                    start: (*self).start,
-                    len: mid,
-                    stride: (*self).stride,
-                    phantom: PhantomData,
-                },
-                DstSlice {
-                    start: (*self).start.byte_add((*self).stride * mid),
-                    len: len - mid,
-                    stride: (*self).stride,
-                    phantom: PhantomData,
-                },
+                arr_index,
+                (0..3).map(move |index| (arr_index * 3 + index) as u16),
             )
-        }
-    }
-}
-
-unsafe impl<'a, H, F> Send for DstSlice<'a, H, F> {}
+        };
 
-unsafe impl<'a, H, F> Sync for DstSlice<'a, H, F> {}
+        let sequential = MaybeUninitDstArray::new(3, 23).init_each(init);
 
-pub struct DstChunksMut<'a, H: Sized, F: Sized> {
-    slice: DstSliceMut<'a, H, F>,
-    chunk_size: usize,
-}
+        for threads in [1, 2, 4, 23, 64] {
+            let parallel = MaybeUninitDstArray::new(3, 23).par_init(threads, init);
 
-impl<'a, H, F> DstChunksMut<'a, H, F> {
-    pub fn new(slice: DstSliceMut<'a, H, F>, size: usize) -> Self {
-        Self {
-            slice,
-            chunk_size: size,
+            assert!(parallel.len() == sequential.len());
+            for arr_index in 0..sequential.len() {
+                assert!(parallel.get_header_ref(arr_index) == sequential.get_header_ref(arr_index));
+                assert!(parallel.get_footer_ref(arr_index) == sequential.get_footer_ref(arr_index));
+            }
         }
+
+        let empty = MaybeUninitDstArray::<usize, u16>::new(3, 0).par_init(4, init);
+        assert!(empty.is_empty());
+
+        let mut prewritten = MaybeUninitDstArray::<usize, u16>::new(3, 8);
+        prewritten.write_header(5, 0);
+        let arr = prewritten.par_init(3, init);
+        assert!(*arr.get_header_ref(5) == 5);
     }
-}
 
-impl<'a, H, F> Iterator for DstChunksMut<'a, H, F> {
-    type Item = DstSliceMut<'a, H, F>;
+    #[test]
+    fn array_par_init_cleanup() {
+        let drops = AtomicUsize::new(0);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            MaybeUninitDstArray::new(1, 20).par_init(4, |arr_index| {
+                if arr_index == 12 {
+                    silent_panic();
+                }
+
+                (SyncDropCounter(&drops), [SyncDropCounter(&drops)])
+            })
+        }));
+
+        assert!(result.is_err());
+        //Members 12 to 14 belong to the thread that panicked, and were never initialized
+        assert!(drops.load(Ordering::Relaxed) == 34);
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.slice.len == 0 {
-            None
-        } else {
-            let chunksz = cmp::min(self.slice.len, self.chunk_size);
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "overwrote the initialized header of member 13")]
+    fn array_par_init_overwriting_drop_header() {
+        let drops = AtomicUsize::new(0);
+        let mut arr = MaybeUninitDstArray::new(1, 20);
 
-            let (fst, snd) = unsafe {
-                (&mut self.slice as *mut DstSliceMut<'a, H, F>)
-                    .split_at_mut(self.slice.len, chunksz)
-            };
-            let x = Some(fst);
-            self.slice = snd;
+        arr.write_header(13, SyncDropCounter(&drops));
 
-            x
-        }
+        let _ = arr.par_init(4, |_| (SyncDropCounter(&drops), [SyncDropCounter(&drops)]));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn get_disjoint_mut() {
+        let mut arr = DstArray::from_fn(2, 5, |index| (index, [index as u8; 2]));
+
+        let [a, b, c] = arr.get_disjoint_mut([4, 0, 2]).unwrap();
+        *a.get_header_mut() += 10;
+        *b.get_header_mut() += 20;
+        c.get_footer_mut()[1] = 9;
+
+        assert!(*arr.get_header_ref(4) == 14);
+        assert!(*arr.get_header_ref(0) == 20);
+        assert!(arr.get_footer_ref(2) == [2, 9]);
+
+        assert!(
+            arr.get_disjoint_mut([1, 3, 1]).err() == Some(GetDisjointMutError::OverlappingIndices)
+        );
+        assert!(arr.get_disjoint_mut([0, 5]).err() == Some(GetDisjointMutError::IndexOutOfBounds));
+        assert!(arr.get_disjoint_mut([]).is_ok());
+
+        let mut slice = arr.slice_mut(1..4);
+        let [first, last] = slice.get_disjoint_mut([0, 2]).unwrap();
+        mem::swap(first.get_header_mut(), last.get_header_mut());
+        assert!(slice.get_disjoint_mut([3]).err() == Some(GetDisjointMutError::IndexOutOfBounds));
+
+        assert!(*arr.get_header_ref(1) == 3);
+        assert!(*arr.get_header_ref(3) == 1);
+
+        let mut empty = empty_array();
+        assert!(empty.get_disjoint_mut([0]).err() == Some(GetDisjointMutError::IndexOutOfBounds));
+    }
 
     #[test]
-    fn writing() {
-        let mut dst = MaybeUninitDst::<u32, u64>::new(2);
+    fn split_first_and_last() {
+        fn bump_front(slice: DstSliceMut<'_, usize, u16>) {
+            if let Some((first, rest)) = slice.split_first_mut() {
+                *first.get_header_mut() += 1;
+                first.get_footer_mut()[0] += 1;
+                bump_front(rest);
+            }
+        }
 
-        dst.write_header(2);
+        fn bump_back(slice: DstSliceMut<'_, usize, u16>) {
+            if let Some((last, rest)) = slice.split_last_mut() {
+                *last.get_header_mut() += 10;
+                bump_back(rest);
+            }
+        }
 
-        let header_ref = dst.get_header_ptr();
+        fn sum_back(slice: DstSlice<'_, usize, u16>) -> usize {
+            match slice.split_last() {
+                Some((last, rest)) => *last.get_header() + sum_back(rest),
+                None => 0,
+            }
+        }
 
-        unsafe { assert!(*header_ref == 2) }
+        let mut arr = DstArray::from_fn(1, 6, |index| (index, [index as u16]));
 
-        let footer = [1, 2];
+        bump_front(arr.as_mut_slice());
+        bump_back(arr.slice_mut(2..));
 
-        dst.write_footer(&footer);
+        let headers = arr.iter_headers().copied().collect::<Vec<_>>();
+        assert!(headers == [1, 2, 13, 14, 15, 16]);
+        assert!((0..6).all(|index| arr.get_footer_ref(index) == [index as u16 + 1]));
+        assert!(sum_back(arr.as_slice()) == 61);
 
-        let footer_ref = dst.get_footer_ptr();
+        let (first, rest) = arr.as_slice().split_first().unwrap();
+        assert!(*first.get_header() == 1);
+        assert!(rest.len() == 5);
+
+        let mut empty = empty_array();
+        assert!(empty.as_slice().split_first().is_none());
+        assert!(empty.as_slice().split_last().is_none());
+        assert!(empty.as_mut_slice().split_first_mut().is_none());
+        assert!(empty.as_mut_slice().split_last_mut().is_none());
+    }
+
+    #[test]
+    fn checked_get() {
+        let mut arr = DstArray::from_fn(2, 4, |index| (index, [index as u8; 2]));
+
+        assert!(arr.get(4).is_none());
+        assert!(arr.get_mut(4).is_none());
+        assert!(arr.get(usize::MAX).is_none());
+        assert!(*arr.get(3).unwrap().get_header() == 3);
+        assert!(*arr.first().unwrap().get_header() == 0);
+        assert!(*arr.last().unwrap().get_header() == 3);
+
+        arr.get_mut(3).unwrap().get_footer_mut()[1] = 7;
+        *arr.first_mut().unwrap().get_header_mut() = 10;
+        *arr.last_mut().unwrap().get_header_mut() += 10;
+        assert!(arr.get_footer_ref(3) == [3, 7]);
+        assert!(*arr.get_header_ref(0) == 10);
+        assert!(*arr.get_header_ref(3) == 13);
+
+        let mut slice = arr.slice_mut(1..3);
+        assert!(slice.get(2).is_none());
+        assert!(slice.get_mut(2).is_none());
+        assert!(*slice.get(1).unwrap().get_header() == 2);
+        assert!(*slice.last_mut().unwrap().get_header() == 2);
+
+        let slice = arr.slice(1..3);
+        assert!(slice.get(2).is_none());
+        assert!(*slice.get(1).unwrap().get_header() == 2);
+
+        let mut empty = empty_array();
+        assert!(empty.get(0).is_none());
+        assert!(empty.get_mut(0).is_none());
+        assert!(empty.first().is_none());
+        assert!(empty.last().is_none());
+        assert!(empty.first_mut().is_none());
+        assert!(empty.last_mut().is_none());
+        assert!(empty.as_slice().last().is_none());
+    }
+
+    #[test]
+    fn unchecked_get() {
+        let mut arr = DstArray::from_fn(1, 3, |index| (index, [index as u8]));
 
         unsafe {
-            assert!((*footer_ref)[0] == 1);
-            assert!((*footer_ref)[1] == 2);
+            *arr.get_unchecked_mut(2).get_header_mut() = 7;
+            assert!(*arr.get_unchecked(2).get_header() == 7);
+
+            let mut slice = arr.slice_mut(1..);
+            slice.get_unchecked_mut(0).get_footer_mut()[0] = 9;
+            assert!(*slice.get_unchecked(1).get_header() == 7);
+
+            let member = arr.slice(1..).get_unchecked(0);
+            assert!(member.get_footer() == [9]);
         }
     }
 
     #[test]
-    #[should_panic]
-    fn invalid_footer_write() {
-        let mut dst = MaybeUninitDst::<u32, u64>::new(2);
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "get_unchecked index 3 out of bounds")]
+    fn unchecked_get_debug_assert() {
+        let arr = DstArray::from_fn(1, 3, |index| (index, [index as u8]));
 
-        let footer = [1, 2, 3];
-
-        dst.write_footer(&footer);
+        unsafe { arr.get_unchecked(3) };
     }
 
     #[test]
-    #[should_panic]
-    fn invalid_element_write() {
-        let mut dst = MaybeUninitDst::<u32, u64>::new(2);
+    fn copy_within() {
+        let parts = |index: usize| (index as u32, [index as u16, index as u16 * 10]);
+
+        for (src, dest) in [
+            (2..6, 0),
+            (0..4, 3),
+            (1..5, 2),
+            (3..7, 1),
+            (0..7, 0),
+            (4..4, 7),
+        ] {
+            let mut arr = DstArray::from_fn(2, 7, parts);
+            let mut model = (0..7).map(parts).collect::<Vec<_>>();
+
+            arr.copy_within(src.clone(), dest);
+            model.copy_within(src, dest);
+
+            for (index, (header, footer)) in model.iter().enumerate() {
+                assert!(arr.get_header_ref(index) == header);
+                assert!(arr.get_footer_ref(index) == footer);
+            }
+        }
 
-        dst.write_footer_element(3, 1);
+        let mut arr = DstArray::from_fn(2, 7, parts);
+        arr.slice_mut(2..6).copy_within(..2, 2);
+        assert!(arr.iter_headers().eq(&[0, 1, 2, 3, 2, 3, 6]));
     }
 
     #[test]
-    fn element_write() {
-        let mut dst = MaybeUninitDst::<u32, u64>::new(2);
+    #[should_panic(expected = "dest is out of bounds")]
+    fn copy_within_dest_out_of_bounds() {
+        let mut arr = DstArray::from_fn(1, 4, |index| (index, [0u8]));
 
-        dst.write_footer_element(1, 1);
+        arr.copy_within(1..3, 3);
+    }
 
-        let footer_element_ref = dst.get_footer_element_ptr(1);
+    #[test]
+    #[should_panic(expected = "range end index 5 out of range for slice of length 4")]
+    fn copy_within_src_out_of_bounds() {
+        let mut arr = DstArray::from_fn(1, 4, |index| (index, [0u8]));
 
-        unsafe { assert!(*footer_element_ref == 1) }
+        arr.copy_within(2..5, 0);
     }
 
     #[test]
-    fn element_write2() {
-        let mut dst = MaybeUninitDst::<u32, u64>::new(3);
+    fn retain_into() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let arr = DstArray::from_fn(2, 7, |index| {
+                (index, [DropCounter(&drops), DropCounter(&drops)])
+            });
+
+            let mut calls = 0;
+            let kept = arr.retain_into(|member| {
+                calls += 1;
+                member.get_header() % 3 != 0
+            });
+
+            assert!(calls == 7);
+            assert!(drops.get() == 6);
+            assert!(kept.iter_headers().eq(&[1, 2, 4, 5]));
+
+            let none = kept.retain_into(|_| false);
+            assert!(none.is_empty());
+            assert!(none.get_footer_len() == 2);
+            assert!(drops.get() == 14);
+
+            let all = DstArray::from_fn(1, 3, |index| (DropCounter(&drops), [index]));
+            let all = all.retain_into(|_| true);
+            assert!(all.len() == 3);
+            assert!(drops.get() == 14);
+            assert!((0..3).all(|index| all.get_footer_ref(index) == [index]));
+        });
+
+        assert!(drops.get() == 17);
+    }
 
-        let footer = [1, 2, 3];
+    #[test]
+    fn filter_clone() {
+        let drops = Cell::new(0);
 
-        dst.write_footer(&footer);
+        assert_no_leaks(|| {
+            let arr = DstArray::from_fn(1, 6, |index| (index, [DropCounter(&drops)]));
 
-        dst.write_footer_element(1, 5);
+            let odd = arr.filter_clone(|member| member.get_header() % 2 == 1);
+            assert!(odd.iter_headers().eq(&[1, 3, 5]));
+            assert!(arr.len() == 6);
 
-        let footer_ptr = dst.get_footer_ptr();
+            let none = arr.filter_clone(|_| false);
+            assert!(none.is_empty());
 
-        unsafe {
-            assert!((*footer_ptr)[0] == 1);
-            assert!((*footer_ptr)[1] == 5);
-            assert!((*footer_ptr)[2] == 3)
-        }
+            let all = arr.filter_clone(|_| true);
+            assert!(all.iter_headers().eq(arr.iter_headers()));
+
+            drop(odd);
+            assert!(drops.get() == 3);
+        });
+
+        assert!(drops.get() == 15);
     }
 
     #[test]
-    fn assume_init() {
-        let mut dst = MaybeUninitDst::<u8, u64>::new(5);
+    fn concat() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let parts = |index: usize| (index, [index as u16, index as u16 + 1]);
+            let first = DstArray::from_fn(2, 3, parts);
+            let second = DstArray::from_fn(2, 4, |index| parts(index + 3));
+
+            let arr = DstArray::concat(first, second).unwrap();
+            let model = [
+                (0..3).map(parts).collect::<Vec<_>>(),
+                (3..7).map(parts).collect(),
+            ]
+            .concat();
+
+            assert!(arr.len() == model.len());
+            for (index, (header, footer)) in model.iter().enumerate() {
+                assert!(arr.get_header_ref(index) == header);
+                assert!(arr.get_footer_ref(index) == footer);
+            }
 
-        dst.write_header(1);
+            let empty = DstArray::concat(DstArray::from_fn(2, 0, parts), arr.clone()).unwrap();
+            assert!(empty.iter_headers().eq(arr.iter_headers()));
+
+            let counted =
+                |len| DstArray::from_fn(1, len, |_| (DropCounter(&drops), [DropCounter(&drops)]));
+            let arr = DstArray::concat(counted(2), counted(3)).unwrap();
+            assert!(drops.get() == 0);
+            assert!(arr.len() == 5);
+            drop(arr);
+            assert!(drops.get() == 10);
+
+            let longer = DstArray::from_fn(2, 2, |_| {
+                (
+                    DropCounter(&drops),
+                    [DropCounter(&drops), DropCounter(&drops)],
+                )
+            });
+            let Err(err) = DstArray::concat(counted(1), longer) else {
+                panic!("the footers have different lengths");
+            };
+            assert!(err.to_string() == "the footers of the first array have 1 elements but those of the second have 2");
+            assert!(err.first.len() == 1);
+            assert!(err.second.len() == 2);
+            assert!(drops.get() == 10);
+        });
 
-        let footer = [0, 1, 2, 3, 4];
+        assert!(drops.get() == 18);
+    }
 
-        dst.write_footer(&footer);
+    #[test]
+    fn split_off() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let counted = || {
+                DstArray::from_fn(2, 5, |index| {
+                    (
+                        (index, DropCounter(&drops)),
+                        [DropCounter(&drops), DropCounter(&drops)],
+                    )
+                })
+            };
 
-        let dst = unsafe { dst.assume_init() };
+            let (front, back) = counted().split_off(2);
+            assert!(drops.get() == 0);
+            assert!(front.len() == 2 && back.len() == 3);
+            assert!(front.get_footer_len() == 2 && back.get_footer_len() == 2);
+            assert!(front.iter_headers().map(|header| header.0).eq(0..2));
+            assert!(back.iter_headers().map(|header| header.0).eq(2..5));
+
+            drop(front);
+            assert!(drops.get() == 6);
+            drop(back);
+            assert!(drops.get() == 15);
+
+            let arr = counted();
+            let address = arr.as_ptr();
+            let (empty, whole) = arr.split_off(0);
+            assert!(empty.is_empty() && empty.get_footer_len() == 2);
+            assert!(whole.len() == 5 && ptr::addr_eq(whole.as_ptr(), address));
+
+            let (whole, empty) = whole.split_off(5);
+            assert!(empty.is_empty());
+            assert!(whole.len() == 5 && ptr::addr_eq(whole.as_ptr(), address));
+            assert!(drops.get() == 15);
+
+            drop(whole);
+            assert!(drops.get() == 30);
+
+            let (front, back) = DstArray::from_fn(1, 3, |index| ((), [index])).split_off(1);
+            assert!(front.get_footer_ref(0) == [0]);
+            assert!(back.get_footer_ref(0) == [1] && back.get_footer_ref(1) == [2]);
+        });
+
+        assert!(drops.get() == 30);
+    }
 
-        assert!(dst.get_footer_len() == 5);
+    #[test]
+    #[should_panic(expected = "mid 4 out of range for array of length 3")]
+    fn split_off_out_of_range() {
+        let _ = DstArray::from_fn(1, 3, |index| (index, [0u8])).split_off(4);
+    }
 
-        assert!(dst.get_footer_ref().eq(&footer));
+    #[test]
+    fn truncate_into() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let arr = DstArray::from_fn(2, 6, |index| {
+                (
+                    (index, DropCounter(&drops)),
+                    [DropCounter(&drops), DropCounter(&drops)],
+                )
+            });
+            let address = arr.as_ptr();
+
+            let arr = arr.truncate_into(6).truncate_into(10);
+            assert!(arr.len() == 6 && ptr::addr_eq(arr.as_ptr(), address));
+            assert!(drops.get() == 0);
+
+            let mut arr = arr.truncate_into(4);
+            assert!(drops.get() == 6);
+            assert!(arr.len() == 4 && arr.get_footer_len() == 2);
+            assert!(arr.iter().count() == 4);
+            assert!(arr.iter_headers().map(|header| header.0).eq(0..4));
+            assert!(arr.get(3).is_some() && arr.get(4).is_none());
+            assert!(arr[3].get_header().0 == 3);
+
+            arr[0].get_header_mut().0 = 10;
+            drop(arr);
+            assert!(drops.get() == 18);
+
+            let arr = DstArray::from_fn(3, 4, |index| (index, [index, index + 1, index + 2]));
+            let arr = arr.truncate_into(3);
+            for index in 0..3 {
+                assert!(*arr.get_header_ref(index) == index);
+                assert!(arr.get_footer_ref(index) == [index, index + 1, index + 2]);
+            }
 
-        assert!(*dst.get_header_ref() == 1);
+            let empty = arr.truncate_into(0);
+            assert!(empty.is_empty() && empty.get_footer_len() == 3);
+        });
+
+        assert!(drops.get() == 18);
     }
 
-    #[test]
-    fn array() {
-        let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(2, 2);
+    ///Touches every pointer the crate hands out, so that running
+    ///`MIRIFLAGS="-Zmiri-strict-provenance" cargo +nightly miri test miri` (with and without
+    ///`-Zmiri-tree-borrows`) checks the pointer handling
+    mod miri {
+        use super::*;
 
-        let mut arr = [0, 1];
+        #[test]
+        fn dst_parts() {
+            let mut dst = MaybeUninitDst::<u16, u32>::new(3);
 
-        dst_arr.write_header(0, 0);
-        dst_arr.write_footer(0, &arr);
+            dst.write_header(1);
+            dst.write_footer_element(0, 2);
+            unsafe {
+                dst.get_footer_element_ptr_mut(1).write(3);
+                dst.get_footer_element_ptr_mut(2).write(4);
+            }
 
-        arr[1] = 5;
+            let mut dst = unsafe { dst.assume_init() };
 
-        dst_arr.write_header(1, 1);
-        dst_arr.write_footer(1, &arr);
+            *dst.get_header_ref_mut() += 1;
+            dst.get_footer_ref_mut()[2] += 1;
 
-        let dst_arr = unsafe { dst_arr.assume_init() };
+            assert!(*dst.get_header_ref() == 2);
+            assert!(dst.get_footer_ref() == [2, 3, 5]);
+        }
 
-        assert!(*dst_arr.get_header_ref(0) == 0);
-        assert!(dst_arr.get_footer_ref(0)[0] == 0);
-        assert!(dst_arr.get_footer_ref(0)[1] == 1);
+        #[test]
+        fn array_elements() {
+            let mut dst_arr = MaybeUninitDstArray::<u8, String>::new(2, 3);
 
-        assert!(*dst_arr.get_header_ref(1) == 1);
-        assert!(dst_arr.get_footer_ref(1)[0] == 0);
-        assert!(dst_arr.get_footer_ref(1)[1] == 5);
-    }
+            for arr_index in 0..3 {
+                dst_arr.write_header(arr_index, arr_index as u8);
+                dst_arr.write_footer_from_vec(
+                    arr_index,
+                    vec![arr_index.to_string(), String::from("footer")],
+                );
+            }
 
-    #[test]
-    #[should_panic]
-    fn array_invalid() {
-        let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(2, 2);
+            let mut dst_arr = unsafe { dst_arr.assume_init() };
 
-        dst_arr.write_header(2, 1);
-    }
+            dst_arr[1].get_footer_mut()[0].push('!');
+            *dst_arr.get_mut_arr_element(2).get_header_mut() = 7;
 
-    #[test]
-    fn swap() {
-        let mut dst_arr1 = MaybeUninitDstArray::<u32, u8>::new(1, 1);
+            assert!(dst_arr[1].get_footer()[0] == "1!");
+            assert!(*dst_arr.get_arr_element(2).get_header() == 7);
+            assert!(dst_arr.get_footer_ref(0) == ["0", "footer"]);
+        }
 
-        dst_arr1.write_header(0, 0);
-        dst_arr1.write_footer_element(0, 0, 0);
+        #[test]
+        fn raw_reconstruct_and_drop() {
+            let dst = Dst::from_vec(String::from("header"), vec![String::from("footer")]);
 
-        let mut dst_arr1 = unsafe { dst_arr1.assume_init() };
+            let ptr = dst.into_raw();
+            let dst = unsafe { Dst::from_raw(ptr) };
 
-        let mut dst_arr2 = MaybeUninitDstArray::<u32, u8>::new(1, 1);
+            let (ptr, len) = dst.into_raw_parts();
+            let dst = unsafe { Dst::<String, String>::from_raw_parts(ptr, len) };
 
-        dst_arr2.write_header(0, 1);
-        dst_arr2.write_footer_element(0, 0, 1);
+            assert!(dst.get_footer_ref() == ["footer"]);
+        }
 
-        let mut dst_arr2 = unsafe { dst_arr2.assume_init() };
+        #[test]
+        fn slices_and_chunks() {
+            let mut dst_arr = MaybeUninitDstArray::<u32, u8>::new(1, 4);
 
-        let ptr1 = dst_arr1.ptr;
-        let ptr2 = dst_arr2.ptr;
+            for arr_index in 0..4 {
+                dst_arr.write_header(arr_index, arr_index as u32);
+                dst_arr.write_footer(arr_index, &[arr_index as u8]);
+            }
 
-        assert!(*dst_arr1.get_header_ref(0) == 0);
-        assert!(*dst_arr2.get_header_ref(0) == 1);
+            let mut dst_arr = unsafe { dst_arr.assume_init() };
 
-        dst_arr1.swap(&mut dst_arr2);
+            {
+                let mut slice = dst_arr.get_mut_slice(1, 4);
 
-        assert!(ptr1 == dst_arr2.ptr);
-        assert!(ptr2 == dst_arr1.ptr);
+                *slice[0].get_header_mut() += 10;
+                unsafe { (*slice.as_mut_ptr()).get_footer_mut()[0] += 10 };
 
-        assert!(*dst_arr1.get_header_ref(0) == 1);
-        assert!(*dst_arr2.get_header_ref(0) == 0);
+                let shared = slice.as_shared_slice();
+                assert!(*shared[0].get_header() == 11);
+            }
+
+            for (chunk_index, mut chunk) in
+                DstChunksMut::new(dst_arr.get_mut_slice(0, 4), 3).enumerate()
+            {
+                *chunk[0].get_header_mut() += 100 * chunk_index as u32;
+            }
+
+            let first = dst_arr.get_slice(0, 2);
+            let second = dst_arr.get_slice(1, 4);
+
+            assert!(*first[1].get_header() == 11);
+            assert!(second[0].get_footer()[0] == 11);
+            assert!(*second[2].get_header() == 103);
+        }
     }
 }
@@ -0,0 +1,309 @@
+//!Recycles the allocations of Dsts that share a footer length
+
+use std::{
+    alloc::Global,
+    cell::RefCell,
+    clone::CloneToUninit,
+    fmt,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ops::{Deref, DerefMut},
+    ptr::{drop_in_place, NonNull},
+};
+
+use crate::{dealloc_layout, Dst, DstData, PrefixGuard};
+
+///Hands out Dsts whose footers all have the same length, keeping the memory of the ones that
+///are dropped to be handed out again instead of returning it to the allocator
+///
+///```
+///use custom_dst::pool::DstPool;
+///
+///let pool = DstPool::new(2);
+///
+///let first = pool.alloc(1u32, &[2u8, 3]);
+///let address = first.get_footer().as_ptr();
+///drop(first);
+///
+///let second = pool.alloc(4, &[5, 6]);
+///
+///assert_eq!(second.get_footer().as_ptr(), address);
+///assert_eq!(second.get_footer(), [5, 6]);
+///```
+pub struct DstPool<H, F> {
+    footer_len: usize,
+    ///The most allocations kept around once their values have been dropped
+    max_free: usize,
+    free: RefCell<Vec<NonNull<DstData<H, F>>>>,
+    phantom: PhantomData<DstData<H, F>>,
+}
+
+impl<H, F> DstPool<H, F> {
+    ///Creates a pool of Dsts with `footer_len` footer elements, which keeps every allocation
+    ///it is given back
+    pub fn new(footer_len: usize) -> DstPool<H, F> {
+        Self::with_max_free(footer_len, usize::MAX)
+    }
+
+    ///Creates a pool of Dsts with `footer_len` footer elements, which keeps at most
+    ///`max_free` allocations it is given back and frees the rest
+    pub fn with_max_free(footer_len: usize, max_free: usize) -> DstPool<H, F> {
+        DstPool {
+            footer_len,
+            max_free,
+            free: RefCell::new(Vec::new()),
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns the number of footer elements of every Dst the pool hands out
+    pub fn get_footer_len(&self) -> usize {
+        self.footer_len
+    }
+
+    ///Returns the number of allocations waiting to be handed out again
+    pub fn free_len(&self) -> usize {
+        self.free.borrow().len()
+    }
+
+    ///Frees every allocation waiting to be handed out again
+    pub fn clear(&self) {
+        for ptr in mem::take(&mut *self.free.borrow_mut()) {
+            unsafe { Self::deallocate(ptr) };
+        }
+    }
+
+    unsafe fn deallocate(ptr: NonNull<DstData<H, F>>) {
+        unsafe {
+            let layout = DstData::layout_of_raw(ptr.as_ptr());
+
+            dealloc_layout(&Global, ptr.cast::<u8>().as_ptr(), layout);
+        }
+    }
+
+    ///Returns uninitialized memory for a Dst, reusing a free allocation if there is one
+    fn take_slot(&self) -> Slot<'_, H, F> {
+        let ptr = match self.free.borrow_mut().pop() {
+            Some(ptr) => ptr,
+            None => unsafe { DstData::alloc_self(self.footer_len, &Global) }
+                .unwrap_or_else(|err| err.handle()),
+        };
+
+        Slot { ptr, pool: self }
+    }
+
+    ///Keeps the memory of a dropped Dst to be handed out again, or frees it if the pool is
+    ///full
+    fn release(&self, ptr: NonNull<DstData<H, F>>) {
+        let mut free = self.free.borrow_mut();
+
+        if free.len() < self.max_free {
+            free.push(ptr);
+        } else {
+            unsafe { Self::deallocate(ptr) };
+        }
+    }
+
+    ///Hands out a Dst holding `header` and a clone of every element of `footer`
+    ///
+    ///# Panics
+    ///
+    ///Panics if `footer` doesn't have exactly [`DstPool::get_footer_len`] elements
+    #[track_caller]
+    pub fn alloc(&self, header: H, footer: &[F]) -> PooledDst<'_, H, F>
+    where
+        F: Clone,
+    {
+        assert!(
+            footer.len() == self.footer_len,
+            "the footer has {} elements but the pool's footers have {}",
+            footer.len(),
+            self.footer_len
+        );
+
+        let slot = self.take_slot();
+
+        unsafe {
+            let ptr = slot.ptr.as_ptr();
+
+            footer.clone_to_uninit(DstData::get_footer_slice(ptr).as_mut_ptr().cast::<u8>());
+            DstData::get_header_ptr(ptr).write(header);
+
+            slot.into_pooled()
+        }
+    }
+
+    ///Hands out a Dst holding `header` and a footer whose element `index` is `f(index)`
+    ///
+    ///If `f` panics, the header and the elements written so far are dropped and the memory
+    ///goes back to the pool
+    pub fn alloc_with(&self, header: H, mut f: impl FnMut(usize) -> F) -> PooledDst<'_, H, F> {
+        let slot = self.take_slot();
+
+        unsafe {
+            let ptr = slot.ptr.as_ptr();
+
+            let mut guard = PrefixGuard::new(DstData::get_footer_slice(ptr).as_mut_ptr());
+            for index in 0..self.footer_len {
+                guard.push(f(index));
+            }
+            guard.finish();
+
+            DstData::get_header_ptr(ptr).write(header);
+
+            slot.into_pooled()
+        }
+    }
+}
+
+impl<H, F> Drop for DstPool<H, F> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+unsafe impl<H: Send, F: Send> Send for DstPool<H, F> {}
+
+///Memory taken from a [`DstPool`] that goes back to it, without dropping anything, unless it
+///is initialized and turned into a [`PooledDst`]
+struct Slot<'pool, H, F> {
+    ptr: NonNull<DstData<H, F>>,
+    pool: &'pool DstPool<H, F>,
+}
+
+impl<'pool, H, F> Slot<'pool, H, F> {
+    ///# Safety
+    ///
+    /// The header and every footer element must have been initialized
+    unsafe fn into_pooled(self) -> PooledDst<'pool, H, F> {
+        let this = ManuallyDrop::new(self);
+
+        PooledDst {
+            ptr: this.ptr,
+            pool: this.pool,
+        }
+    }
+}
+
+impl<H, F> Drop for Slot<'_, H, F> {
+    fn drop(&mut self) {
+        self.pool.release(self.ptr);
+    }
+}
+
+///A Dst handed out by a [`DstPool`], whose memory goes back to the pool when it is dropped
+pub struct PooledDst<'pool, H, F> {
+    ptr: NonNull<DstData<H, F>>,
+    pool: &'pool DstPool<H, F>,
+}
+
+impl<H, F> PooledDst<'_, H, F> {
+    ///Takes the value out of the pool, so that its memory is freed rather than reused once
+    ///the returned Dst is dropped
+    pub fn into_dst(self) -> Dst<H, F> {
+        let this = ManuallyDrop::new(self);
+
+        Dst {
+            ptr: this.ptr,
+            phantom: PhantomData,
+            alloc: Global,
+        }
+    }
+}
+
+impl<H, F> Deref for PooledDst<'_, H, F> {
+    type Target = DstData<H, F>;
+
+    fn deref(&self) -> &DstData<H, F> {
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<H, F> DerefMut for PooledDst<'_, H, F> {
+    fn deref_mut(&mut self) -> &mut DstData<H, F> {
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<H: fmt::Debug, F: fmt::Debug> fmt::Debug for PooledDst<'_, H, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<H, F> Drop for PooledDst<'_, H, F> {
+    fn drop(&mut self) {
+        unsafe { drop_in_place(self.ptr.as_ptr()) };
+
+        self.pool.release(self.ptr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{assert_no_leaks, silent_panic, DropCounter};
+    use std::{
+        cell::Cell,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    #[test]
+    fn pool_reuses_allocations() {
+        let pool = DstPool::new(3);
+
+        let first = pool.alloc(1u64, &[1u16, 2, 3]);
+        let second = pool.alloc_with(2, |index| index as u16);
+        let addresses = [first.get_header() as *const u64, second.get_header()];
+        assert!(pool.free_len() == 0);
+
+        drop(first);
+        drop(second);
+        assert!(pool.free_len() == 2);
+
+        let third = pool.alloc(3, &[4, 5, 6]);
+        let fourth = pool.alloc(4, &[7, 8, 9]);
+        let reused = [third.get_header() as *const u64, fourth.get_header()];
+        assert!(reused == [addresses[1], addresses[0]]);
+        assert!(third.get_footer() == [4, 5, 6]);
+        assert!(format!("{fourth:?}") == format!("{:?}", Dst::new(4u64, &[7u16, 8, 9])));
+
+        //Taken out of the pool, so its memory is freed instead of coming back
+        let dst = third.into_dst();
+        assert!(dst.get_footer_ref() == [4, 5, 6]);
+        drop(dst);
+        assert!(pool.free_len() == 0);
+    }
+
+    #[test]
+    fn pool_drops_and_frees() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let pool = DstPool::with_max_free(1, 2);
+
+            let handles = (0..4)
+                .map(|_| pool.alloc_with(DropCounter(&drops), |_| DropCounter(&drops)))
+                .collect::<Vec<_>>();
+            drop(handles);
+
+            assert!(drops.get() == 8);
+            assert!(pool.free_len() == 2);
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                pool.alloc_with(DropCounter(&drops), |_| silent_panic())
+            }));
+            assert!(result.is_err());
+            assert!(drops.get() == 9);
+            assert!(pool.free_len() == 2);
+
+            pool.clear();
+            assert!(pool.free_len() == 0);
+
+            let _kept = pool.alloc(DropCounter(&drops), &[DropCounter(&drops)]);
+        });
+
+        assert!(drops.get() == 12);
+    }
+}
@@ -0,0 +1,283 @@
+//!Parallel iterators through `rayon`, enabled by the `rayon` feature
+//!
+//!`&DstArray`, `&mut DstArray` and the slice types implement `IntoParallelIterator`, so
+//!`par_iter` and `par_iter_mut` from rayon's prelude work on them. The iterators are indexed
+//!and split with [`DstSlice::split_at_unchecked`] and [`DstSliceMut::split_at_mut_unchecked`],
+//!the same way rayon splits a `[T]`
+//!
+//!```
+//!use custom_dst::DstArray;
+//!use rayon::prelude::*;
+//!
+//!let mut arr = DstArray::from_fn(2, 100, |index| (index, [0u32; 2]));
+//!
+//!arr.par_iter_mut().for_each(|member| {
+//!    let header = *member.get_header() as u32;
+//!    member.get_footer_mut().fill(header);
+//!});
+//!
+//!assert_eq!(arr.get_footer_ref(42), [42, 42]);
+//!```
+
+use std::alloc::Allocator;
+
+use rayon::iter::{
+    plumbing::{bridge, Consumer, Producer, ProducerCallback, UnindexedConsumer},
+    IndexedParallelIterator, IntoParallelIterator, ParallelIterator,
+};
+
+use crate::{DstArray, DstData, DstIter, DstIterMut, DstSlice, DstSliceMut};
+
+///A parallel iterator over the members of a [`DstArray`] or a [`DstSlice`]
+pub struct DstParIter<'a, H: Sized, F: Sized> {
+    slice: DstSlice<'a, H, F>,
+}
+
+impl<'a, H: Sync, F: Sync> ParallelIterator for DstParIter<'a, H, F> {
+    type Item = &'a DstData<H, F>;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.slice.len())
+    }
+}
+
+impl<H: Sync, F: Sync> IndexedParallelIterator for DstParIter<'_, H, F> {
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(DstProducer { slice: self.slice })
+    }
+}
+
+struct DstProducer<'a, H: Sized, F: Sized> {
+    slice: DstSlice<'a, H, F>,
+}
+
+impl<'a, H: Sync, F: Sync> Producer for DstProducer<'a, H, F> {
+    type Item = &'a DstData<H, F>;
+    type IntoIter = DstIter<'a, H, F>;
+
+    fn into_iter(self) -> DstIter<'a, H, F> {
+        self.slice.iter()
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        //rayon only splits within the length it was given
+        let (left, right) = unsafe { self.slice.split_at_unchecked(index) };
+
+        (DstProducer { slice: left }, DstProducer { slice: right })
+    }
+}
+
+///A parallel iterator over mutable references to the members of a [`DstArray`] or a
+///[`DstSliceMut`]
+pub struct DstParIterMut<'a, H: Sized, F: Sized> {
+    slice: DstSliceMut<'a, H, F>,
+}
+
+impl<'a, H: Send, F: Send> ParallelIterator for DstParIterMut<'a, H, F> {
+    type Item = &'a mut DstData<H, F>;
+
+    fn drive_unindexed<C: UnindexedConsumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        Some(self.slice.len())
+    }
+}
+
+impl<H: Send, F: Send> IndexedParallelIterator for DstParIterMut<'_, H, F> {
+    fn drive<C: Consumer<Self::Item>>(self, consumer: C) -> C::Result {
+        bridge(self, consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.slice.len()
+    }
+
+    fn with_producer<CB: ProducerCallback<Self::Item>>(self, callback: CB) -> CB::Output {
+        callback.callback(DstProducerMut { slice: self.slice })
+    }
+}
+
+struct DstProducerMut<'a, H: Sized, F: Sized> {
+    slice: DstSliceMut<'a, H, F>,
+}
+
+impl<'a, H: Send, F: Send> Producer for DstProducerMut<'a, H, F> {
+    type Item = &'a mut DstData<H, F>;
+    type IntoIter = DstIterMut<'a, H, F>;
+
+    fn into_iter(self) -> DstIterMut<'a, H, F> {
+        IntoIterator::into_iter(self.slice)
+    }
+
+    fn split_at(self, index: usize) -> (Self, Self) {
+        //rayon only splits within the length it was given
+        let (left, right) = unsafe { self.slice.split_at_mut_unchecked(index) };
+
+        (
+            DstProducerMut { slice: left },
+            DstProducerMut { slice: right },
+        )
+    }
+}
+
+impl<'a, H: Sync, F: Sync, A: Allocator> IntoParallelIterator for &'a DstArray<H, F, A> {
+    type Item = &'a DstData<H, F>;
+    type Iter = DstParIter<'a, H, F>;
+
+    fn into_par_iter(self) -> DstParIter<'a, H, F> {
+        self.as_slice().into_par_iter()
+    }
+}
+
+impl<'a, H: Send, F: Send, A: Allocator> IntoParallelIterator for &'a mut DstArray<H, F, A> {
+    type Item = &'a mut DstData<H, F>;
+    type Iter = DstParIterMut<'a, H, F>;
+
+    fn into_par_iter(self) -> DstParIterMut<'a, H, F> {
+        self.as_mut_slice().into_par_iter()
+    }
+}
+
+impl<'a, H: Sync, F: Sync> IntoParallelIterator for DstSlice<'a, H, F> {
+    type Item = &'a DstData<H, F>;
+    type Iter = DstParIter<'a, H, F>;
+
+    fn into_par_iter(self) -> DstParIter<'a, H, F> {
+        DstParIter { slice: self }
+    }
+}
+
+impl<'a, H: Sync, F: Sync> IntoParallelIterator for &DstSlice<'a, H, F> {
+    type Item = &'a DstData<H, F>;
+    type Iter = DstParIter<'a, H, F>;
+
+    fn into_par_iter(self) -> DstParIter<'a, H, F> {
+        DstParIter { slice: *self }
+    }
+}
+
+impl<'a, H: Send, F: Send> IntoParallelIterator for DstSliceMut<'a, H, F> {
+    type Item = &'a mut DstData<H, F>;
+    type Iter = DstParIterMut<'a, H, F>;
+
+    fn into_par_iter(self) -> DstParIterMut<'a, H, F> {
+        DstParIterMut { slice: self }
+    }
+}
+
+impl<'b, H: Sync, F: Sync> IntoParallelIterator for &'b DstSliceMut<'_, H, F> {
+    type Item = &'b DstData<H, F>;
+    type Iter = DstParIter<'b, H, F>;
+
+    fn into_par_iter(self) -> DstParIter<'b, H, F> {
+        DstParIter {
+            slice: self.as_shared(),
+        }
+    }
+}
+
+impl<'b, H: Send, F: Send> IntoParallelIterator for &'b mut DstSliceMut<'_, H, F> {
+    type Item = &'b mut DstData<H, F>;
+    type Iter = DstParIterMut<'b, H, F>;
+
+    fn into_par_iter(self) -> DstParIterMut<'b, H, F> {
+        DstParIterMut {
+            slice: self.reborrow(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        test_support::{next_random, SyncDropCounter},
+        DstArray,
+    };
+    use rayon::prelude::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn par_iter_sum() {
+        let mut state = 0x853c_49e6_748f_ea9b;
+        let arr = DstArray::from_fn(3, 1000, |_| {
+            let header = next_random(&mut state) >> 16;
+
+            (header, [header as u8; 3])
+        });
+
+        let sequential: u64 = arr.iter().map(|member| *member.get_header()).sum();
+        let parallel: u64 = arr.par_iter().map(|member| *member.get_header()).sum();
+        assert!(parallel == sequential);
+
+        //An indexed parallel iterator over part of the array, split unevenly by `zip`
+        let slice = arr.slice(100..900);
+        let pairs: Vec<_> = slice
+            .par_iter()
+            .zip(slice.par_iter().skip(1))
+            .map(|(a, b)| (*a.get_header(), *b.get_header()))
+            .collect();
+        assert!(pairs.len() == 799);
+        assert!(pairs.iter().enumerate().all(|(index, &pair)| pair
+            == (
+                *arr.get_header_ref(index + 100),
+                *arr.get_header_ref(index + 101)
+            )));
+
+        let empty = DstArray::<u64, u8>::from_fn(2, 0, |_| (0, [0; 2]));
+        assert!(empty.par_iter().count() == 0);
+    }
+
+    #[test]
+    fn par_iter_mut_footers() {
+        let dropped = AtomicUsize::new(0);
+        let mut arr = DstArray::from_fn(4, 500, |index| {
+            (index, [(); 4].map(|_| (0, SyncDropCounter(&dropped))))
+        });
+
+        arr.par_iter_mut().enumerate().for_each(|(index, member)| {
+            assert!(*member.get_header() == index);
+
+            for (position, element) in member.get_footer_mut().iter_mut().enumerate() {
+                *element = (index * 4 + position, SyncDropCounter(&dropped));
+            }
+        });
+        assert!(dropped.load(Ordering::Relaxed) == 2000);
+
+        let (front, mut back) = arr.as_mut_slice().split_at_mut(250);
+        (&mut back)
+            .into_par_iter()
+            .for_each(|member| *member.get_header_mut() += 1);
+        assert!(front
+            .into_par_iter()
+            .all(|member| *member.get_header() < 250));
+        assert!((&back)
+            .into_par_iter()
+            .all(|member| *member.get_header() > 250));
+
+        for (index, member) in arr.iter().enumerate() {
+            let footer: Vec<_> = member
+                .get_footer()
+                .iter()
+                .map(|element| element.0)
+                .collect();
+            assert!(footer == [0, 1, 2, 3].map(|position| index * 4 + position));
+        }
+
+        drop(arr);
+        assert!(dropped.load(Ordering::Relaxed) == 4000);
+    }
+}
@@ -0,0 +1,117 @@
+//!Constructors for [`DstData`]s shared through an [`Rc`]
+//!
+//!The value is built in a [`Dst`] and then moved into the `Rc`'s allocation, so accessing it
+//!afterwards takes a single pointer chase
+//!
+//![`Rc::make_mut`] works when both the header and footer are `Clone`
+
+use std::rc::Rc;
+
+use crate::{Dst, DstData};
+
+///Creates an `Rc` holding `header` and a clone of every element of `footer`
+///
+///```
+///use custom_dst::rc_dst;
+///
+///let shared = rc_dst::new(String::from("header"), &[1, 2, 3]);
+///let clone = shared.clone();
+///
+///assert_eq!(clone.get_footer(), [1, 2, 3]);
+///```
+pub fn new<H, F: Clone>(header: H, footer: &[F]) -> Rc<DstData<H, F>> {
+    Rc::from(Dst::new(header, footer).into_box())
+}
+
+///Creates an `Rc` holding `header` and the elements of `footer`, moving them rather than
+///cloning them
+pub fn from_vec<H, F>(header: H, footer: Vec<F>) -> Rc<DstData<H, F>> {
+    Rc::from(Dst::from_vec(header, footer).into_box())
+}
+
+///Creates an `Rc` holding `header` and the items of `iter`
+///
+///# Panics
+///
+///Panics if the iterator yields fewer or more items than its reported length
+#[track_caller]
+pub fn from_header_and_iter<H, F, I>(header: H, iter: I) -> Rc<DstData<H, F>>
+where
+    I: IntoIterator<Item = F>,
+    I::IntoIter: ExactSizeIterator,
+{
+    Rc::from(Dst::from_header_and_iter(header, iter).into_box())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        rc_dst,
+        test_support::{assert_no_leaks, panicking_footer},
+    };
+    use std::{
+        cell::Cell,
+        panic::{self, AssertUnwindSafe},
+        rc::Rc,
+    };
+
+    #[test]
+    fn rc_weak_upgrade() {
+        assert_no_leaks(|| {
+            let rc = rc_dst::from_header_and_iter(1u8, [String::from("a")]);
+            let weak = Rc::downgrade(&rc);
+
+            let upgraded = weak.upgrade().unwrap();
+            assert!(upgraded.get_footer() == ["a"]);
+            assert!(Rc::strong_count(&rc) == 2);
+
+            drop(upgraded);
+            drop(rc);
+
+            assert!(weak.upgrade().is_none());
+        });
+    }
+
+    #[test]
+    fn rc_get_mut_and_make_mut() {
+        assert_no_leaks(|| {
+            let mut rc = rc_dst::new(String::from("header"), &[String::from("a")]);
+
+            Rc::get_mut(&mut rc).unwrap().get_footer_mut()[0].push('b');
+
+            let shared = Rc::clone(&rc);
+            assert!(Rc::get_mut(&mut rc).is_none());
+
+            Rc::make_mut(&mut rc).get_header_mut().push('!');
+
+            assert!(rc.get_header() == "header!");
+            assert!(rc.get_footer() == ["ab"]);
+            assert!(shared.get_header() == "header");
+        });
+    }
+
+    #[test]
+    fn make_mut_clone_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+
+        //Panics on the second clone made by make_mut
+        let footer = panicking_footer(3, 4, &clones, &drops);
+
+        assert_no_leaks(|| {
+            let mut rc = rc_dst::new(String::from("header"), &footer);
+            let shared = Rc::clone(&rc);
+
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                Rc::make_mut(&mut rc);
+            }));
+
+            assert!(result.is_err());
+            assert!(drops.get() == 1);
+
+            drop((rc, shared));
+        });
+
+        assert!(drops.get() == 4);
+    }
+}
@@ -0,0 +1,187 @@
+//!Zero-copy archiving through `rkyv`, enabled by the `rkyv` feature
+//!
+//!A [`Dst`] is archived as an [`ArchivedDst`], which keeps the archived header inline and the
+//!archived footer elements behind a relative pointer, the same way `rkyv` archives a `Vec`.
+//!With the default `rkyv` features the archive is laid out as
+//!
+//!```text
+//!| out-of-line data of the header | out-of-line data of the footer elements |
+//!| [F::Archived; len] | ArchivedDst { header: H::Archived, footer: { offset: i32, len: u32 } } |
+//!```
+//!
+//!with padding for alignment between the parts. The root `ArchivedDst` is `#[repr(C)]` and
+//!ends the buffer, `offset` is the distance in bytes from the `offset` field itself to the
+//!first footer element, and both fields are little-endian. The footer elements are contiguous
+//!and aligned for `F::Archived`, so an archive can be read in place once it has been validated
+//!with [`rkyv::access`]. Deserializing an `ArchivedDst` back into a `Dst` allocates once
+
+use std::alloc::Allocator;
+
+use rkyv::{
+    munge::munge,
+    rancor::Fallible,
+    ser::{Allocator as SerAllocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Deserialize, Place, Portable, Serialize,
+};
+
+use crate::{Dst, MaybeUninitDst};
+
+///The archived form of a [`Dst`] with an `H` header and an `F` footer
+///
+///```
+///use custom_dst::{ArchivedDst, Dst};
+///use rkyv::{rancor::Error, string::ArchivedString, Archived};
+///
+///let dst = Dst::new(String::from("header"), &[1u32, 2, 3]);
+///let bytes = rkyv::to_bytes::<Error>(&dst).unwrap();
+///
+///let archived =
+///    rkyv::access::<ArchivedDst<ArchivedString, Archived<u32>>, Error>(&bytes).unwrap();
+///assert_eq!(archived.get_header(), "header");
+///assert_eq!(archived.get_footer(), [1, 2, 3]);
+///
+///let back: Dst<String, u32> = rkyv::deserialize::<_, Error>(archived).unwrap();
+///assert_eq!(back, dst);
+///```
+#[derive(Portable, rkyv::bytecheck::CheckBytes)]
+#[bytecheck(crate = rkyv::bytecheck)]
+#[repr(C)]
+pub struct ArchivedDst<H, F> {
+    header: H,
+    footer: ArchivedVec<F>,
+}
+
+impl<H, F> ArchivedDst<H, F> {
+    pub fn get_header(&self) -> &H {
+        &self.header
+    }
+
+    pub fn get_footer(&self) -> &[F] {
+        self.footer.as_slice()
+    }
+
+    pub fn get_footer_len(&self) -> usize {
+        self.footer.len()
+    }
+}
+
+impl<H: Archive, F: Archive, A: Allocator> Archive for Dst<H, F, A> {
+    type Archived = ArchivedDst<H::Archived, F::Archived>;
+    type Resolver = (H::Resolver, VecResolver);
+
+    fn resolve(&self, (header, footer): Self::Resolver, out: Place<Self::Archived>) {
+        munge!(let ArchivedDst { header: out_header, footer: out_footer } = out);
+
+        self.get_header_ref().resolve(header, out_header);
+        ArchivedVec::resolve_from_slice(self.get_footer_ref(), footer, out_footer);
+    }
+}
+
+impl<H, F, A, S> Serialize<S> for Dst<H, F, A>
+where
+    H: Serialize<S>,
+    F: Serialize<S>,
+    A: Allocator,
+    S: Fallible + SerAllocator + Writer + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        Ok((
+            self.get_header_ref().serialize(serializer)?,
+            ArchivedVec::serialize_from_slice(self.get_footer_ref(), serializer)?,
+        ))
+    }
+}
+
+///Allocates the Dst once and deserializes the footer elements straight into it. If an element
+///fails to deserialize, the header and the elements deserialized so far are dropped
+impl<H, F, D> Deserialize<Dst<H, F>, D> for ArchivedDst<H::Archived, F::Archived>
+where
+    H: Archive,
+    F: Archive,
+    H::Archived: Deserialize<H, D>,
+    F::Archived: Deserialize<F, D>,
+    D: Fallible + ?Sized,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<Dst<H, F>, D::Error> {
+        let header = self.header.deserialize(deserializer)?;
+        let footer = self.get_footer();
+
+        let mut dst = MaybeUninitDst::new(footer.len());
+        dst.element_mut()
+            .try_write_footer_with(|index| footer[index].deserialize(deserializer))?;
+        dst.write_header(header);
+
+        Ok(unsafe { dst.assume_init() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::assert_no_leaks;
+    use rkyv::{rancor::Error, string::ArchivedString, Archived};
+
+    type ArchivedRecord = ArchivedDst<ArchivedString, ArchivedString>;
+
+    fn record() -> Dst<String, String> {
+        Dst::from_vec(
+            String::from("header"),
+            vec![String::from("a"), String::from("bc"), String::from("def")],
+        )
+    }
+
+    #[test]
+    fn rkyv_round_trip() {
+        //Serializing sets up an arena for the thread that is kept around afterwards
+        let _ = rkyv::to_bytes::<Error>(&record()).unwrap();
+
+        assert_no_leaks(|| {
+            let dst = record();
+            let bytes = rkyv::to_bytes::<Error>(&dst).unwrap();
+
+            let archived = rkyv::access::<ArchivedRecord, Error>(&bytes).unwrap();
+            assert!(archived.get_header() == "header");
+            assert!(archived.get_footer_len() == 3);
+            assert!(archived.get_footer()[2] == "def");
+
+            let back: Dst<String, String> = rkyv::deserialize::<_, Error>(archived).unwrap();
+            assert!(back == dst);
+        });
+
+        let empty = Dst::<u64, u16>::new(7, &[]);
+        let bytes = rkyv::to_bytes::<Error>(&empty).unwrap();
+        let archived =
+            rkyv::access::<ArchivedDst<Archived<u64>, Archived<u16>>, Error>(&bytes).unwrap();
+        assert!(*archived.get_header() == 7);
+        assert!(archived.get_footer().is_empty());
+
+        let back: Dst<u64, u16> = rkyv::deserialize::<_, Error>(archived).unwrap();
+        assert!(back == empty);
+    }
+
+    #[test]
+    fn rkyv_validation_failure() {
+        let bytes = rkyv::to_bytes::<Error>(&record()).unwrap();
+
+        for end in 0..bytes.len() {
+            let mut truncated = rkyv::util::AlignedVec::<16>::new();
+            truncated.extend_from_slice(&bytes[..end]);
+
+            assert!(rkyv::access::<ArchivedRecord, Error>(&truncated).is_err());
+        }
+
+        //The footer length is the last field of the root, which ends the buffer
+        let mut long_footer = bytes.clone();
+        let len = long_footer.len();
+        long_footer[len - 4..].copy_from_slice(&1000u32.to_le_bytes());
+        assert!(rkyv::access::<ArchivedRecord, Error>(&long_footer).is_err());
+
+        let dst = Dst::new((), &[String::from("abc")]);
+        let mut bytes = rkyv::to_bytes::<Error>(&dst).unwrap();
+        let position = bytes.iter().position(|&byte| byte == b'b').unwrap();
+        bytes[position] = 0x80;
+        assert!(rkyv::access::<ArchivedDst<(), ArchivedString>, Error>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,366 @@
+//!`Serialize` and `Deserialize` implementations, enabled by the `serde` feature
+//!
+//!A [`DstData`] is serialized as a struct named `DstData` with a `header` field and a `footer`
+//!field holding the footer as a sequence. Deserializing a [`Dst`] reads the length of the footer
+//!from the sequence and allocates once, writing each element as it is deserialized. Formats that
+//!don't record the length of a sequence, such as JSON, collect the footer into a `Vec` first
+
+use std::{alloc::Allocator, fmt, marker::PhantomData, mem};
+
+use serde::{
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{Dst, DstData, MaybeUninitDst};
+
+const FIELDS: &[&str] = &["header", "footer"];
+
+///The number of bytes a deserialized footer may take up unless a [`DstSeed`] says otherwise
+pub const DEFAULT_MAX_FOOTER_BYTES: usize = 1 << 20;
+
+impl<H: Serialize, F: Serialize> Serialize for DstData<H, F> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("DstData", 2)?;
+        state.serialize_field("header", self.get_header())?;
+        state.serialize_field("footer", self.get_footer())?;
+        state.end()
+    }
+}
+
+impl<H: Serialize, F: Serialize, A: Allocator> Serialize for Dst<H, F, A> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.get_data().serialize(serializer)
+    }
+}
+
+///Deserializes a footer of up to [`DEFAULT_MAX_FOOTER_BYTES`], see [`DstSeed`] for other limits
+impl<'de, H: Deserialize<'de>, F: Deserialize<'de>> Deserialize<'de> for Dst<H, F> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        DstSeed::new().deserialize(deserializer)
+    }
+}
+
+///Deserializes a [`Dst`] whose footer has at most a chosen number of elements
+///
+///The limit is checked against the length the input claims before anything is allocated, so
+///untrusted input can't make the deserializer allocate more than the limit allows
+///
+///```
+///use custom_dst::{Dst, DstSeed};
+///use serde::de::DeserializeSeed;
+///
+///let mut deserializer = serde_json::Deserializer::from_str(r#"{"header":1,"footer":[1,2,3]}"#);
+///let result = DstSeed::<u8, u8>::with_max_footer_len(2).deserialize(&mut deserializer);
+///
+///assert!(result.is_err());
+///```
+pub struct DstSeed<H, F> {
+    max_footer_len: usize,
+    phantom: PhantomData<fn() -> Dst<H, F>>,
+}
+
+impl<H, F> DstSeed<H, F> {
+    ///Accepts footers taking up to [`DEFAULT_MAX_FOOTER_BYTES`]
+    pub fn new() -> Self {
+        Self::with_max_footer_len(DEFAULT_MAX_FOOTER_BYTES / mem::size_of::<F>().max(1))
+    }
+
+    ///Accepts footers of up to `max_footer_len` elements
+    pub fn with_max_footer_len(max_footer_len: usize) -> Self {
+        DstSeed {
+            max_footer_len,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<H, F> Default for DstSeed<H, F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H, F> Clone for DstSeed<H, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H, F> Copy for DstSeed<H, F> {}
+
+impl<'de, H: Deserialize<'de>, F: Deserialize<'de>> DeserializeSeed<'de> for DstSeed<H, F> {
+    type Value = Dst<H, F>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct("DstData", FIELDS, self)
+    }
+}
+
+impl<'de, H: Deserialize<'de>, F: Deserialize<'de>> Visitor<'de> for DstSeed<H, F> {
+    type Value = Dst<H, F>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("struct DstData")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let header = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+
+        seq.next_element_seed(FooterSeed {
+            header,
+            max_footer_len: self.max_footer_len,
+            phantom: PhantomData,
+        })?
+        .ok_or_else(|| de::Error::invalid_length(1, &self))
+    }
+
+    fn visit_map<M: MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+        let mut header = None;
+        let mut dst = None;
+
+        while let Some(field) = map.next_key()? {
+            match field {
+                Field::Header => {
+                    if header.is_some() || dst.is_some() {
+                        return Err(de::Error::duplicate_field("header"));
+                    }
+
+                    header = Some(map.next_value()?);
+                }
+                Field::Footer => {
+                    if dst.is_some() {
+                        return Err(de::Error::duplicate_field("footer"));
+                    }
+
+                    let Some(header) = header.take() else {
+                        return Err(de::Error::custom(
+                            "the header of a Dst must come before its footer",
+                        ));
+                    };
+
+                    dst = Some(map.next_value_seed(FooterSeed {
+                        header,
+                        max_footer_len: self.max_footer_len,
+                        phantom: PhantomData,
+                    })?);
+                }
+            }
+        }
+
+        match (dst, header) {
+            (Some(dst), _) => Ok(dst),
+            (None, Some(_)) => Err(de::Error::missing_field("footer")),
+            (None, None) => Err(de::Error::missing_field("header")),
+        }
+    }
+}
+
+enum Field {
+    Header,
+    Footer,
+}
+
+impl<'de> Deserialize<'de> for Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct FieldVisitor;
+
+impl Visitor<'_> for FieldVisitor {
+    type Value = Field;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("`header` or `footer`")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Field, E> {
+        match value {
+            0 => Ok(Field::Header),
+            1 => Ok(Field::Footer),
+            _ => Err(de::Error::invalid_value(
+                de::Unexpected::Unsigned(value),
+                &self,
+            )),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+        match value {
+            "header" => Ok(Field::Header),
+            "footer" => Ok(Field::Footer),
+            _ => Err(de::Error::unknown_field(value, FIELDS)),
+        }
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Field, E> {
+        match value {
+            b"header" => Ok(Field::Header),
+            b"footer" => Ok(Field::Footer),
+            _ => Err(de::Error::unknown_field(
+                &String::from_utf8_lossy(value),
+                FIELDS,
+            )),
+        }
+    }
+}
+
+///Deserializes the footer of a Dst whose header was already deserialized
+struct FooterSeed<H, F> {
+    header: H,
+    max_footer_len: usize,
+    phantom: PhantomData<fn() -> F>,
+}
+
+impl<'de, H, F: Deserialize<'de>> DeserializeSeed<'de> for FooterSeed<H, F> {
+    type Value = Dst<H, F>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, H, F: Deserialize<'de>> Visitor<'de> for FooterSeed<H, F> {
+    type Value = Dst<H, F>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "a footer of at most {} elements",
+            self.max_footer_len
+        )
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let Some(len) = seq.size_hint() else {
+            let mut footer = Vec::new();
+
+            while let Some(element) = seq.next_element()? {
+                if footer.len() == self.max_footer_len {
+                    return Err(de::Error::invalid_length(footer.len() + 1, &self));
+                }
+
+                footer.push(element);
+            }
+
+            return Ok(Dst::from_vec(self.header, footer));
+        };
+
+        if len > self.max_footer_len {
+            return Err(de::Error::invalid_length(len, &self));
+        }
+
+        let expected = format!("a footer of {len} elements");
+        let mut dst = MaybeUninitDst::try_new(len).map_err(de::Error::custom)?;
+
+        dst.element_mut().try_write_footer_with(|index| {
+            seq.next_element()?
+                .ok_or_else(|| de::Error::invalid_length(index, &expected.as_str()))
+        })?;
+        dst.write_header(self.header);
+
+        let dst = unsafe { dst.assume_init() };
+
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(len + 1, &expected.as_str()));
+        }
+
+        Ok(dst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::assert_no_leaks;
+
+    #[test]
+    fn serde_json_round_trip() {
+        let dst = Dst::new(String::from("header"), &[1u32, 2, 3]);
+
+        let json = serde_json::to_string(&dst).unwrap();
+        assert!(json == r#"{"header":"header","footer":[1,2,3]}"#);
+
+        let back: Dst<String, u32> = serde_json::from_str(&json).unwrap();
+        assert!(back == dst);
+
+        let from_seq: Dst<String, u32> = serde_json::from_str(r#"["header",[1,2,3]]"#).unwrap();
+        assert!(from_seq == dst);
+
+        let empty: Dst<(), u8> = serde_json::from_str(r#"{"header":null,"footer":[]}"#).unwrap();
+        assert!(empty.get_footer_len() == 0);
+    }
+
+    #[test]
+    fn postcard_round_trip() {
+        let dst = Dst::new(7u16, &[String::from("a"), String::from("bc")]);
+
+        let bytes = postcard::to_allocvec(&dst).unwrap();
+        let back: Dst<u16, String> = postcard::from_bytes(&bytes).unwrap();
+
+        assert!(back == dst);
+    }
+
+    #[test]
+    fn serde_json_errors() {
+        assert_no_leaks(|| {
+            for json in [
+                r#"{"header":"h","footer":["a","b",3]}"#,
+                r#"{"header":"h","footer":["a","b"],"footer":["c"]}"#,
+                r#"{"footer":["a","b"],"header":"h"}"#,
+                r#"{"header":"h"}"#,
+                r#"{"header":"h","footer":["a"],"extra":1}"#,
+                r#"["h"]"#,
+            ] {
+                assert!(serde_json::from_str::<Dst<String, String>>(json).is_err());
+            }
+        });
+    }
+
+    #[test]
+    fn postcard_truncated_footer() {
+        let dst = Dst::new(
+            String::from("header"),
+            &[String::from("a"), String::from("b")],
+        );
+        let bytes = postcard::to_allocvec(&dst).unwrap();
+
+        assert_no_leaks(|| {
+            for end in 0..bytes.len() {
+                assert!(postcard::from_bytes::<Dst<String, String>>(&bytes[..end]).is_err());
+            }
+        });
+    }
+
+    #[test]
+    fn footer_len_limit() {
+        assert_no_leaks(|| {
+            //A header of 0 followed by a footer claiming u32::MAX elements
+            let hostile = [0, 0xff, 0xff, 0xff, 0xff, 0x0f];
+            assert!(postcard::from_bytes::<Dst<u8, u64>>(&hostile).is_err());
+            assert!(postcard::from_bytes::<Dst<u8, ()>>(&hostile).is_err());
+
+            let dst = Dst::new(0u8, &[0u8; 3]);
+            let bytes = postcard::to_allocvec(&dst).unwrap();
+            let json = serde_json::to_string(&dst).unwrap();
+
+            let seed = DstSeed::<u8, u8>::with_max_footer_len(3);
+            let mut deserializer = postcard::Deserializer::from_bytes(&bytes);
+            assert!(seed.deserialize(&mut deserializer).is_ok());
+            let mut deserializer = serde_json::Deserializer::from_str(&json);
+            assert!(seed.deserialize(&mut deserializer).is_ok());
+
+            let seed = DstSeed::<u8, u8>::with_max_footer_len(2);
+            let mut deserializer = postcard::Deserializer::from_bytes(&bytes);
+            assert!(seed.deserialize(&mut deserializer).is_err());
+            let mut deserializer = serde_json::Deserializer::from_str(&json);
+            assert!(seed.deserialize(&mut deserializer).is_err());
+        });
+    }
+}
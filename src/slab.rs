@@ -0,0 +1,492 @@
+//!Stable, generation-checked keys to Dsts that share a footer length
+
+use std::{
+    alloc::Global,
+    clone::CloneToUninit,
+    cmp,
+    iter::FusedIterator,
+    marker::PhantomData,
+    ptr::{self, drop_in_place, NonNull},
+};
+
+use crate::{DstData, MaybeUninitDstArray};
+
+///The number of slots a slab grows to the first time it allocates
+const MIN_CAPACITY: usize = 4;
+
+///Identifies a value inserted into a [`DstSlab`]
+///
+///A key stops matching once its value is removed, even if the slot is reused by a later
+///insertion
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    index: usize,
+    generation: u32,
+}
+
+///Stores Dsts with the same footer length in reusable slots, handing out a [`Key`] for each
+///that stays valid until it is removed
+///
+///```
+///use custom_dst::slab::DstSlab;
+///
+///let mut slab = DstSlab::new(2);
+///
+///let first = slab.insert('a', &[1, 2]);
+///let second = slab.insert('b', &[3, 4]);
+///
+///assert_eq!(slab.remove(first), Some(('a', vec![1, 2])));
+///
+///let third = slab.insert('c', &[5, 6]);
+///
+///assert!(slab.get(first).is_none());
+///assert_eq!(slab.get(second).unwrap().get_footer(), [3, 4]);
+///assert_eq!(*slab.get(third).unwrap().get_header(), 'c');
+///```
+pub struct DstSlab<H, F> {
+    ///Every slot, whether it holds a value or not. Dropping it only frees the memory
+    storage: MaybeUninitDstArray<H, F>,
+    ///One bit per slot that has been used, set if the slot holds a value
+    occupied: Vec<u64>,
+    ///The generation of every slot that has been used, bumped whenever its value is removed
+    generations: Vec<u32>,
+    ///The slots whose value was removed, to be reused before any new slot
+    vacant: Vec<usize>,
+    len: usize,
+}
+
+impl<H, F> DstSlab<H, F> {
+    ///Creates an empty slab whose values will have `footer_len` footer elements
+    pub fn new(footer_len: usize) -> DstSlab<H, F> {
+        Self::with_capacity(footer_len, 0)
+    }
+
+    ///Creates an empty slab with room for `capacity` values of `footer_len` footer elements
+    pub fn with_capacity(footer_len: usize, capacity: usize) -> DstSlab<H, F> {
+        DstSlab {
+            storage: Self::new_storage(footer_len, capacity),
+            occupied: Vec::new(),
+            generations: Vec::new(),
+            vacant: Vec::new(),
+            len: 0,
+        }
+    }
+
+    fn new_storage(footer_len: usize, capacity: usize) -> MaybeUninitDstArray<H, F> {
+        //Slots are written and read many times over, which the tracker can't follow
+        MaybeUninitDstArray::new_untracked_in(footer_len, capacity, Global)
+    }
+
+    ///Returns the number of values in the slab
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///Returns the number of values the slab can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.storage.len
+    }
+
+    ///Returns the number of footer elements of every value
+    pub fn get_footer_len(&self) -> usize {
+        unsafe { DstData::get_len(self.storage.ptr.as_ptr()) }
+    }
+
+    fn is_occupied(&self, index: usize) -> bool {
+        self.occupied[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn set_occupied(&mut self, index: usize, occupied: bool) {
+        let bit = 1 << (index % 64);
+
+        if occupied {
+            self.occupied[index / 64] |= bit;
+        } else {
+            self.occupied[index / 64] &= !bit;
+        }
+    }
+
+    ///Returns `true` if `key` refers to a value in the slab
+    pub fn contains_key(&self, key: Key) -> bool {
+        self.generations.get(key.index) == Some(&key.generation) && self.is_occupied(key.index)
+    }
+
+    ///Moves every slot into an allocation with at least one more slot
+    fn grow(&mut self) {
+        let capacity = cmp::max(self.storage.len.saturating_mul(2), MIN_CAPACITY);
+        let storage = Self::new_storage(self.get_footer_len(), capacity);
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.storage.ptr.cast::<u8>().as_ptr(),
+                storage.ptr.cast::<u8>().as_ptr(),
+                self.storage.stride * self.storage.len,
+            );
+        }
+
+        self.storage = storage;
+    }
+
+    ///Inserts a value holding `header` and a clone of every element of `footer`, returning
+    ///its key
+    ///
+    ///If a clone panics, the elements cloned so far and `header` are dropped and the slab is
+    ///left unchanged
+    ///
+    ///# Panics
+    ///
+    ///Panics if `footer` doesn't have exactly [`DstSlab::get_footer_len`] elements
+    #[track_caller]
+    pub fn insert(&mut self, header: H, footer: &[F]) -> Key
+    where
+        F: Clone,
+    {
+        let footer_len = self.get_footer_len();
+        assert!(
+            footer.len() == footer_len,
+            "the footer has {} elements but the slab's footers have {footer_len}",
+            footer.len()
+        );
+
+        let index = match self.vacant.last() {
+            Some(&index) => index,
+            None => {
+                if self.generations.len() == self.storage.len {
+                    self.grow();
+                }
+                self.generations.reserve(1);
+                self.occupied.reserve(1);
+
+                self.generations.len()
+            }
+        };
+
+        unsafe {
+            let ptr = self.storage.get_element(index).as_ptr();
+
+            footer.clone_to_uninit(DstData::get_footer_slice(ptr).as_mut_ptr().cast::<u8>());
+            DstData::get_header_ptr(ptr).write(header);
+        }
+
+        if index == self.generations.len() {
+            self.generations.push(0);
+            if index % 64 == 0 {
+                self.occupied.push(0);
+            }
+        } else {
+            self.vacant.pop();
+        }
+
+        self.set_occupied(index, true);
+        self.len += 1;
+
+        Key {
+            index,
+            generation: self.generations[index],
+        }
+    }
+
+    ///Removes the value `key` refers to, returning its header and a Vec holding its footer
+    ///elements, or `None` if it was already removed
+    pub fn remove(&mut self, key: Key) -> Option<(H, Vec<F>)> {
+        if !self.contains_key(key) {
+            return None;
+        }
+
+        self.vacant.reserve(1);
+        let parts = unsafe { DstData::read_parts(self.storage.get_element(key.index).as_ptr()) };
+
+        self.vacate(key.index);
+
+        Some(parts)
+    }
+
+    ///Marks the value in slot `index` as removed without dropping it, so that its keys stop
+    ///matching
+    ///
+    ///`vacant` must have room for one more slot
+    fn vacate(&mut self, index: usize) {
+        self.set_occupied(index, false);
+        self.len -= 1;
+
+        //A slot whose generation would wrap is retired, so that no old key can match it again
+        if let Some(generation) = self.generations[index].checked_add(1) {
+            self.generations[index] = generation;
+            self.vacant.push(index);
+        }
+    }
+
+    ///Returns the value `key` refers to, or `None` if it was removed
+    pub fn get(&self, key: Key) -> Option<&DstData<H, F>> {
+        if !self.contains_key(key) {
+            return None;
+        }
+
+        Some(unsafe { self.storage.get_element(key.index).as_ref() })
+    }
+
+    ///Returns the value `key` refers to mutably, or `None` if it was removed
+    pub fn get_mut(&mut self, key: Key) -> Option<&mut DstData<H, F>> {
+        if !self.contains_key(key) {
+            return None;
+        }
+
+        Some(unsafe { self.storage.get_element(key.index).as_mut() })
+    }
+
+    ///Drops every value, keeping the allocation
+    ///
+    ///Every key handed out so far stops matching
+    pub fn clear(&mut self) {
+        self.vacant.reserve(self.len);
+
+        for index in 0..self.generations.len() {
+            if self.is_occupied(index) {
+                //Leaks the value rather than dropping it twice if its destructor panics
+                self.vacate(index);
+
+                unsafe { drop_in_place(self.storage.get_element(index).as_ptr()) };
+            }
+        }
+    }
+
+    ///Returns an iterator over the keys and values in the slab, in slot order
+    pub fn iter(&self) -> DstSlabIter<'_, H, F> {
+        DstSlabIter {
+            slots: SlotCursor::new(self),
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns an iterator over the keys and mutable references to the values in the slab, in
+    ///slot order
+    pub fn iter_mut(&mut self) -> DstSlabIterMut<'_, H, F> {
+        DstSlabIterMut {
+            slots: SlotCursor::new(self),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<H, F> Drop for DstSlab<H, F> {
+    fn drop(&mut self) {
+        for index in 0..self.generations.len() {
+            if self.is_occupied(index) {
+                unsafe { drop_in_place(self.storage.get_element(index).as_ptr()) };
+            }
+        }
+    }
+}
+
+unsafe impl<H: Send, F: Send> Send for DstSlab<H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstSlab<H, F> {}
+
+///Walks the occupied slots of a slab that outlives it
+struct SlotCursor<H, F> {
+    start: NonNull<DstData<H, F>>,
+    stride: usize,
+    occupied: NonNull<[u64]>,
+    generations: NonNull<[u32]>,
+    ///The first slot that hasn't been visited
+    index: usize,
+    remaining: usize,
+}
+
+impl<H, F> SlotCursor<H, F> {
+    fn new(slab: &DstSlab<H, F>) -> Self {
+        SlotCursor {
+            start: slab.storage.ptr,
+            stride: slab.storage.stride,
+            occupied: NonNull::from(slab.occupied.as_slice()),
+            generations: NonNull::from(slab.generations.as_slice()),
+            index: 0,
+            remaining: slab.len,
+        }
+    }
+
+    fn next(&mut self) -> Option<(Key, NonNull<DstData<H, F>>)> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let occupied = unsafe { self.occupied.as_ref() };
+
+        //Skips whole words of vacant slots, and there is an occupied slot left to find
+        let index = loop {
+            let word = occupied[self.index / 64] >> (self.index % 64);
+
+            if word != 0 {
+                break self.index + word.trailing_zeros() as usize;
+            }
+
+            self.index = (self.index / 64 + 1) * 64;
+        };
+
+        self.index = index + 1;
+        self.remaining -= 1;
+
+        let key = Key {
+            index,
+            generation: unsafe { self.generations.as_ref() }[index],
+        };
+
+        Some((key, unsafe { self.start.byte_add(self.stride * index) }))
+    }
+}
+
+///An iterator over the keys and values of a [`DstSlab`]
+pub struct DstSlabIter<'a, H, F> {
+    slots: SlotCursor<H, F>,
+    phantom: PhantomData<&'a DstSlab<H, F>>,
+}
+
+impl<'a, H, F> Iterator for DstSlabIter<'a, H, F> {
+    type Item = (Key, &'a DstData<H, F>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, ptr) = self.slots.next()?;
+
+        Some((key, unsafe { ptr.as_ref() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slots.remaining, Some(self.slots.remaining))
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstSlabIter<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstSlabIter<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Send for DstSlabIter<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstSlabIter<'_, H, F> {}
+
+///An iterator over the keys and mutable references to the values of a [`DstSlab`]
+pub struct DstSlabIterMut<'a, H, F> {
+    slots: SlotCursor<H, F>,
+    phantom: PhantomData<&'a mut DstSlab<H, F>>,
+}
+
+impl<'a, H, F> Iterator for DstSlabIterMut<'a, H, F> {
+    type Item = (Key, &'a mut DstData<H, F>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, mut ptr) = self.slots.next()?;
+
+        Some((key, unsafe { ptr.as_mut() }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.slots.remaining, Some(self.slots.remaining))
+    }
+}
+
+impl<H, F> ExactSizeIterator for DstSlabIterMut<'_, H, F> {}
+
+impl<H, F> FusedIterator for DstSlabIterMut<'_, H, F> {}
+
+unsafe impl<H: Send, F: Send> Send for DstSlabIterMut<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstSlabIterMut<'_, H, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{assert_no_leaks, next_random, DropCounter};
+    use std::{cell::Cell, collections::HashMap};
+
+    #[test]
+    fn slab_interleaved() {
+        let mut slab = DstSlab::new(2);
+        let mut model = HashMap::new();
+        let mut stale = Vec::new();
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        let steps: u32 = if cfg!(miri) { 200 } else { 2000 };
+
+        for step in 0..steps {
+            let keys = model.keys().copied().collect::<Vec<_>>();
+
+            if keys.is_empty() || !next_random(&mut state).is_multiple_of(3) {
+                let footer = [step as u16, (step >> 16) as u16];
+                let key = slab.insert(step, &footer);
+
+                assert!(model.insert(key, (step, footer)).is_none());
+            } else {
+                let key = keys[next_random(&mut state) as usize % keys.len()];
+                let (header, footer) = model.remove(&key).unwrap();
+
+                assert!(slab.remove(key) == Some((header, footer.to_vec())));
+                stale.push(key);
+            }
+
+            assert!(slab.len() == model.len());
+        }
+
+        for &key in &stale {
+            assert!(!slab.contains_key(key));
+            assert!(slab.get(key).is_none());
+            assert!(slab.get_mut(key).is_none());
+            assert!(slab.remove(key).is_none());
+        }
+
+        for (key, (header, footer)) in &model {
+            let value = slab.get(*key).unwrap();
+
+            assert!(value.get_header() == header);
+            assert!(value.get_footer() == footer);
+        }
+
+        assert!(slab.iter().len() == model.len());
+        for (key, value) in slab.iter() {
+            assert!(*value.get_header() == model[&key].0);
+        }
+
+        for (_, value) in slab.iter_mut() {
+            value.get_footer_mut()[0] = 0;
+        }
+        assert!(slab.iter().all(|(_, value)| value.get_footer()[0] == 0));
+
+        let keys = model.keys().copied().collect::<Vec<_>>();
+        slab.clear();
+        assert!(slab.is_empty());
+        assert!(slab.iter().next().is_none());
+        assert!(keys.iter().all(|&key| slab.get(key).is_none()));
+    }
+
+    #[test]
+    fn slab_drops() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut slab = DstSlab::new(1);
+
+            let keys = (0..6)
+                .map(|_| slab.insert(DropCounter(&drops), &[DropCounter(&drops)]))
+                .collect::<Vec<_>>();
+            assert!(drops.get() == 6);
+
+            drop(slab.remove(keys[2]));
+            assert!(drops.get() == 8);
+
+            let reused = slab.insert(DropCounter(&drops), &[DropCounter(&drops)]);
+            assert!(reused != keys[2]);
+            assert!(slab.get(keys[2]).is_none());
+            assert!(slab.get(reused).is_some());
+            assert!(slab.capacity() == 8);
+
+            slab.clear();
+            assert!(drops.get() == 21);
+
+            slab.insert(DropCounter(&drops), &[DropCounter(&drops)]);
+        });
+
+        assert!(drops.get() == 24);
+    }
+}
@@ -0,0 +1,261 @@
+//!A [`Dst`] that keeps short footers inline instead of on the heap
+
+use std::{
+    fmt,
+    mem::{ManuallyDrop, MaybeUninit},
+    ptr::{self, drop_in_place},
+};
+
+use crate::{Dst, DstData, PrefixGuard};
+
+///An owned header and footer that are stored inline when the footer has at most `N`
+///elements, and in a heap allocated [`Dst`] otherwise
+///
+///```
+///use custom_dst::small::SmallDst;
+///
+///let short = SmallDst::<u32, u16, 4>::new(1, &[2, 3]);
+///let long = SmallDst::<u32, u16, 4>::new(1, &[2, 3, 4, 5, 6]);
+///
+///assert!(short.is_inline());
+///assert!(!long.is_inline());
+///assert_eq!(short.get_footer_ref(), [2, 3]);
+///assert_eq!(long.get_footer_ref(), [2, 3, 4, 5, 6]);
+///```
+pub struct SmallDst<H, F, const N: usize> {
+    ///The footer length, which is greater than `N` exactly when `data` holds a Dst
+    len: usize,
+    data: SmallData<H, F, N>,
+}
+
+union SmallData<H, F, const N: usize> {
+    inline: ManuallyDrop<Inline<H, F, N>>,
+    heap: ManuallyDrop<Dst<H, F>>,
+}
+
+///Laid out like a [`DstData`] with `N` footer elements, so that a prefix of it can be viewed as
+///one
+#[repr(C)]
+struct Inline<H, F, const N: usize> {
+    header: H,
+    footer: [MaybeUninit<F>; N],
+}
+
+impl<H, F, const N: usize> SmallDst<H, F, N> {
+    ///Creates a value holding `header` and a footer of `len` elements, where element `index`
+    ///is `f(index)`
+    ///
+    ///If `f` panics, the header and the elements written so far are dropped
+    pub fn new_with(header: H, len: usize, mut f: impl FnMut(usize) -> F) -> SmallDst<H, F, N> {
+        if len > N {
+            return SmallDst {
+                len,
+                data: SmallData {
+                    heap: ManuallyDrop::new(Dst::new_with(header, len, f)),
+                },
+            };
+        }
+
+        let mut footer = [const { MaybeUninit::uninit() }; N];
+
+        let mut guard = PrefixGuard::new(footer.as_mut_ptr().cast::<F>());
+        for index in 0..len {
+            unsafe { guard.push(f(index)) };
+        }
+        guard.finish();
+
+        SmallDst {
+            len,
+            data: SmallData {
+                inline: ManuallyDrop::new(Inline { header, footer }),
+            },
+        }
+    }
+
+    ///Creates a value holding `header` and a clone of every element of `footer`
+    pub fn new(header: H, footer: &[F]) -> SmallDst<H, F, N>
+    where
+        F: Clone,
+    {
+        if footer.len() > N {
+            return SmallDst {
+                len: footer.len(),
+                data: SmallData {
+                    heap: ManuallyDrop::new(Dst::new(header, footer)),
+                },
+            };
+        }
+
+        Self::new_with(header, footer.len(), |index| footer[index].clone())
+    }
+
+    ///Returns `true` if the header and footer are stored inline rather than on the heap
+    pub fn is_inline(&self) -> bool {
+        self.len <= N
+    }
+
+    ///Returns the value as a [`DstData`] reference
+    pub fn get_data(&self) -> &DstData<H, F> {
+        unsafe {
+            if self.is_inline() {
+                &*ptr::from_raw_parts(&self.data.inline as *const _ as *const (), self.len)
+            } else {
+                self.data.heap.ptr.as_ref()
+            }
+        }
+    }
+
+    ///Returns the value as a mutable [`DstData`] reference
+    pub fn get_data_mut(&mut self) -> &mut DstData<H, F> {
+        unsafe {
+            if self.is_inline() {
+                &mut *ptr::from_raw_parts_mut(&mut self.data.inline as *mut _ as *mut (), self.len)
+            } else {
+                let mut ptr = self.data.heap.ptr;
+                ptr.as_mut()
+            }
+        }
+    }
+
+    pub fn get_header_ref(&self) -> &H {
+        self.get_data().get_header()
+    }
+
+    pub fn get_header_ref_mut(&mut self) -> &mut H {
+        self.get_data_mut().get_header_mut()
+    }
+
+    pub fn get_footer_ref(&self) -> &[F] {
+        self.get_data().get_footer()
+    }
+
+    pub fn get_footer_ref_mut(&mut self) -> &mut [F] {
+        self.get_data_mut().get_footer_mut()
+    }
+
+    pub fn get_footer_len(&self) -> usize {
+        self.len
+    }
+
+    ///Converts the value into a heap allocated Dst, which only allocates if it was inline
+    pub fn into_dst(self) -> Dst<H, F> {
+        let mut this = ManuallyDrop::new(self);
+
+        unsafe {
+            if this.is_inline() {
+                let inline = ManuallyDrop::take(&mut this.data.inline);
+                let mut footer = inline.footer.into_iter().take(this.len);
+
+                Dst::new_with(inline.header, this.len, |_| {
+                    footer.next().unwrap_unchecked().assume_init()
+                })
+            } else {
+                ManuallyDrop::take(&mut this.data.heap)
+            }
+        }
+    }
+}
+
+impl<H, F, const N: usize> From<SmallDst<H, F, N>> for Dst<H, F> {
+    fn from(value: SmallDst<H, F, N>) -> Self {
+        value.into_dst()
+    }
+}
+
+impl<H: fmt::Debug, F: fmt::Debug, const N: usize> fmt::Debug for SmallDst<H, F, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmallDst")
+            .field("header", self.get_header_ref())
+            .field("footer", &self.get_footer_ref())
+            .finish()
+    }
+}
+
+impl<H, F, const N: usize> Drop for SmallDst<H, F, N> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.is_inline() {
+                drop_in_place(self.get_data_mut());
+            } else {
+                ManuallyDrop::drop(&mut self.data.heap);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{assert_no_leaks, panicking_footer, DropCounter};
+    use std::{
+        cell::Cell,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    #[test]
+    fn small_dst_size() {
+        type Small = SmallDst<u32, u16, 6>;
+
+        //The inline value is laid out like a DstData, so it has no padding beyond that
+        assert!(size_of::<Small>() == size_of::<usize>() + 16);
+        assert!(size_of::<SmallDst<u8, u8, 0>>() == size_of::<usize>() + size_of::<Dst<u8, u8>>());
+        assert!(
+            size_of::<SmallDst<u64, u8, 64>>()
+                == size_of::<usize>() + DstData::<u64, u8>::layout_of(64).unwrap().size()
+        );
+    }
+
+    #[test]
+    fn small_dst_boundary_drops() {
+        const N: usize = 3;
+
+        for len in [0, N, N + 1] {
+            let drops = Cell::new(0);
+
+            assert_no_leaks(|| {
+                let mut small = SmallDst::<_, _, N>::new_with(DropCounter(&drops), len, |_| {
+                    DropCounter(&drops)
+                });
+
+                assert!(small.is_inline() == (len <= N));
+                assert!(small.get_footer_len() == len);
+                assert!(small.get_footer_ref_mut().len() == len);
+
+                drop(small);
+                assert!(drops.get() == len + 1);
+
+                let dst = SmallDst::<_, _, N>::new_with(DropCounter(&drops), len, |_| {
+                    DropCounter(&drops)
+                })
+                .into_dst();
+
+                assert!(dst.get_footer_len() == len);
+                assert!(drops.get() == len + 1);
+            });
+
+            assert!(drops.get() == 2 * (len + 1));
+        }
+    }
+
+    #[test]
+    fn small_dst_new_panic() {
+        let clones = Cell::new(0);
+        let drops = Cell::new(0);
+        let header_drops = Cell::new(0);
+
+        let footer = panicking_footer(4, 2, &clones, &drops);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                SmallDst::<_, _, 4>::new(DropCounter(&header_drops), &footer);
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(clones.get() == 2);
+        assert!(drops.get() == 2);
+        assert!(header_drops.get() == 1);
+    }
+}
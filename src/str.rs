@@ -0,0 +1,172 @@
+//!A [`Dst`] whose footer is a UTF-8 string
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    str::{self, Utf8Error},
+};
+
+use crate::Dst;
+
+///An owned header followed by a string in a single allocation
+///
+///The footer is always valid UTF-8, so it is only handed out as a `str`
+///
+///```
+///use custom_dst::str::DstStr;
+///
+///let mut name = DstStr::new(7u32, "header");
+///
+///name.as_str_mut().make_ascii_uppercase();
+///
+///assert_eq!(*name.get_header_ref(), 7);
+///assert_eq!(name, *"HEADER");
+///assert_eq!(name.len(), 6);
+///```
+pub struct DstStr<H> {
+    dst: Dst<H, u8>,
+}
+
+impl<H> DstStr<H> {
+    pub fn new(header: H, footer: &str) -> DstStr<H> {
+        DstStr {
+            dst: Dst::new(header, footer.as_bytes()),
+        }
+    }
+
+    ///Checks that the footer of `dst` is UTF-8, returning `dst` along with the error if it
+    ///isn't
+    pub fn from_utf8(dst: Dst<H, u8>) -> Result<DstStr<H>, (Utf8Error, Dst<H, u8>)> {
+        match str::from_utf8(dst.get_footer_ref()) {
+            Ok(_) => Ok(DstStr { dst }),
+            Err(err) => Err((err, dst)),
+        }
+    }
+
+    ///# Safety
+    ///
+    /// The footer of `dst` must be valid UTF-8
+    pub unsafe fn from_utf8_unchecked(dst: Dst<H, u8>) -> DstStr<H> {
+        DstStr { dst }
+    }
+
+    pub fn get_header_ref(&self) -> &H {
+        self.dst.get_header_ref()
+    }
+
+    pub fn get_header_ref_mut(&mut self) -> &mut H {
+        self.dst.get_header_ref_mut()
+    }
+
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(self.dst.get_footer_ref()) }
+    }
+
+    pub fn as_str_mut(&mut self) -> &mut str {
+        unsafe { str::from_utf8_unchecked_mut(self.dst.get_footer_ref_mut()) }
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.dst.get_footer_ref()
+    }
+
+    ///# Safety
+    ///
+    /// The footer must still be valid UTF-8 once the borrow ends
+    pub unsafe fn as_bytes_mut(&mut self) -> &mut [u8] {
+        self.dst.get_footer_ref_mut()
+    }
+
+    ///Returns the underlying Dst, whose footer holds the bytes of the string
+    pub fn into_dst(self) -> Dst<H, u8> {
+        self.dst
+    }
+}
+
+impl<H> Deref for DstStr<H> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<H> DerefMut for DstStr<H> {
+    fn deref_mut(&mut self) -> &mut str {
+        self.as_str_mut()
+    }
+}
+
+impl<H> fmt::Display for DstStr<H> {
+    ///Formats the string, leaving out the header
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl<H: fmt::Debug> fmt::Debug for DstStr<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DstStr")
+            .field("header", self.get_header_ref())
+            .field("footer", &self.as_str())
+            .finish()
+    }
+}
+
+impl<H> PartialEq<str> for DstStr<H> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<H> PartialEq<&str> for DstStr<H> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<H> From<DstStr<H>> for Dst<H, u8> {
+    fn from(value: DstStr<H>) -> Self {
+        value.into_dst()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::assert_no_leaks;
+
+    #[test]
+    fn dst_str_from_utf8() {
+        assert_no_leaks(|| {
+            let Ok(valid) = DstStr::from_utf8(Dst::new(1u8, "héllo".as_bytes())) else {
+                panic!("the footer is valid UTF-8");
+            };
+
+            assert!(valid == "héllo");
+            assert!(valid.chars().count() == 5);
+            assert!(format!("{valid:>7}") == "  héllo");
+
+            let Err((err, invalid)) = DstStr::from_utf8(Dst::new(2u8, &[b'a', 0xff])) else {
+                panic!("0xff is not valid UTF-8");
+            };
+
+            assert!(err.valid_up_to() == 1);
+            assert!(*invalid.get_header_ref() == 2);
+        });
+    }
+
+    #[test]
+    fn dst_str_mutation() {
+        let mut string = DstStr::new(String::from("header"), "abc");
+
+        string.get_header_ref_mut().push('!');
+        string.make_ascii_uppercase();
+
+        assert!(format!("{string:?}") == r#"DstStr { header: "header!", footer: "ABC" }"#);
+
+        let dst = string.into_dst();
+        assert!(dst.get_footer_ref() == b"ABC");
+    }
+}
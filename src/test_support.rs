@@ -0,0 +1,168 @@
+//!Helpers shared by the tests of every module
+
+use crate::Dst;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    panic,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+///Counts the allocations made by the current thread that have not been freed yet
+struct TrackingAllocator;
+
+thread_local! {
+    static LIVE_ALLOCATIONS: Cell<isize> = const { Cell::new(0) };
+}
+
+fn track_allocation(delta: isize) {
+    let _ = LIVE_ALLOCATIONS.try_with(|live| live.set(live.get() + delta));
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            track_allocation(1);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            track_allocation(1);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        track_allocation(-1);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: TrackingAllocator = TrackingAllocator;
+
+///Runs `f` and asserts that every allocation it made on this thread was freed
+pub(crate) fn assert_no_leaks<R>(f: impl FnOnce() -> R) -> R {
+    let before = LIVE_ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let after = LIVE_ALLOCATIONS.with(Cell::get);
+
+    assert!(before == after, "leaked {} allocations", after - before);
+
+    result
+}
+
+///Unwinds without running the panic hook, so no allocations are made for the message
+pub(crate) fn silent_panic() -> ! {
+    panic::resume_unwind(Box::new(()))
+}
+
+#[derive(Clone)]
+pub(crate) struct DropCounter<'a>(pub(crate) &'a Cell<usize>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[repr(align(32))]
+pub(crate) struct Aligned32(pub(crate) u8);
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct OddHeader(pub(crate) [u8; 3]);
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub(crate) struct Marker;
+
+///Panics on the `panic_at`th clone, counting every clone and drop
+pub(crate) struct PanicOnClone<'a> {
+    pub(crate) clones: &'a Cell<usize>,
+    pub(crate) drops: &'a Cell<usize>,
+    pub(crate) panic_at: usize,
+}
+
+impl Clone for PanicOnClone<'_> {
+    fn clone(&self) -> Self {
+        if self.clones.get() == self.panic_at {
+            silent_panic();
+        }
+        self.clones.set(self.clones.get() + 1);
+
+        PanicOnClone {
+            clones: self.clones,
+            drops: self.drops,
+            panic_at: self.panic_at,
+        }
+    }
+}
+
+impl Drop for PanicOnClone<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+pub(crate) fn panicking_footer<'a>(
+    len: usize,
+    panic_at: usize,
+    clones: &'a Cell<usize>,
+    drops: &'a Cell<usize>,
+) -> Vec<PanicOnClone<'a>> {
+    (0..len)
+        .map(|_| PanicOnClone {
+            clones,
+            drops,
+            panic_at,
+        })
+        .collect()
+}
+
+///An iterator whose reported length is off by `error`
+pub(crate) struct LyingIter<'a> {
+    pub(crate) remaining: usize,
+    pub(crate) error: isize,
+    pub(crate) drops: &'a Cell<usize>,
+}
+
+impl<'a> Iterator for LyingIter<'a> {
+    type Item = DropCounter<'a>;
+
+    fn next(&mut self) -> Option<DropCounter<'a>> {
+        self.remaining = self.remaining.checked_sub(1)?;
+
+        Some(DropCounter(self.drops))
+    }
+}
+
+impl ExactSizeIterator for LyingIter<'_> {
+    fn len(&self) -> usize {
+        self.remaining.strict_add_signed(self.error)
+    }
+}
+
+pub(crate) fn counter_dst<'a>(drops: &'a Cell<usize>) -> Dst<DropCounter<'a>, DropCounter<'a>> {
+    Dst::new_with(DropCounter(drops), 3, |_| DropCounter(drops))
+}
+
+///Counts its drops across threads
+pub(crate) struct SyncDropCounter<'a>(pub(crate) &'a AtomicUsize);
+
+impl Drop for SyncDropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+///A xorshift generator, for tests that want varied but reproducible data
+pub(crate) fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
@@ -0,0 +1,293 @@
+//!A [`Dst`] whose handle is a single thin pointer
+
+use std::{
+    alloc::{Global, Layout, LayoutError},
+    marker::PhantomData,
+    mem::{self, ManuallyDrop},
+    ptr::{self, NonNull},
+};
+
+use crate::{alloc_layout, dealloc_layout, Dst, DstData, DstError, PrefixGuard};
+
+///An owned [`DstData`] that stores its footer length in front of it in the allocation, so
+///that the handle is one machine word
+///
+///```
+///use custom_dst::thin::ThinDst;
+///
+///let thin = ThinDst::new(1u32, &[2u8, 3]);
+///
+///assert_eq!(size_of::<ThinDst<u32, u8>>(), size_of::<usize>());
+///assert_eq!(size_of::<Option<ThinDst<u32, u8>>>(), size_of::<usize>());
+///assert_eq!(thin.get_footer_ref(), [2, 3]);
+///```
+pub struct ThinDst<H, F> {
+    ///Points to the length, which is followed by the value
+    ptr: NonNull<usize>,
+    phantom: PhantomData<DstData<H, F>>,
+}
+
+///Frees a `ThinDst` allocation unless it is forgotten
+struct AllocationGuard {
+    ptr: NonNull<u8>,
+    layout: Layout,
+}
+
+impl Drop for AllocationGuard {
+    fn drop(&mut self) {
+        unsafe { dealloc_layout(&Global, self.ptr.as_ptr(), self.layout) }
+    }
+}
+
+impl<H, F> ThinDst<H, F> {
+    ///Returns the layout of the allocation of a value whose footer has `len` elements, and the
+    ///offset of the value in it
+    fn layout_of(len: usize) -> Result<(Layout, usize), LayoutError> {
+        let (layout, offset) = Layout::new::<usize>().extend(DstData::<H, F>::layout_of(len)?)?;
+
+        Ok((layout.pad_to_align(), offset))
+    }
+
+    ///Returns the offset of the value in the allocation, which is the same for every length
+    fn value_offset() -> usize {
+        unsafe { Self::layout_of(0).unwrap_unchecked().1 }
+    }
+
+    ///Allocates a value holding `header` and a footer of `len` elements, where element
+    ///`index` is `f(index)`
+    ///
+    ///If `f` panics, the header and the elements written so far are dropped and the
+    ///allocation is freed
+    pub fn new_with(header: H, len: usize, mut f: impl FnMut(usize) -> F) -> ThinDst<H, F> {
+        let (layout, offset) =
+            Self::layout_of(len).unwrap_or_else(|err| DstError::from(err).handle());
+
+        let ptr = alloc_layout(&Global, layout).unwrap_or_else(|err| err.handle());
+        let allocation = AllocationGuard {
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            layout,
+        };
+
+        unsafe {
+            ptr.cast::<usize>().write(len);
+
+            let value = ptr::from_raw_parts_mut::<DstData<H, F>>(ptr.add(offset), len);
+
+            let mut guard = PrefixGuard::new(DstData::get_footer_slice(value).as_mut_ptr());
+            for index in 0..len {
+                guard.push(f(index));
+            }
+            guard.finish();
+
+            DstData::get_header_ptr(value).write(header);
+        }
+
+        mem::forget(allocation);
+
+        ThinDst {
+            ptr: unsafe { NonNull::new_unchecked(ptr.cast::<usize>()) },
+            phantom: PhantomData,
+        }
+    }
+
+    ///Allocates a value holding `header` and a clone of every element of `footer`
+    pub fn new(header: H, footer: &[F]) -> ThinDst<H, F>
+    where
+        F: Clone,
+    {
+        Self::new_with(header, footer.len(), |index| footer[index].clone())
+    }
+
+    fn value_ptr(&self) -> NonNull<DstData<H, F>> {
+        unsafe {
+            NonNull::from_raw_parts(
+                self.ptr.byte_add(Self::value_offset()),
+                self.get_footer_len(),
+            )
+        }
+    }
+
+    pub fn get_header_ref(&self) -> &H {
+        unsafe { self.value_ptr().as_ref().get_header() }
+    }
+
+    pub fn get_header_ref_mut(&mut self) -> &mut H {
+        unsafe { self.value_ptr().as_mut().get_header_mut() }
+    }
+
+    pub fn get_footer_ref(&self) -> &[F] {
+        unsafe { self.value_ptr().as_ref().get_footer() }
+    }
+
+    pub fn get_footer_ref_mut(&mut self) -> &mut [F] {
+        unsafe { self.value_ptr().as_mut().get_footer_mut() }
+    }
+
+    pub fn get_footer_len(&self) -> usize {
+        unsafe { self.ptr.read() }
+    }
+
+    ///Returns the value as a [`DstData`] reference
+    pub fn get_data(&self) -> &DstData<H, F> {
+        unsafe { self.value_ptr().as_ref() }
+    }
+
+    ///Returns the value as a mutable [`DstData`] reference
+    pub fn get_data_mut(&mut self) -> &mut DstData<H, F> {
+        unsafe { self.value_ptr().as_mut() }
+    }
+
+    ///Frees the allocation without dropping the value
+    unsafe fn dealloc(&self) {
+        unsafe {
+            let (layout, _) = Self::layout_of(self.get_footer_len()).unwrap_unchecked();
+
+            dealloc_layout(&Global, self.ptr.cast::<u8>().as_ptr(), layout);
+        }
+    }
+}
+
+impl<H, F> From<Dst<H, F>> for ThinDst<H, F> {
+    ///Moves the value into a new allocation with room for the length
+    fn from(value: Dst<H, F>) -> Self {
+        let len = value.get_footer_len();
+        let (layout, offset) = unsafe { Self::layout_of(len).unwrap_unchecked() };
+
+        let ptr = alloc_layout(&Global, layout).unwrap_or_else(|err| err.handle());
+        let src = value.into_raw();
+
+        unsafe {
+            ptr.cast::<usize>().write(len);
+            ptr::copy_nonoverlapping(src.cast::<u8>(), ptr.add(offset), mem::size_of_val_raw(src));
+
+            dealloc_layout(&Global, src.cast::<u8>(), Layout::for_value_raw(src));
+
+            ThinDst {
+                ptr: NonNull::new_unchecked(ptr.cast::<usize>()),
+                phantom: PhantomData,
+            }
+        }
+    }
+}
+
+impl<H, F> From<ThinDst<H, F>> for Dst<H, F> {
+    ///Moves the value into a new allocation without the length
+    fn from(value: ThinDst<H, F>) -> Self {
+        let ptr = unsafe { DstData::alloc_self(value.get_footer_len(), &Global) }
+            .unwrap_or_else(|err| err.handle());
+
+        let value = ManuallyDrop::new(value);
+        let src = value.value_ptr().as_ptr();
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                src.cast::<u8>(),
+                ptr.as_ptr().cast::<u8>(),
+                mem::size_of_val_raw(src),
+            );
+
+            value.dealloc();
+        }
+
+        Dst {
+            ptr,
+            phantom: PhantomData,
+            alloc: Global,
+        }
+    }
+}
+
+impl<H, F> Drop for ThinDst<H, F> {
+    fn drop(&mut self) {
+        unsafe {
+            ptr::drop_in_place(self.value_ptr().as_ptr());
+
+            self.dealloc();
+        }
+    }
+}
+
+unsafe impl<H: Send, F: Send> Send for ThinDst<H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for ThinDst<H, F> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{
+        assert_no_leaks, counter_dst, silent_panic, Aligned32, DropCounter, Marker, OddHeader,
+    };
+    use std::{
+        cell::Cell,
+        panic::{self, AssertUnwindSafe},
+    };
+
+    #[test]
+    fn thin_dst_is_one_word() {
+        assert!(mem::size_of::<ThinDst<Aligned32, u8>>() == mem::size_of::<usize>());
+        assert!(mem::size_of::<Option<ThinDst<u8, String>>>() == mem::size_of::<usize>());
+    }
+
+    #[test]
+    fn thin_dst_high_alignment() {
+        assert_no_leaks(|| {
+            let mut thin = ThinDst::new(Aligned32(1), &[OddHeader([2, 3, 4]); 5]);
+
+            assert!((thin.get_header_ref() as *const Aligned32).is_aligned());
+            assert!(thin.get_header_ref().0 == 1);
+            assert!(thin.get_footer_ref() == [OddHeader([2, 3, 4]); 5]);
+
+            thin.get_footer_ref_mut()[4].0[0] = 9;
+            assert!(thin.get_data().get_footer()[4].0 == [9, 3, 4]);
+
+            let empty = ThinDst::<Aligned32, Aligned32>::new(Aligned32(5), &[]);
+            assert!(empty.get_footer_len() == 0 && empty.get_header_ref().0 == 5);
+
+            let zst = ThinDst::new(Marker, &[Marker; 3]);
+            assert!(zst.get_footer_len() == 3);
+        });
+    }
+
+    #[test]
+    fn thin_dst_conversions() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let thin = ThinDst::from(counter_dst(&drops));
+
+            assert!(thin.get_footer_len() == 3);
+
+            let dst = Dst::from(thin);
+
+            assert!(dst.get_footer_len() == 3);
+            assert!(drops.get() == 0);
+
+            let aligned = Dst::from(ThinDst::from(Dst::new(Aligned32(7), &[Aligned32(8)])));
+            assert!(aligned.get_footer_ref() == [Aligned32(8)]);
+        });
+
+        assert!(drops.get() == 4);
+    }
+
+    #[test]
+    fn thin_dst_new_with_panic() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                ThinDst::new_with(DropCounter(&drops), 4, |index| {
+                    if index == 2 {
+                        silent_panic();
+                    }
+
+                    DropCounter(&drops)
+                })
+            }));
+
+            assert!(result.is_err());
+        });
+
+        assert!(drops.get() == 3);
+    }
+}
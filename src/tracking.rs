@@ -0,0 +1,226 @@
+use std::{error::Error, fmt, mem, ops::Range};
+
+///The parts of an uninitialized Dst or Dst array that were never written
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct UninitializedReport {
+    ///The indices of the members whose header was never written
+    pub missing_headers: Vec<usize>,
+    ///The `(member index, footer index)` pairs of the footer elements that were never written
+    pub missing_footer_elements: Vec<(usize, usize)>,
+}
+
+impl UninitializedReport {
+    pub fn is_empty(&self) -> bool {
+        self.missing_headers.is_empty() && self.missing_footer_elements.is_empty()
+    }
+}
+
+impl fmt::Display for UninitializedReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "missing headers at {:?} and footer elements at {:?}",
+            self.missing_headers, self.missing_footer_elements
+        )
+    }
+}
+
+impl Error for UninitializedReport {}
+
+///Records which headers and footer elements of an allocation have been written
+///
+///Writes made through a mutable pointer getter can't be observed, so the slots behind such a
+///pointer are treated as written as soon as the pointer is handed out
+#[cfg(any(debug_assertions, feature = "init-tracking"))]
+#[derive(Debug)]
+pub(crate) struct InitTracker {
+    ///`None` if the allocation came from a raw pointer and its state is unknown
+    state: Option<TrackerState>,
+}
+
+#[cfg(any(debug_assertions, feature = "init-tracking"))]
+#[derive(Debug)]
+struct TrackerState {
+    footer_len: usize,
+    headers: SlotBits,
+    footers: SlotBits,
+}
+
+///One bit per slot, set once the slot is written
+///
+///Slots of a zero-sized type aren't tracked, so that the bits never take more memory than the
+///allocation they describe. They are never reported missing, and never reported overwritten
+#[cfg(any(debug_assertions, feature = "init-tracking"))]
+#[derive(Debug)]
+struct SlotBits {
+    ///`None` if the slots aren't tracked
+    words: Option<Vec<u64>>,
+    len: usize,
+}
+
+#[cfg(any(debug_assertions, feature = "init-tracking"))]
+impl SlotBits {
+    ///Tracks `len` slots of type `T`, or none if `T` is zero-sized or `len` overflowed
+    fn new<T>(len: Option<usize>) -> Self {
+        match len {
+            Some(len) if mem::size_of::<T>() != 0 => SlotBits {
+                words: Some(vec![0; len.div_ceil(64)]),
+                len,
+            },
+            _ => SlotBits {
+                words: None,
+                len: 0,
+            },
+        }
+    }
+
+    ///Returns whether the slot is tracked and was written
+    fn get(&self, index: usize) -> bool {
+        match &self.words {
+            Some(words) => words[index / 64] & (1 << (index % 64)) != 0,
+            None => false,
+        }
+    }
+
+    fn set_range(&mut self, range: Range<usize>) {
+        if let Some(words) = &mut self.words {
+            for index in range {
+                words[index / 64] |= 1 << (index % 64);
+            }
+        }
+    }
+
+    ///Returns the tracked slots that were never written
+    fn missing(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(|&index| !self.get(index))
+    }
+}
+
+#[cfg(any(debug_assertions, feature = "init-tracking"))]
+impl InitTracker {
+    ///Tracks `array_size` members of `H` headers and `footer_len` `F` footer elements
+    pub(crate) fn new<H, F>(array_size: usize, footer_len: usize) -> Self {
+        InitTracker {
+            state: Some(TrackerState {
+                footer_len,
+                headers: SlotBits::new::<H>(Some(array_size)),
+                footers: SlotBits::new::<F>(array_size.checked_mul(footer_len)),
+            }),
+        }
+    }
+
+    pub(crate) fn untracked() -> Self {
+        InitTracker { state: None }
+    }
+
+    ///Panics if `needs_drop` and the header was already written, as overwriting it leaks
+    #[track_caller]
+    pub(crate) fn check_header(&self, arr_index: usize, needs_drop: bool) {
+        if let Some(state) = &self.state {
+            assert!(
+                !(needs_drop && state.headers.get(arr_index)),
+                "overwrote the initialized header of member {arr_index}, leaking it"
+            );
+        }
+    }
+
+    pub(crate) fn mark_header(&mut self, arr_index: usize) {
+        if let Some(state) = &mut self.state {
+            state.headers.set_range(arr_index..arr_index + 1);
+        }
+    }
+
+    ///Panics if `needs_drop` and any footer element in `range` was already written, as
+    ///overwriting it leaks
+    #[track_caller]
+    pub(crate) fn check_footer(&self, arr_index: usize, range: Range<usize>, needs_drop: bool) {
+        if let Some(state) = &self.state {
+            if !needs_drop {
+                return;
+            }
+
+            let start = arr_index * state.footer_len;
+            if let Some(footer_index) = range
+                .clone()
+                .find(|footer_index| state.footers.get(start + footer_index))
+            {
+                panic!(
+                    "overwrote the initialized footer element {footer_index} of member \
+                     {arr_index}, leaking it"
+                );
+            }
+        }
+    }
+
+    pub(crate) fn mark_footer(&mut self, arr_index: usize, range: Range<usize>) {
+        if let Some(state) = &mut self.state {
+            let start = arr_index * state.footer_len;
+            state
+                .footers
+                .set_range(start + range.start..start + range.end);
+        }
+    }
+
+    ///Returns the parts that were never written, or `None` if everything was written or the
+    ///state is unknown
+    pub(crate) fn report(&self) -> Option<UninitializedReport> {
+        let state = self.state.as_ref()?;
+
+        let report = UninitializedReport {
+            missing_headers: state.headers.missing().collect(),
+            missing_footer_elements: state
+                .footers
+                .missing()
+                .map(|index| (index / state.footer_len, index % state.footer_len))
+                .collect(),
+        };
+
+        if report.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+
+    ///Panics in debug builds if anything was never written
+    #[track_caller]
+    pub(crate) fn debug_assert_init(&self) {
+        #[cfg(debug_assertions)]
+        if let Some(report) = self.report() {
+            panic!("assume_init called before everything was written: {report}");
+        }
+    }
+}
+
+///Stands in for the tracker when tracking is disabled, compiling every call away
+#[cfg(not(any(debug_assertions, feature = "init-tracking")))]
+#[derive(Debug)]
+pub(crate) struct InitTracker;
+
+#[cfg(not(any(debug_assertions, feature = "init-tracking")))]
+impl InitTracker {
+    #[inline(always)]
+    pub(crate) fn new<H, F>(_array_size: usize, _footer_len: usize) -> Self {
+        InitTracker
+    }
+
+    #[inline(always)]
+    pub(crate) fn untracked() -> Self {
+        InitTracker
+    }
+
+    #[inline(always)]
+    pub(crate) fn check_header(&self, _arr_index: usize, _needs_drop: bool) {}
+
+    #[inline(always)]
+    pub(crate) fn mark_header(&mut self, _arr_index: usize) {}
+
+    #[inline(always)]
+    pub(crate) fn check_footer(&self, _arr_index: usize, _range: Range<usize>, _needs_drop: bool) {}
+
+    #[inline(always)]
+    pub(crate) fn mark_footer(&mut self, _arr_index: usize, _range: Range<usize>) {}
+
+    #[inline(always)]
+    pub(crate) fn debug_assert_init(&self) {}
+}
@@ -0,0 +1,428 @@
+//!A growable array of [`DstData`]s that share a footer length
+
+use std::{
+    alloc::Global,
+    clone::CloneToUninit,
+    cmp,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    ptr::{self, drop_in_place, NonNull},
+};
+
+use crate::{
+    check_index, dealloc_layout, DstData, DstError, DstIter, DstIterMut, DstSlice, DstSliceMut,
+};
+
+///The capacity a vec grows to the first time it allocates
+const MIN_CAPACITY: usize = 4;
+
+///A [`DstArray`](crate::DstArray) that can grow, whose footer length is chosen when it is
+///created
+///
+///```
+///use custom_dst::vec::DstVec;
+///
+///let mut records = DstVec::new(2);
+///
+///records.push("first", &[1, 2]);
+///records.push_parts("second", vec![3, 4]);
+///
+///assert_eq!(records.len(), 2);
+///assert_eq!(records[1].get_footer(), [3, 4]);
+///assert_eq!(records.pop(), Some(("second", vec![3, 4])));
+///```
+pub struct DstVec<H, F> {
+    ///Carries the footer length as its metadata, and dangles while nothing is allocated
+    ptr: NonNull<DstData<H, F>>,
+    len: usize,
+    ///Always `usize::MAX` if the members are zero sized, as they never need memory
+    cap: usize,
+    stride: usize,
+    phantom: PhantomData<DstData<H, F>>,
+}
+
+impl<H, F> DstVec<H, F> {
+    ///Creates an empty vec whose members will have `footer_len` footer elements, without
+    ///allocating
+    pub fn new(footer_len: usize) -> DstVec<H, F> {
+        Self::with_capacity(footer_len, 0)
+    }
+
+    ///Creates an empty vec with room for `capacity` members of `footer_len` footer elements
+    pub fn with_capacity(footer_len: usize, capacity: usize) -> DstVec<H, F> {
+        let (ptr, stride) = unsafe { DstData::alloc_self_array(footer_len, capacity, &Global) }
+            .unwrap_or_else(|err| err.handle());
+
+        DstVec {
+            ptr,
+            len: 0,
+            cap: if stride == 0 { usize::MAX } else { capacity },
+            stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns the number of members in the vec
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    ///Returns the number of members the vec can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    ///Returns the number of footer elements of each member
+    pub fn get_footer_len(&self) -> usize {
+        unsafe { DstData::get_len(self.ptr.as_ptr()) }
+    }
+
+    ///Makes room for at least `additional` more members, at least doubling the capacity if
+    ///it has to reallocate
+    pub fn reserve(&mut self, additional: usize) {
+        let required = self
+            .len
+            .checked_add(additional)
+            .unwrap_or_else(|| DstError::LayoutOverflow.handle());
+
+        if required <= self.cap {
+            return;
+        }
+
+        let capacity = cmp::max(cmp::max(self.cap.saturating_mul(2), required), MIN_CAPACITY);
+
+        self.reallocate(capacity);
+    }
+
+    ///Moves the members into a new allocation with room for `capacity` members
+    fn reallocate(&mut self, capacity: usize) {
+        let (ptr, _) =
+            unsafe { DstData::alloc_self_array(self.get_footer_len(), capacity, &Global) }
+                .unwrap_or_else(|err| err.handle());
+
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.ptr.cast::<u8>().as_ptr(),
+                ptr.cast::<u8>().as_ptr(),
+                self.stride * self.len,
+            );
+
+            self.deallocate();
+        }
+
+        self.ptr = ptr;
+        self.cap = capacity;
+    }
+
+    ///Frees the allocation without dropping any member
+    unsafe fn deallocate(&mut self) {
+        unsafe {
+            let layout = DstData::array_layout_of_raw(self.ptr.as_ptr(), self.cap, self.stride);
+
+            dealloc_layout(&Global, self.ptr.cast::<u8>().as_ptr(), layout);
+        }
+    }
+
+    ///Returns a pointer to member `index`, which may be past the initialized members
+    ///
+    ///# Safety
+    ///
+    /// `index` must be at most the capacity of the vec
+    unsafe fn member_ptr(&self, index: usize) -> NonNull<DstData<H, F>> {
+        unsafe { self.ptr.byte_add(self.stride * index) }
+    }
+
+    #[track_caller]
+    fn check_footer_len(&self, len: usize) {
+        let footer_len = self.get_footer_len();
+
+        assert!(
+            len == footer_len,
+            "the footer has {len} elements but the members of the vec have {footer_len}"
+        );
+    }
+
+    ///Appends a member holding `header` and a clone of every element of `footer`
+    ///
+    ///If a clone panics, the elements cloned so far and `header` are dropped and the vec is
+    ///left unchanged
+    ///
+    ///# Panics
+    ///
+    ///Panics if `footer` doesn't have exactly [`DstVec::get_footer_len`] elements
+    #[track_caller]
+    pub fn push(&mut self, header: H, footer: &[F])
+    where
+        F: Clone,
+    {
+        self.check_footer_len(footer.len());
+        self.reserve(1);
+
+        unsafe {
+            let ptr = self.member_ptr(self.len).as_ptr();
+
+            footer.clone_to_uninit(DstData::get_footer_slice(ptr).as_mut_ptr().cast::<u8>());
+            DstData::get_header_ptr(ptr).write(header);
+        }
+
+        self.len += 1;
+    }
+
+    ///Appends a member holding `header` and the elements of `footer`, moving them rather than
+    ///cloning them
+    ///
+    ///# Panics
+    ///
+    ///Panics if `footer` doesn't have exactly [`DstVec::get_footer_len`] elements
+    #[track_caller]
+    pub fn push_parts(&mut self, header: H, mut footer: Vec<F>) {
+        self.check_footer_len(footer.len());
+        self.reserve(1);
+
+        unsafe {
+            let ptr = self.member_ptr(self.len).as_ptr();
+
+            ptr::copy_nonoverlapping(
+                footer.as_ptr(),
+                DstData::get_footer_slice(ptr).as_mut_ptr(),
+                footer.len(),
+            );
+            //The elements now belong to the vec, so only the Vec's buffer may be freed
+            footer.set_len(0);
+
+            DstData::get_header_ptr(ptr).write(header);
+        }
+
+        self.len += 1;
+    }
+
+    ///Removes the last member, returning its header and a Vec holding its footer elements
+    pub fn pop(&mut self) -> Option<(H, Vec<F>)> {
+        let index = self.len.checked_sub(1)?;
+
+        let parts = unsafe { DstData::read_parts(self.member_ptr(index).as_ptr()) };
+        self.len = index;
+
+        Some(parts)
+    }
+
+    ///Drops every member, keeping the allocation
+    pub fn clear(&mut self) {
+        let len = self.len;
+
+        //Leaks the remaining members rather than dropping one twice if a destructor panics
+        self.len = 0;
+
+        for index in 0..len {
+            unsafe { drop_in_place(self.member_ptr(index).as_ptr()) };
+        }
+    }
+
+    ///Returns member `index`, or `None` if it is out of bounds
+    pub fn get(&self, index: usize) -> Option<&DstData<H, F>> {
+        self.as_slice().get(index)
+    }
+
+    ///Returns member `index` mutably, or `None` if it is out of bounds
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut DstData<H, F>> {
+        if index < self.len {
+            Some(unsafe { self.member_ptr(index).as_mut() })
+        } else {
+            None
+        }
+    }
+
+    pub fn as_ptr(&self) -> *const DstData<H, F> {
+        self.ptr.as_ptr().cast_const()
+    }
+
+    ///Returns a view of every member
+    pub fn as_slice(&self) -> DstSlice<'_, H, F> {
+        DstSlice {
+            start: self.ptr,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns a mutable view of every member
+    pub fn as_mut_slice(&mut self) -> DstSliceMut<'_, H, F> {
+        DstSliceMut {
+            start: self.ptr,
+            len: self.len,
+            stride: self.stride,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Returns an iterator over the members of the vec
+    pub fn iter(&self) -> DstIter<'_, H, F> {
+        self.as_slice().iter()
+    }
+
+    ///Returns an iterator over mutable references to the members of the vec
+    pub fn iter_mut(&mut self) -> DstIterMut<'_, H, F> {
+        self.as_mut_slice().into_iter()
+    }
+}
+
+unsafe impl<H: Send, F: Send> Send for DstVec<H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstVec<H, F> {}
+
+impl<H, F> Drop for DstVec<H, F> {
+    fn drop(&mut self) {
+        for index in 0..self.len {
+            unsafe { drop_in_place(self.member_ptr(index).as_ptr()) };
+        }
+
+        unsafe { self.deallocate() };
+    }
+}
+
+impl<H, F> Index<usize> for DstVec<H, F> {
+    type Output = DstData<H, F>;
+
+    #[track_caller]
+    fn index(&self, index: usize) -> &DstData<H, F> {
+        check_index(index, self.len);
+
+        unsafe { self.member_ptr(index).as_ref() }
+    }
+}
+
+impl<H, F> IndexMut<usize> for DstVec<H, F> {
+    #[track_caller]
+    fn index_mut(&mut self, index: usize) -> &mut DstData<H, F> {
+        check_index(index, self.len);
+
+        unsafe { self.member_ptr(index).as_mut() }
+    }
+}
+
+impl<'a, H, F> IntoIterator for &'a DstVec<H, F> {
+    type Item = &'a DstData<H, F>;
+    type IntoIter = DstIter<'a, H, F>;
+
+    fn into_iter(self) -> DstIter<'a, H, F> {
+        self.iter()
+    }
+}
+
+impl<'a, H, F> IntoIterator for &'a mut DstVec<H, F> {
+    type Item = &'a mut DstData<H, F>;
+    type IntoIter = DstIterMut<'a, H, F>;
+
+    fn into_iter(self) -> DstIterMut<'a, H, F> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_support::{assert_no_leaks, DropCounter};
+    use std::cell::Cell;
+
+    #[test]
+    fn vec_growth() {
+        let mut vec = DstVec::new(3);
+        assert!(vec.capacity() == 0);
+        assert!(vec.pop().is_none());
+
+        for index in 0..100u32 {
+            vec.push(index, &[index as u16, 1, 2]);
+        }
+
+        assert!(vec.len() == 100);
+        assert!(vec.capacity() >= 100);
+        for (index, member) in vec.iter().enumerate() {
+            assert!(*member.get_header() == index as u32);
+            assert!(member.get_footer() == [index as u16, 1, 2]);
+        }
+
+        vec[7].get_footer_mut()[2] = 9;
+        assert!(vec.get(7).unwrap().get_footer() == [7, 1, 9]);
+        assert!(vec.get(100).is_none());
+        assert!(vec.as_slice().len() == 100);
+        assert!(vec.pop() == Some((99, vec![99, 1, 2])));
+
+        let mut vec = DstVec::<(), ()>::new(5);
+        for _ in 0..10 {
+            vec.push_parts((), vec![(); 5]);
+        }
+        assert!(vec.len() == 10);
+        assert!(vec.capacity() == usize::MAX);
+    }
+
+    #[test]
+    fn vec_reallocation() {
+        let mut vec = DstVec::with_capacity(2, 2);
+        assert!(vec.capacity() == 2);
+
+        vec.push(String::from("a"), &[String::from("b"), String::from("c")]);
+        vec.push_parts(
+            String::from("d"),
+            vec![String::from("e"), String::from("f")],
+        );
+
+        let before = vec.as_ptr();
+        vec.reserve(1);
+        assert!(vec.capacity() >= 3);
+        assert!(!ptr::addr_eq(vec.as_ptr(), before));
+
+        assert!(vec[0].get_header() == "a");
+        assert!(vec[1].get_footer() == ["e", "f"]);
+
+        let capacity = vec.capacity();
+        let after = vec.as_ptr();
+        vec.reserve(capacity - vec.len());
+        assert!(ptr::addr_eq(vec.as_ptr(), after));
+    }
+
+    #[test]
+    fn vec_drops() {
+        let drops = Cell::new(0);
+
+        assert_no_leaks(|| {
+            let mut vec = DstVec::new(2);
+            for _ in 0..5 {
+                vec.push_parts(
+                    DropCounter(&drops),
+                    vec![DropCounter(&drops), DropCounter(&drops)],
+                );
+            }
+
+            let popped = vec.pop();
+            assert!(drops.get() == 0);
+            drop(popped);
+            assert!(drops.get() == 3);
+
+            vec.clear();
+            assert!(drops.get() == 15);
+            assert!(vec.is_empty());
+
+            vec.push(
+                DropCounter(&drops),
+                &[DropCounter(&drops), DropCounter(&drops)],
+            );
+        });
+
+        //The footer pushed by clone drops both its clones and the originals
+        assert!(drops.get() == 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "the footer has 1 elements but the members of the vec have 2")]
+    fn vec_footer_len_mismatch() {
+        let mut vec = DstVec::new(2);
+
+        vec.push(0u8, &[1u8]);
+    }
+}
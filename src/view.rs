@@ -0,0 +1,267 @@
+use std::{alloc::Allocator, marker::PhantomData, ptr::NonNull};
+
+use crate::{Dst, DstData};
+
+///A shared view of a [`DstData`] stored in memory the caller owns
+///
+///```
+///use custom_dst::DstRef;
+///
+///#[repr(C)]
+///struct Record {
+///    header: u32,
+///    footer: [u16; 3],
+///}
+///
+///let record = Record {
+///    header: 1,
+///    footer: [2, 3, 4],
+///};
+///
+/////The pointer must cover the whole record, not just the header field
+///let ptr = (&record as *const Record).cast::<u32>();
+///let view = unsafe { DstRef::<u32, u16>::from_raw_parts(ptr, 3) };
+///
+///assert_eq!(*view.get_header(), 1);
+///assert_eq!(view.get_footer(), [2, 3, 4]);
+///```
+pub struct DstRef<'a, H, F> {
+    ptr: NonNull<DstData<H, F>>,
+    phantom: PhantomData<&'a DstData<H, F>>,
+}
+
+impl<'a, H, F> DstRef<'a, H, F> {
+    ///Creates a view of the header at `ptr` followed by a footer of `len` elements
+    ///
+    ///# Safety
+    ///
+    /// `ptr` must point to an initialized header followed by `len` initialized footer
+    /// elements, laid out and aligned like a `DstData<H, F>`, and must be allowed to access
+    /// all of it. The memory must stay valid and must not be mutated for `'a`
+    pub unsafe fn from_raw_parts(ptr: *const H, len: usize) -> DstRef<'a, H, F> {
+        DstRef {
+            ptr: NonNull::from_raw_parts(unsafe { NonNull::new_unchecked(ptr.cast_mut()) }, len),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn get_header(&self) -> &'a H {
+        unsafe { self.ptr.as_ref().get_header() }
+    }
+
+    pub fn get_footer(&self) -> &'a [F] {
+        unsafe { self.ptr.as_ref().get_footer() }
+    }
+
+    pub fn get_parts(&self) -> (&'a H, &'a [F]) {
+        (self.get_header(), self.get_footer())
+    }
+
+    ///Returns the viewed value as a [`DstData`] reference
+    pub fn get_data(&self) -> &'a DstData<H, F> {
+        unsafe { self.ptr.as_ref() }
+    }
+
+    ///Clones the viewed value into a new [`Dst`]
+    pub fn to_owned(&self) -> Dst<H, F>
+    where
+        H: Clone,
+        F: Clone,
+    {
+        Dst::new(self.get_header().clone(), self.get_footer())
+    }
+}
+
+impl<H, F> Clone for DstRef<'_, H, F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H, F> Copy for DstRef<'_, H, F> {}
+
+impl<'a, H, F> From<&'a DstData<H, F>> for DstRef<'a, H, F> {
+    fn from(value: &'a DstData<H, F>) -> Self {
+        DstRef {
+            ptr: NonNull::from(value),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<'a, H, F> From<DstMut<'a, H, F>> for DstRef<'a, H, F> {
+    fn from(value: DstMut<'a, H, F>) -> Self {
+        DstRef {
+            ptr: value.ptr,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<H: PartialEq, F: PartialEq> PartialEq for DstRef<'_, H, F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.get_parts() == other.get_parts()
+    }
+}
+
+impl<H: PartialEq, F: PartialEq, A: Allocator> PartialEq<Dst<H, F, A>> for DstRef<'_, H, F> {
+    fn eq(&self, other: &Dst<H, F, A>) -> bool {
+        self.get_parts() == (other.get_header_ref(), other.get_footer_ref())
+    }
+}
+
+impl<H: PartialEq, F: PartialEq, A: Allocator> PartialEq<DstRef<'_, H, F>> for Dst<H, F, A> {
+    fn eq(&self, other: &DstRef<'_, H, F>) -> bool {
+        other == self
+    }
+}
+
+unsafe impl<H: Sync, F: Sync> Send for DstRef<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstRef<'_, H, F> {}
+
+///A mutable view of a [`DstData`] stored in memory the caller owns
+pub struct DstMut<'a, H, F> {
+    ptr: NonNull<DstData<H, F>>,
+    phantom: PhantomData<&'a mut DstData<H, F>>,
+}
+
+impl<'a, H, F> DstMut<'a, H, F> {
+    ///Creates a mutable view of the header at `ptr` followed by a footer of `len` elements
+    ///
+    ///# Safety
+    ///
+    /// `ptr` must point to an initialized header followed by `len` initialized footer
+    /// elements, laid out and aligned like a `DstData<H, F>`, and must be allowed to access
+    /// all of it. The memory must stay valid and must not be accessed through any other
+    /// pointer for `'a`
+    pub unsafe fn from_raw_parts(ptr: *mut H, len: usize) -> DstMut<'a, H, F> {
+        DstMut {
+            ptr: NonNull::from_raw_parts(unsafe { NonNull::new_unchecked(ptr) }, len),
+            phantom: PhantomData,
+        }
+    }
+
+    pub fn get_header(&self) -> &H {
+        unsafe { self.ptr.as_ref().get_header() }
+    }
+
+    pub fn get_header_mut(&mut self) -> &mut H {
+        unsafe { self.ptr.as_mut().get_header_mut() }
+    }
+
+    pub fn get_footer(&self) -> &[F] {
+        unsafe { self.ptr.as_ref().get_footer() }
+    }
+
+    pub fn get_footer_mut(&mut self) -> &mut [F] {
+        unsafe { self.ptr.as_mut().get_footer_mut() }
+    }
+
+    pub fn get_parts(&self) -> (&H, &[F]) {
+        (self.get_header(), self.get_footer())
+    }
+
+    pub fn get_parts_mut(&mut self) -> (&mut H, &mut [F]) {
+        unsafe { self.ptr.as_mut().get_parts_mut() }
+    }
+
+    ///Returns the viewed value as a [`DstData`] reference
+    pub fn get_data_mut(&mut self) -> &mut DstData<H, F> {
+        unsafe { self.ptr.as_mut() }
+    }
+
+    ///Reborrows the view as a shared one
+    pub fn to_ref(&self) -> DstRef<'_, H, F> {
+        DstRef {
+            ptr: self.ptr,
+            phantom: PhantomData,
+        }
+    }
+
+    ///Clones the viewed value into a new [`Dst`]
+    pub fn to_owned(&self) -> Dst<H, F>
+    where
+        H: Clone,
+        F: Clone,
+    {
+        self.to_ref().to_owned()
+    }
+}
+
+impl<'a, H, F> From<&'a mut DstData<H, F>> for DstMut<'a, H, F> {
+    fn from(value: &'a mut DstData<H, F>) -> Self {
+        DstMut {
+            ptr: NonNull::from(value),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<H: PartialEq, F: PartialEq, A: Allocator> PartialEq<Dst<H, F, A>> for DstMut<'_, H, F> {
+    fn eq(&self, other: &Dst<H, F, A>) -> bool {
+        self.to_ref() == *other
+    }
+}
+
+unsafe impl<H: Send, F: Send> Send for DstMut<'_, H, F> {}
+
+unsafe impl<H: Sync, F: Sync> Sync for DstMut<'_, H, F> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Dst, DstMut, DstRef};
+
+    #[repr(C)]
+    struct Record {
+        header: u32,
+        footer: [u16; 3],
+    }
+
+    #[test]
+    fn dst_ref_over_caller_memory() {
+        let record = Record {
+            header: 1,
+            footer: [2, 3, 4],
+        };
+
+        let ptr = (&record as *const Record).cast::<u32>();
+        let view = unsafe { DstRef::<u32, u16>::from_raw_parts(ptr, 3) };
+        let copy = view;
+
+        assert!(copy.get_parts() == (&1, &[2, 3, 4][..]));
+        assert!(view == copy);
+
+        let owned = view.to_owned();
+
+        assert!(view == owned && owned == view);
+        assert!(DstRef::from(view.get_data()) == Dst::new(1, &[2u16, 3, 4]));
+        assert!(view != Dst::new(1, &[2u16, 3]));
+    }
+
+    #[test]
+    fn dst_mut_over_caller_memory() {
+        let mut record = Record {
+            header: 1,
+            footer: [2, 3, 4],
+        };
+
+        {
+            let ptr = (&mut record as *mut Record).cast::<u32>();
+            let mut view = unsafe { DstMut::<u32, u16>::from_raw_parts(ptr, 3) };
+
+            *view.get_header_mut() = 10;
+            view.get_footer_mut()[2] = 40;
+
+            let (header, footer) = view.get_parts_mut();
+            *header += 1;
+            footer[0] += 1;
+
+            assert!(view == Dst::new(11, &[3u16, 3, 40]));
+            assert!(view.to_owned() == view.to_ref());
+        }
+
+        assert!(record.header == 11);
+        assert!(record.footer == [3, 3, 40]);
+    }
+}